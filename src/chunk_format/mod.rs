@@ -5,14 +5,19 @@
 // - 2023-12-17
 //
 // ## File Version
-// - 1.0.0
+// - 1.0.2
 //
 // ## Changelog
 // - 1.0.0: Initial version
+// - 1.0.1: Chunks are checked against the chunk schema, corrupted chunks are skipped with a warning [mrmarkolinus:2026-10-16]
+// - 1.0.2: Tags are read through borrowing accessors, nothing is copied [mrmarkolinus:2026-10-16]
 
 use crate::nbt_tag;
+use crate::nbt_schema;
 use crate::blocks;
 
+use log::warn;
+
 use std::collections::{HashMap, HashSet};
 
 /// Inspects Minecraft chunks and extracts block positions based on resource locations.
@@ -34,6 +39,8 @@ use std::collections::{HashMap, HashSet};
 /// string, and the value is a vector of Coordinates structs representing the positions of 
 /// the blocks in the Minecraft world.
 /// 
+/// Chunks that do not match `nbt_schema::chunk()` are skipped, with a warning per violation.
+/// 
 pub fn inspect_chunks<'a>(block_resource_location: Vec::<String>, tag_compounds_list: &'a Vec<nbt_tag::NbtTagCompound>) -> HashMap::<String, Vec::<blocks::MinecraftBlock>> {
     // Refer to https://minecraft.fandom.com/wiki/Chunk_format to see how a block is saved in a chunk
     //sections (TAG List)
@@ -42,16 +49,33 @@ pub fn inspect_chunks<'a>(block_resource_location: Vec::<String>, tag_compounds_
     // ---- block (TAG Compound)
     // ------ Name (TAG String)
     let mut blocks_positions_list = HashMap::<String, Vec::<blocks::MinecraftBlock>>::new();
+    let chunk_schema = nbt_schema::chunk();
+
+    for (chunk_index, tag_compound) in tag_compounds_list.iter().enumerate() {
+        // Compounds without sections are not chunks, e.g. the root of a litematic
+        if !tag_compound.values.contains_key("sections") {
+            continue;
+        }
+
+        let violations = tag_compound.validate(&chunk_schema);
+        if !violations.is_empty() {
+            for violation in violations.iter() {
+                warn!("Skipping chunk {}: {}", chunk_index, violation);
+            }
+            continue;
+        }
 
-    for tag_compound in tag_compounds_list.iter() {
         let mut chunk_pos = get_chunk_coordinates(tag_compound);
         
         if let Some(sections_tag) = tag_compound.values.get("sections") {
-            if let Some(sections_list) = sections_tag.list_as_ref(){
+            if let Some(sections_list) = sections_tag.as_list(){
                 for sections in sections_list.values.iter() {
                     if let Some(block_states_tag) = find_block_states_in_section(sections) {
-                        //TODO: replace unwraps
-                        let subchunk_y_pos = sections.compound_as_ref().unwrap().values.get("Y").unwrap().byte().unwrap().value as i32;
+                        // The chunk schema requires a Y byte in every section
+                        let subchunk_y_pos = sections.as_compound()
+                            .and_then(|section| section.get("Y"))
+                            .and_then(nbt_tag::NbtTag::as_i8)
+                            .expect("Y is checked by the chunk schema") as i32;
                         // The y position got from get_chunk_coordinates is always -4, since the chunk always starts at -4 * 16 = -64
                         // what we need is the actual subchunk position
                         chunk_pos.y = subchunk_y_pos;
@@ -126,7 +150,7 @@ pub fn get_absolute_blocks_positions<'a>   (block_states_tag: &nbt_tag::NbtTag,
                         let mut subchunk_z_pos = 0;  
 
                         for blocks_data in blocks_data_array {
-                            let palette_ids = get_palette_ids_from_data_array_element(blocks_data.clone(), data_index_bit_size);
+                            let palette_ids = get_palette_ids_from_data_array_element(*blocks_data, data_index_bit_size);
                         
                             /* #40: get the block position in the subchunk 
                             * block position is a tridimensional coordinate x,y,z. The blocks are stored with YZX order
@@ -293,12 +317,12 @@ pub fn get_block_properties(block_tag: &nbt_tag::NbtTag) -> HashMap<String, Stri
     
     let mut block_properties = HashMap::<String, String>::new();
     
-    if let Some(block_compound) = block_tag.compound_as_ref() {
+    if let Some(block_compound) = block_tag.as_compound() {
         if let Some(block_properties_tag) = block_compound.values.get("Properties") {
-            if let Some(block_properties_compound) = block_properties_tag.compound_as_ref() {
+            if let Some(block_properties_compound) = block_properties_tag.as_compound() {
                 for (key, value) in block_properties_compound.values.iter() {
-                    if value.ty() == nbt_tag::NbtTagType::String {
-                        block_properties.insert(key.to_string(), value.string().unwrap().value);
+                    if let Some(property) = value.as_str() {
+                        block_properties.insert(key.to_string(), property.to_string());
                     }
                 }
             }
@@ -404,22 +428,22 @@ pub fn get_chunk_coordinates(chunk_compound: &nbt_tag::NbtTagCompound) -> blocks
     let mut result: blocks::Coordinates = blocks::Coordinates::new(vec![0, 0, 0]);
     
     if let Some(x_coord_tag) = chunk_compound.values.get("xPos") {
-        if let Some(x_coord) = x_coord_tag.int() {
-            result.x = x_coord.value;
+        if let Some(x_coord) = x_coord_tag.as_i32() {
+            result.x = x_coord;
         }
         
     }
 
     if let Some(y_coord_tag) = chunk_compound.values.get("yPos") {
-        if let Some(y_coord) = y_coord_tag.int() {
-            result.y = y_coord.value;
+        if let Some(y_coord) = y_coord_tag.as_i32() {
+            result.y = y_coord;
         }
         
     }
 
     if let Some(z_coord_tag) = chunk_compound.values.get("zPos") {
-        if let Some(z_coord) = z_coord_tag.int() {
-            result.z = z_coord.value;
+        if let Some(z_coord) = z_coord_tag.as_i32() {
+            result.z = z_coord;
         }
         
     }
@@ -448,7 +472,7 @@ pub fn get_chunk_coordinates(chunk_compound: &nbt_tag::NbtTagCompound) -> blocks
 /// This approach ensures that only relevant and existing block state information is retrieved, avoiding potential errors or misinterpretations of the chunk data.
 pub fn find_block_states_in_section<'a>(block_states_tag: & 'a nbt_tag::NbtTag) -> Option<& 'a nbt_tag::NbtTag> {    
 
-    if let Some(block_states_compound) = block_states_tag.compound_as_ref() {
+    if let Some(block_states_compound) = block_states_tag.as_compound() {
         if let Some(block_states) = block_states_compound.values.get("block_states") {
             Some(block_states)
         }
@@ -487,11 +511,11 @@ pub fn find_palette_in_block_states<'a>(block_states_tag: & 'a nbt_tag::NbtTag)
     
     //let mut data_values = &Vec::<i64>::new();
 
-    if let Some(block_states_compound) = block_states_tag.compound_as_ref() {
+    if let Some(block_states_compound) = block_states_tag.as_compound() {
         if let Some(palette_tag) = block_states_compound.values.get("palette") {
-            if let Some(palette_list) = palette_tag.list_as_ref() {
+            if let Some(palette_list) = palette_tag.as_list() {
                 if let Some(data_values_tag) = block_states_compound.values.get("data") {
-                    if let Some(data_values_taglong) = data_values_tag.long_array_as_ref() {
+                    if let Some(data_values_taglong) = data_values_tag.as_long_array() {
 
                         (Some(palette_list), Some(&data_values_taglong.values))
                     }
@@ -541,10 +565,10 @@ pub fn find_block_name_in_palette(blocks_tag: &nbt_tag::NbtTag, block_resouce_lo
     
     let mut block_name_found = false;
     
-    if let Some(block_compound) = blocks_tag.compound_as_ref() {
+    if let Some(block_compound) = blocks_tag.as_compound() {
         if let Some(block_name_tag) = block_compound.values.get("Name") {
-            if let Some(block_name) = block_name_tag.string() {
-                if block_name.value == block_resouce_location {
+            if let Some(block_name) = block_name_tag.as_str() {
+                if block_name == block_resouce_location {
                     block_name_found = true
                 }
             }
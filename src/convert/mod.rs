@@ -0,0 +1,369 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.2
+//
+// ## Changelog
+// - 1.0.0: Initial version, nbt! macro and conversions between tags and Rust values [mrmarkolinus:2026-10-16]
+// - 1.0.1: nbt! accepts Rust number suffixes, for zero bytes [mrmarkolinus:2026-10-16]
+// - 1.0.2: Elements are no longer renamed, tags carry no name [mrmarkolinus:2026-10-16]
+
+//! Short ways to build and read trees.
+//!
+//! The `nbt!` macro builds a compound with SNBT-like syntax:
+//!
+//! ```ignore
+//! let item = nbt!{
+//!     "id": "minecraft:stone",
+//!     "Count": 1b,
+//!     "Pos": [I; 1, 2, 3],
+//!     "tag": { "Damage": (damage), "Tags": ["a", "b"] },
+//! };
+//! ```
+//!
+//! Keys are string literals or identifiers. Literal values are read as SNBT, so `1b`, `2s`,
+//! `3L`, `1.5f` and `true` have the same types as in commands. Rust reads `0b` as the start
+//! of a binary number, so a zero byte is written `0i8` or `false`; the Rust suffixes `i8`,
+//! `i16`, `i32`, `i64`, `f32` and `f64` are accepted for every number. A Rust expression in
+//! parentheses, or a variable, is converted with `NbtTag::from`. `[B; …]`, `[I; …]` and
+//! `[L; …]` are arrays, other brackets lists. The macro panics on literals that are not
+//! valid SNBT, lists with elements of different types and array elements out of range.
+//!
+//! `From` converts numbers, strings, `Vec`s and maps into tags. `Vec<i8>`, `Vec<i32>` and
+//! `Vec<i64>` become arrays, other `Vec`s lists. Hash maps become compounds with sorted keys,
+//! so the result does not depend on the hash order. `TryFrom<&NbtTag>` reads them back and
+//! fails with `TypeMismatch` when the tag has another type; int, byte and long vectors are
+//! read from arrays and from lists.
+
+use crate::error::{NbtError, NbtErrorKind};
+use crate::nbt_tag::*;
+
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests;
+
+/// Builds an `NbtTagCompound` with SNBT-like syntax, see the `convert` module.
+#[macro_export]
+macro_rules! nbt {
+    ($($body:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut compound = $crate::nbt_tag::NbtTagCompound::new("");
+        $crate::__nbt_entries!(compound; $($body)*);
+        compound
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nbt_entries {
+    ($compound:ident;) => {};
+    ($compound:ident; $key:tt : - $value:literal $(, $($rest:tt)*)?) => {
+        $compound.insert($crate::__nbt_key!($key), $crate::__nbt_value!(- $value));
+        $crate::__nbt_entries!($compound; $($($rest)*)?);
+    };
+    ($compound:ident; $key:tt : $value:tt $(, $($rest:tt)*)?) => {
+        $compound.insert($crate::__nbt_key!($key), $crate::__nbt_value!($value));
+        $crate::__nbt_entries!($compound; $($($rest)*)?);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nbt_key {
+    ($key:literal) => { $key };
+    ($key:ident) => { stringify!($key) };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nbt_value {
+    ({ $($body:tt)* }) => { $crate::nbt_tag::NbtTag::Compound($crate::nbt!($($body)*)) };
+    ([B; $($values:tt)*]) => { $crate::convert::array($crate::nbt_tag::NbtTagType::ByteArray, $crate::__nbt_values!([] $($values)*)) };
+    ([I; $($values:tt)*]) => { $crate::convert::array($crate::nbt_tag::NbtTagType::IntArray, $crate::__nbt_values!([] $($values)*)) };
+    ([L; $($values:tt)*]) => { $crate::convert::array($crate::nbt_tag::NbtTagType::LongArray, $crate::__nbt_values!([] $($values)*)) };
+    ([ $($values:tt)* ]) => { $crate::convert::list($crate::__nbt_values!([] $($values)*)) };
+    (- $value:literal) => { $crate::convert::literal(concat!("-", stringify!($value))) };
+    ($value:literal) => { $crate::convert::literal(stringify!($value)) };
+    (( $value:expr )) => { $crate::nbt_tag::NbtTag::from($value) };
+    ($value:ident) => { $crate::nbt_tag::NbtTag::from($value) };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nbt_values {
+    ([$($out:expr,)*]) => { vec![$($out,)*] };
+    ([$($out:expr,)*] - $value:literal $(, $($rest:tt)*)?) => {
+        $crate::__nbt_values!([$($out,)* $crate::__nbt_value!(- $value),] $($($rest)*)?)
+    };
+    ([$($out:expr,)*] $value:tt $(, $($rest:tt)*)?) => {
+        $crate::__nbt_values!([$($out,)* $crate::__nbt_value!($value),] $($($rest)*)?)
+    };
+}
+
+/// Reads a literal of `nbt!` as SNBT, after replacing a Rust type suffix.
+#[doc(hidden)]
+pub fn literal(text: &str) -> NbtTag {
+    let rust_suffix = [("i8", "b"), ("i16", "s"), ("i32", ""), ("i64", "L"), ("f32", "f"), ("f64", "d")].iter()
+        .find_map(|(rust, snbt)| text.strip_suffix(rust).filter(|number| number.ends_with(|c: char| c.is_ascii_digit())).map(|number| format!("{}{}", number, snbt)));
+
+    match NbtTag::from_snbt(rust_suffix.as_deref().unwrap_or(text)) {
+        Ok(tag) => tag,
+        Err(e) => panic!("nbt!: invalid literal {}: {}", text, e),
+    }
+}
+
+/// Builds a list of `nbt!`, typed after its first element.
+#[doc(hidden)]
+pub fn list(values: Vec<NbtTag>) -> NbtTag {
+    let ty = values.first().map_or(NbtTagType::End, NbtTag::ty);
+    if let Some(value) = values.iter().find(|value| value.ty() != ty) {
+        panic!("nbt!: {:?} element in a list of {:?}", value.ty(), ty);
+    }
+
+    NbtTag::List(NbtTagList::new(ty, values))
+}
+
+/// Builds an array of `nbt!` from integer tags that fit its element type.
+#[doc(hidden)]
+pub fn array(ty: NbtTagType, values: Vec<NbtTag>) -> NbtTag {
+    let values = values.iter().map(|value| match value {
+        NbtTag::Byte(x) => x.value as i64,
+        NbtTag::Short(x) => x.value as i64,
+        NbtTag::Int(x) => x.value as i64,
+        NbtTag::Long(x) => x.value,
+        _ => panic!("nbt!: {:?} element in a {:?}", value.ty(), ty),
+    });
+
+    match ty {
+        NbtTagType::ByteArray => NbtTag::from(values.map(|v| i8::try_from(v).unwrap_or_else(|_| panic!("nbt!: {} out of range in a {:?}", v, ty))).collect::<Vec<_>>()),
+        NbtTagType::IntArray => NbtTag::from(values.map(|v| i32::try_from(v).unwrap_or_else(|_| panic!("nbt!: {} out of range in a {:?}", v, ty))).collect::<Vec<_>>()),
+        _ => NbtTag::from(values.collect::<Vec<i64>>()),
+    }
+}
+
+macro_rules! impl_from_scalar {
+    ($($rust:ty => $variant:ident($tag:ident)),* $(,)?) => {
+        $(
+            impl From<$rust> for NbtTag {
+                fn from(value: $rust) -> Self {
+                    NbtTag::$variant($tag::new(value.into()))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_scalar! {
+    i8 => Byte(NbtTagByte),
+    i16 => Short(NbtTagShort),
+    i32 => Int(NbtTagInt),
+    i64 => Long(NbtTagLong),
+    f32 => Float(NbtTagFloat),
+    f64 => Double(NbtTagDouble),
+    String => String(NbtTagString),
+    &str => String(NbtTagString),
+    Vec<i8> => ByteArray(NbtTagByteArray),
+    Vec<i32> => IntArray(NbtTagIntArray),
+    Vec<i64> => LongArray(NbtTagLongArray),
+}
+
+impl From<bool> for NbtTag {
+    fn from(value: bool) -> Self {
+        NbtTag::Byte(NbtTagByte::new(value as i8))
+    }
+}
+
+macro_rules! impl_from_tag {
+    ($($tag:ident => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$tag> for NbtTag {
+                fn from(value: $tag) -> Self {
+                    NbtTag::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_tag! {
+    NbtTagByte => Byte,
+    NbtTagShort => Short,
+    NbtTagInt => Int,
+    NbtTagLong => Long,
+    NbtTagFloat => Float,
+    NbtTagDouble => Double,
+    NbtTagByteArray => ByteArray,
+    NbtTagString => String,
+    NbtTagList => List,
+    NbtTagCompound => Compound,
+    NbtTagIntArray => IntArray,
+    NbtTagLongArray => LongArray,
+}
+
+macro_rules! impl_from_vec {
+    ($($rust:ty => $ty:ident),* $(,)?) => {
+        $(
+            impl From<Vec<$rust>> for NbtTag {
+                fn from(values: Vec<$rust>) -> Self {
+                    let values = values.into_iter().map(NbtTag::from).collect();
+                    NbtTag::List(NbtTagList::new(NbtTagType::$ty, values))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_vec! {
+    i16 => Short,
+    f32 => Float,
+    f64 => Double,
+    String => String,
+    &str => String,
+    NbtTagCompound => Compound,
+}
+
+impl<K: Into<String>, V: Into<NbtTag>> From<HashMap<K, V>> for NbtTagCompound {
+    fn from(map: HashMap<K, V>) -> Self {
+        let mut entries: Vec<(String, NbtTag)> = map.into_iter().map(|(key, value)| (key.into(), value.into())).collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut compound = NbtTagCompound::new("");
+        for (key, value) in entries {
+            compound.insert(&key, value);
+        }
+        compound
+    }
+}
+
+impl<K: Into<String>, V: Into<NbtTag>> From<HashMap<K, V>> for NbtTag {
+    fn from(map: HashMap<K, V>) -> Self {
+        NbtTag::Compound(map.into())
+    }
+}
+
+fn type_mismatch(expected: NbtTagType, tag: &NbtTag) -> NbtError {
+    NbtError::new(NbtErrorKind::TypeMismatch { expected, found: tag.ty() }, 0)
+}
+
+macro_rules! impl_try_from_scalar {
+    ($($rust:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl TryFrom<&NbtTag> for $rust {
+                type Error = NbtError;
+
+                fn try_from(tag: &NbtTag) -> Result<Self, Self::Error> {
+                    match tag {
+                        NbtTag::$variant(x) => Ok(x.value),
+                        _ => Err(type_mismatch(NbtTagType::$variant, tag)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_scalar! {
+    i8 => Byte,
+    i16 => Short,
+    i32 => Int,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double,
+}
+
+impl TryFrom<&NbtTag> for String {
+    type Error = NbtError;
+
+    fn try_from(tag: &NbtTag) -> Result<Self, Self::Error> {
+        <&str>::try_from(tag).map(str::to_string)
+    }
+}
+
+impl TryFrom<&NbtTag> for bool {
+    type Error = NbtError;
+
+    fn try_from(tag: &NbtTag) -> Result<Self, Self::Error> {
+        i8::try_from(tag).map(|value| value != 0)
+    }
+}
+
+impl<'a> TryFrom<&'a NbtTag> for &'a str {
+    type Error = NbtError;
+
+    fn try_from(tag: &'a NbtTag) -> Result<Self, Self::Error> {
+        tag.as_str().ok_or_else(|| type_mismatch(NbtTagType::String, tag))
+    }
+}
+
+/// Reads the elements of a list, with their index in errors.
+fn list_values<'a, T: TryFrom<&'a NbtTag, Error = NbtError>>(list: &'a NbtTagList) -> Result<Vec<T>, NbtError> {
+    list.values.iter().enumerate().map(|(index, value)| T::try_from(value).map_err(|e| e.in_index(index))).collect()
+}
+
+macro_rules! impl_try_from_array {
+    ($($rust:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl TryFrom<&NbtTag> for Vec<$rust> {
+                type Error = NbtError;
+
+                fn try_from(tag: &NbtTag) -> Result<Self, Self::Error> {
+                    match tag {
+                        NbtTag::$variant(x) => Ok(x.values.clone()),
+                        NbtTag::List(list) => list_values(list),
+                        _ => Err(type_mismatch(NbtTagType::$variant, tag)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_array! {
+    i8 => ByteArray,
+    i32 => IntArray,
+    i64 => LongArray,
+}
+
+macro_rules! impl_try_from_list {
+    ($($rust:ty),* $(,)?) => {
+        $(
+            impl TryFrom<&NbtTag> for Vec<$rust> {
+                type Error = NbtError;
+
+                fn try_from(tag: &NbtTag) -> Result<Self, Self::Error> {
+                    match tag {
+                        NbtTag::List(list) => list_values(list),
+                        _ => Err(type_mismatch(NbtTagType::List, tag)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_list!(i16, f32, f64, String, NbtTagCompound);
+
+impl TryFrom<&NbtTag> for NbtTagCompound {
+    type Error = NbtError;
+
+    fn try_from(tag: &NbtTag) -> Result<Self, Self::Error> {
+        tag.as_compound().cloned().ok_or_else(|| type_mismatch(NbtTagType::Compound, tag))
+    }
+}
+
+impl<V> TryFrom<&NbtTag> for HashMap<String, V>
+where
+    V: for<'a> TryFrom<&'a NbtTag, Error = NbtError>,
+{
+    type Error = NbtError;
+
+    fn try_from(tag: &NbtTag) -> Result<Self, Self::Error> {
+        let compound = tag.as_compound().ok_or_else(|| type_mismatch(NbtTagType::Compound, tag))?;
+        compound.values.iter().map(|(key, value)| Ok((key.clone(), V::try_from(value).map_err(|e| e.in_key(key))?))).collect()
+    }
+}
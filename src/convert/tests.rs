@@ -0,0 +1,86 @@
+#[cfg(test)]
+
+use super::*;
+use crate::nbt;
+
+#[test]
+fn test_nbt_macro_literals() {
+    let item = nbt!{
+        "id": "minecraft:stone",
+        "Count": 1b,
+        "Slot": -2b,
+        "Zero": 0i8,
+        "Big": 5i64,
+        "Pos": [I; 1, -2, 3],
+        "Blocks": [B; 1b, -1],
+        "Heights": [L; 3L, 4],
+        "Motion": [0.5, -1.0, 0.0],
+        "tag": { Damage: 3s, Unbreakable: true, Tags: ["a", "b"], Empty: [] },
+    };
+
+    assert_eq!(item, NbtTag::from_snbt(concat!(
+        "{id:\"minecraft:stone\",Count:1b,Slot:-2b,Zero:0b,Big:5L,Pos:[I;1,-2,3],Blocks:[B;1b,-1b],Heights:[L;3L,4L],Motion:[0.5d,-1.0d,0.0d],",
+        "tag:{Damage:3s,Unbreakable:1b,Tags:[\"a\",\"b\"],Empty:[]}}"
+    )).unwrap().compound().unwrap());
+    assert_eq!(item.values.keys().collect::<Vec<_>>(), vec!["id", "Count", "Slot", "Zero", "Big", "Pos", "Blocks", "Heights", "Motion", "tag"]);
+    assert_eq!(item.get("tag").and_then(NbtTag::as_compound).unwrap().name, "");
+}
+
+#[test]
+fn test_nbt_macro_expressions() {
+    let damage = 7;
+    let heights = vec![1i64, 2];
+    let item = nbt!{ "Damage": damage, "Name": (format!("{}-{}", "a", damage)), "Heights": (heights.clone()), "Lore": [(String::from("x")), "y"] };
+
+    assert_eq!(item.get_i32("Damage"), Some(7));
+    assert_eq!(item.get_str("Name"), Some("a-7"));
+    assert_eq!(Vec::<i64>::try_from(item.get("Heights").unwrap()).unwrap(), heights);
+    assert_eq!(Vec::<String>::try_from(item.get("Lore").unwrap()).unwrap(), vec!["x", "y"]);
+    assert_eq!(nbt!{}, NbtTagCompound::new(""));
+}
+
+#[test]
+#[should_panic(expected = "nbt!: Int element in a list of Byte")]
+fn test_nbt_macro_mixed_list() {
+    nbt!{ "l": [1b, 2] };
+}
+
+#[test]
+#[should_panic(expected = "nbt!: 300 out of range in a ByteArray")]
+fn test_nbt_macro_array_out_of_range() {
+    nbt!{ "a": [B; 300] };
+}
+
+#[test]
+fn test_from_values() {
+    assert_eq!(NbtTag::from(1i8), NbtTag::Byte(NbtTagByte::new(1)));
+    assert_eq!(NbtTag::from(true), NbtTag::Byte(NbtTagByte::new(1)));
+    assert_eq!(NbtTag::from("x"), NbtTag::String(NbtTagString::new("x".to_string())));
+    assert_eq!(NbtTag::from(vec![1i32, 2]), NbtTag::IntArray(NbtTagIntArray::new(vec![1, 2])));
+    assert_eq!(NbtTag::from(vec![1.5f32]).to_snbt(), "[1.5f]");
+    assert_eq!(NbtTag::from(vec!["a", "b"]).list().unwrap().ty, NbtTagType::String);
+
+    let map = HashMap::from([("b", 2), ("a", 1)]);
+    let compound = NbtTagCompound::from(map);
+    assert_eq!(compound.values.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+    assert_eq!(compound.get_i32("b"), Some(2));
+}
+
+#[test]
+fn test_try_from_tags() {
+    let tag = NbtTag::Compound(nbt!{ "i": 5, "s": "x", "ints": [1, 2], "longs": [L; 3L], "m": { "a": 1, "b": 2 } });
+    let compound = tag.as_compound().unwrap();
+
+    assert_eq!(i32::try_from(compound.get("i").unwrap()).unwrap(), 5);
+    assert_eq!(<&str>::try_from(compound.get("s").unwrap()).unwrap(), "x");
+    assert_eq!(Vec::<i32>::try_from(compound.get("ints").unwrap()).unwrap(), vec![1, 2]);
+    assert_eq!(Vec::<i64>::try_from(compound.get("longs").unwrap()).unwrap(), vec![3]);
+    assert_eq!(HashMap::<String, i32>::try_from(compound.get("m").unwrap()).unwrap(), HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)]));
+
+    let error = i64::try_from(compound.get("i").unwrap()).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::TypeMismatch { expected: NbtTagType::Long, found: NbtTagType::Int }));
+    let error = HashMap::<String, String>::try_from(&tag).unwrap_err();
+    assert_eq!(error.path(), "i");
+    let error = Vec::<String>::try_from(compound.get("ints").unwrap()).unwrap_err();
+    assert_eq!(error.path(), "[0]");
+}
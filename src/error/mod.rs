@@ -0,0 +1,230 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.10
+//
+// ## Changelog
+// - 1.0.0: Initial version, typed NBT errors with byte offset and tag path [mrmarkolinus:2026-10-16]
+// - 1.0.1: Added InvalidListType for End-typed lists with elements [mrmarkolinus:2026-10-16]
+// - 1.0.2: NbtError doubles as the serde error type [mrmarkolinus:2026-10-16]
+// - 1.0.3: Added InvalidSnbt [mrmarkolinus:2026-10-16]
+// - 1.0.4: Added InvalidVarint [mrmarkolinus:2026-10-16]
+// - 1.0.5: Added ListTypeMismatch and EndTagValue for the writer [mrmarkolinus:2026-10-16]
+// - 1.0.6: Added InvalidJson [mrmarkolinus:2026-10-16]
+// - 1.0.7: Added InvalidPath [mrmarkolinus:2026-10-16]
+// - 1.0.8: Added TypeMismatch for conversions [mrmarkolinus:2026-10-16]
+// - 1.0.9: Added PatchTestFailed [mrmarkolinus:2026-10-16]
+// - 1.0.10: Added InvalidRegex [mrmarkolinus:2026-10-16]
+
+use crate::nbt_tag::NbtTagType;
+
+use pyo3::create_exception;
+use pyo3::prelude::*;
+use std::fmt;
+use std::io;
+
+#[cfg(test)]
+mod tests;
+
+create_exception!(fastnbt, NbtParseError, pyo3::exceptions::PyIOError);
+
+/// Describes what went wrong while decoding or encoding NBT data.
+#[derive(Debug)]
+pub enum NbtErrorKind {
+    /// A tag type byte that is not part of the NBT specification.
+    UnknownTagId(u8),
+    /// The input ended before the current tag was complete.
+    UnexpectedEof,
+    /// A length prefix (list, array or string) was negative.
+    NegativeLength(i32),
+    /// A name or string value is not valid (Modified) UTF-8.
+    InvalidUtf8,
+    /// A length or size exceeded the limit accepted by the parser.
+    LimitExceeded { limit: &'static str, value: u64, max: u64 },
+    /// A list declares an element type that cannot hold values (`End` with a non-zero length).
+    InvalidListType(NbtTagType),
+    /// The root tag has a type that is not accepted in this context.
+    InvalidRoot(NbtTagType),
+    /// The underlying reader failed for a reason other than end of input.
+    Io(io::Error),
+    /// A value could not be converted to or from a Rust type with serde.
+    Serde(String),
+    /// SNBT text does not follow the command syntax. The offset is a byte offset in the text.
+    InvalidSnbt(String),
+    /// A varint of network NBT does not end within the bytes allowed for its type.
+    InvalidVarint,
+    /// A list element does not have the element type declared by the list.
+    ListTypeMismatch { expected: NbtTagType, found: NbtTagType },
+    /// An `End` tag was found where a value must be written.
+    EndTagValue,
+    /// A JSON document does not follow the expected schema.
+    InvalidJson(String),
+    /// An NBT path does not follow the `/data` syntax. The offset is a byte offset in the path.
+    InvalidPath(String),
+    /// A tag does not have the type a conversion to a Rust value expects.
+    TypeMismatch { expected: NbtTagType, found: NbtTagType },
+    /// A `test` operation of a patch did not find the expected value at its path.
+    PatchTestFailed(String),
+    /// A regular expression of a search does not compile.
+    InvalidRegex(String),
+}
+
+impl fmt::Display for NbtErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NbtErrorKind::UnknownTagId(id) => write!(f, "unknown tag id {}", id),
+            NbtErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            NbtErrorKind::NegativeLength(len) => write!(f, "negative length {}", len),
+            NbtErrorKind::InvalidUtf8 => write!(f, "invalid UTF-8 string"),
+            NbtErrorKind::LimitExceeded { limit, value, max } => write!(f, "{} {} exceeds the limit of {}", limit, value, max),
+            NbtErrorKind::InvalidListType(ty) => write!(f, "list of {:?} tags cannot have elements", ty),
+            NbtErrorKind::InvalidRoot(ty) => write!(f, "invalid root tag {:?}", ty),
+            NbtErrorKind::Io(e) => write!(f, "i/o error: {}", e),
+            NbtErrorKind::Serde(msg) => write!(f, "{}", msg),
+            NbtErrorKind::InvalidSnbt(msg) => write!(f, "invalid SNBT: {}", msg),
+            NbtErrorKind::InvalidVarint => write!(f, "varint too long"),
+            NbtErrorKind::ListTypeMismatch { expected, found } => write!(f, "{:?} element in a list of {:?}", found, expected),
+            NbtErrorKind::EndTagValue => write!(f, "End tag cannot be written as a value"),
+            NbtErrorKind::InvalidJson(msg) => write!(f, "invalid JSON: {}", msg),
+            NbtErrorKind::InvalidPath(msg) => write!(f, "invalid NBT path: {}", msg),
+            NbtErrorKind::TypeMismatch { expected, found } => write!(f, "expected {:?} tag, found {:?}", expected, found),
+            NbtErrorKind::PatchTestFailed(path) => write!(f, "patch test failed at {}", path),
+            NbtErrorKind::InvalidRegex(msg) => write!(f, "invalid regex: {}", msg),
+        }
+    }
+}
+
+/// Error returned when NBT data cannot be decoded, or a tree cannot be encoded.
+///
+/// Besides the error kind, it records the byte offset in the (uncompressed) input where
+/// the problem was detected, or in the output when encoding, and the path of the tag that
+/// was being processed, using the same notation as Minecraft's `/data` command, e.g.
+/// `sections[3].block_states.palette[7].Name`. An empty path means the error happened on
+/// the root tag itself.
+#[derive(Debug)]
+pub struct NbtError {
+    pub kind: NbtErrorKind,
+    pub offset: u64,
+    path: String,
+}
+
+impl NbtError {
+    pub fn new(kind: NbtErrorKind, offset: u64) -> Self {
+        NbtError { kind, offset, path: String::new() }
+    }
+
+    /// Builds an error from a failed read, mapping end of input to `UnexpectedEof`.
+    pub fn from_io(error: io::Error, offset: u64) -> Self {
+        let kind = match error.kind() {
+            io::ErrorKind::UnexpectedEof => NbtErrorKind::UnexpectedEof,
+            _ => NbtErrorKind::Io(error),
+        };
+
+        NbtError::new(kind, offset)
+    }
+
+    /// Path of the tag that was being decoded when the error occurred.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Prepends a compound key to the path. Used while the error propagates up the tree,
+    /// so the path is only built when something actually went wrong.
+    pub fn in_key(mut self, key: &str) -> Self {
+        let key = quote_key(key);
+        if self.path.is_empty() {
+            self.path = key;
+        }
+        else if self.path.starts_with('[') {
+            self.path.insert_str(0, &key);
+        }
+        else {
+            self.path = format!("{}.{}", key, self.path);
+        }
+        self
+    }
+
+    /// Prepends a list index to the path.
+    pub fn in_index(mut self, index: usize) -> Self {
+        if self.path.is_empty() || self.path.starts_with('[') {
+            self.path.insert_str(0, &format!("[{}]", index));
+        }
+        else {
+            self.path = format!("[{}].{}", index, self.path);
+        }
+        self
+    }
+}
+
+/// Keys containing anything other than the characters allowed in unquoted `/data` path
+/// keys are quoted, so the rendered path stays unambiguous.
+pub(crate) fn quote_key(key: &str) -> String {
+    let plain = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '+');
+    if plain {
+        key.to_string()
+    }
+    else {
+        format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+impl fmt::Display for NbtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{} at byte {}", self.kind, self.offset)
+        }
+        else {
+            write!(f, "{} at byte {} (tag {})", self.kind, self.offset, self.path)
+        }
+    }
+}
+
+impl std::error::Error for NbtError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            NbtErrorKind::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl serde::de::Error for NbtError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        NbtError::new(NbtErrorKind::Serde(msg.to_string()), 0)
+    }
+}
+
+impl serde::ser::Error for NbtError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        NbtError::new(NbtErrorKind::Serde(msg.to_string()), 0)
+    }
+}
+
+impl From<NbtError> for io::Error {
+    fn from(error: NbtError) -> Self {
+        let kind = match error.kind {
+            NbtErrorKind::UnexpectedEof => io::ErrorKind::UnexpectedEof,
+            _ => io::ErrorKind::InvalidData,
+        };
+        io::Error::new(kind, error)
+    }
+}
+
+impl From<NbtError> for PyErr {
+    fn from(error: NbtError) -> Self {
+        NbtParseError::new_err((error.to_string(), error.offset, error.path))
+    }
+}
+
+/// Converts an `io::Error` coming from the library into a Python exception.
+/// Errors that wrap an `NbtError` become `NbtParseError`, anything else an `IOError`.
+pub fn io_error_to_py(error: io::Error) -> PyErr {
+    match error.get_ref().and_then(|inner| inner.downcast_ref::<NbtError>()) {
+        Some(nbt_error) => NbtParseError::new_err((nbt_error.to_string(), nbt_error.offset, nbt_error.path.clone())),
+        None => PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", error)),
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+
+use super::*;
+
+#[test]
+fn test_error_path_rendering() {
+    let error = NbtError::new(NbtErrorKind::UnexpectedEof, 42)
+        .in_key("Name")
+        .in_index(7)
+        .in_key("palette")
+        .in_key("block_states")
+        .in_index(3)
+        .in_key("sections");
+
+    assert_eq!(error.path(), "sections[3].block_states.palette[7].Name");
+    assert_eq!(error.offset, 42);
+}
+
+#[test]
+fn test_error_path_quotes_special_keys() {
+    let error = NbtError::new(NbtErrorKind::UnexpectedEof, 0)
+        .in_key("a key.with dots")
+        .in_key("root");
+
+    assert_eq!(error.path(), "root.\"a key.with dots\"");
+}
+
+#[test]
+fn test_error_into_io_error() {
+    let error = NbtError::new(NbtErrorKind::UnknownTagId(99), 5).in_key("x");
+    let io_error: io::Error = error.into();
+
+    assert_eq!(io_error.kind(), io::ErrorKind::InvalidData);
+    let inner = io_error.get_ref().unwrap().downcast_ref::<NbtError>().unwrap();
+    assert!(matches!(inner.kind, NbtErrorKind::UnknownTagId(99)));
+    assert_eq!(inner.path(), "x");
+    assert_eq!(io_error.to_string(), "unknown tag id 99 at byte 5 (tag x)");
+}
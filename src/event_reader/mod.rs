@@ -0,0 +1,370 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.0
+//
+// ## Changelog
+// - 1.0.0: Initial version, pull-based NBT event reader [mrmarkolinus:2026-10-16]
+
+//! Pull-based NBT reader.
+//!
+//! `NbtEventReader` walks the binary data and yields one `NbtEvent` per tag, without building
+//! an `NbtTagCompound` tree. Containers (compounds, lists and arrays) produce a start event
+//! followed by their content and a matching `End`. Callers that only need a few fields can
+//! `skip_container` the containers they are not interested in, which consumes them without decoding.
+//!
+//! ```ignore
+//! let mut events = NbtEventReader::new(bytes.as_slice(), &ParseLimits::default());
+//! while let Some(event) = events.next() {
+//!     match event? {
+//!         NbtEvent::Value(name, NbtScalar::Int(x)) if name == "xPos" => println!("{}", x),
+//!         NbtEvent::StartList(name, _, _) if name == "sections" => events.skip_container()?,
+//!         _ => {}
+//!     }
+//! }
+//! ```
+
+use crate::error::{NbtError, NbtErrorKind};
+use crate::file_parser::{Parser, ParseLimits, ARRAY_BLOCK_SIZE};
+use crate::nbt_tag::NbtTagType;
+
+use std::io::Read;
+
+#[cfg(test)]
+mod tests;
+
+/// A single non-container value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NbtScalar {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+}
+
+/// A block of elements of a byte, int or long array.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NbtArrayChunk {
+    Byte(Vec<i8>),
+    Int(Vec<i32>),
+    Long(Vec<i64>),
+}
+
+/// Event produced by `NbtEventReader`.
+///
+/// Names are the keys of the tags in their parent compound. Elements of a list have no name
+/// and are reported with an empty one, like in the tree built by `file_parser`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NbtEvent {
+    /// Start of a compound. Its entries follow, then `End`.
+    StartCompound(String),
+    /// A scalar value.
+    Value(String, NbtScalar),
+    /// Start of a list with its element type and length. The elements follow, then `End`.
+    StartList(String, NbtTagType, usize),
+    /// Start of a byte, int or long array with its length. The elements follow in one or
+    /// more `ArrayChunk` events, then `End`.
+    StartArray(String, NbtTagType, usize),
+    /// Next block of elements of the current array.
+    ArrayChunk(NbtArrayChunk),
+    /// End of the current compound, list or array.
+    End,
+}
+
+/// How a container was reached from its parent, used to build error paths.
+enum Segment {
+    Root,
+    Key(String),
+    Index(usize),
+}
+
+enum Frame {
+    Compound,
+    List { ty: NbtTagType, len: usize, next: usize },
+    Array { ty: NbtTagType, remaining: usize },
+}
+
+/// Iterator over the events of an uncompressed NBT document. It stops after the `End` of the
+/// root compound, or after the first error.
+///
+/// Nesting depth, array and string lengths are checked against the `ParseLimits` as in
+/// `file_parser::parse_reader`. `max_total_bytes` does not apply, since no tree is kept.
+/// Pass a buffered reader: the reader issues many small reads.
+pub struct NbtEventReader<R: Read> {
+    parser: Parser<R>,
+    stack: Vec<(Frame, Segment)>,
+    started: bool,
+    finished: bool,
+}
+
+impl<R: Read> NbtEventReader<R> {
+    pub fn new(reader: R, limits: &ParseLimits) -> Self {
+        let limits = ParseLimits { max_total_bytes: u64::MAX, ..*limits };
+        NbtEventReader { parser: Parser::new(reader, &limits), stack: Vec::new(), started: false, finished: false }
+    }
+
+    /// Number of uncompressed bytes consumed so far.
+    pub fn offset(&self) -> u64 {
+        self.parser.offset()
+    }
+
+    /// Current nesting depth: 0 before the root and after its `End`, 1 inside the root.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Skips the rest of the innermost open container, including its `End` event.
+    /// Call it right after a start event to ignore that container altogether.
+    pub fn skip_container(&mut self) -> Result<(), NbtError> {
+        let result = self.skip_frame();
+        if result.is_err() {
+            self.finished = true;
+        }
+        result
+    }
+
+    fn skip_frame(&mut self) -> Result<(), NbtError> {
+        let Some((frame, _)) = self.stack.last_mut() else {
+            return Ok(());
+        };
+
+        let result = match frame {
+            Frame::Compound => self.skip_compound_entries(),
+            Frame::List { ty, len, next } => {
+                let (ty, count) = (*ty, *len - *next);
+                *next = *len;
+                self.skip_payloads(ty, count)
+            }
+            Frame::Array { ty, remaining } => {
+                let bytes = *remaining as u64 * element_size(*ty) as u64;
+                *remaining = 0;
+                self.parser.skip(bytes)
+            }
+        };
+        result.map_err(|e| self.locate(e, None))?;
+
+        self.pop();
+        Ok(())
+    }
+
+    /// Reads the next event. `Ok(None)` means the document is complete.
+    pub fn next_event(&mut self) -> Result<Option<NbtEvent>, NbtError> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let result = self.read_event();
+        match &result {
+            Ok(None) | Err(_) => self.finished = true,
+            Ok(Some(_)) => {}
+        }
+        result
+    }
+
+    fn read_event(&mut self) -> Result<Option<NbtEvent>, NbtError> {
+        let Some((frame, _)) = self.stack.last_mut() else {
+            if self.started {
+                return Ok(None);
+            }
+            self.started = true;
+
+            let ty = self.parser.read_tag_type()?;
+            if ty != NbtTagType::Compound {
+                return Err(NbtError::new(NbtErrorKind::InvalidRoot(ty), 0));
+            }
+            let name = self.parser.read_name()?;
+            return self.start_value(ty, name, Segment::Root).map(Some);
+        };
+
+        match frame {
+            Frame::Compound => {
+                let ty = self.parser.read_tag_type().map_err(|e| self.locate(e, None))?;
+                if ty == NbtTagType::End {
+                    self.pop();
+                    return Ok(Some(NbtEvent::End));
+                }
+                let name = self.parser.read_name().map_err(|e| self.locate(e, None))?;
+
+                let segment = Segment::Key(name.clone());
+                self.start_value(ty, name, segment).map(Some)
+            }
+            Frame::List { ty, len, next } => {
+                if *next == *len {
+                    self.pop();
+                    return Ok(Some(NbtEvent::End));
+                }
+
+                let (ty, index) = (*ty, *next);
+                *next += 1;
+                self.start_value(ty, String::new(), Segment::Index(index)).map(Some)
+            }
+            Frame::Array { ty, remaining } => {
+                if *remaining == 0 {
+                    self.pop();
+                    return Ok(Some(NbtEvent::End));
+                }
+
+                let ty = *ty;
+                let count = (*remaining).min(ARRAY_BLOCK_SIZE / element_size(ty));
+                *remaining -= count;
+                let chunk = match ty {
+                    NbtTagType::ByteArray => self.parser.read_array(count, i8::from_be_bytes).map(NbtArrayChunk::Byte),
+                    NbtTagType::IntArray => self.parser.read_array(count, i32::from_be_bytes).map(NbtArrayChunk::Int),
+                    _ => self.parser.read_array(count, i64::from_be_bytes).map(NbtArrayChunk::Long),
+                };
+                chunk.map(|c| Some(NbtEvent::ArrayChunk(c))).map_err(|e| self.locate(e, None))
+            }
+        }
+    }
+
+    /// Reads the header or the value of a tag whose type and name are already known.
+    fn start_value(&mut self, ty: NbtTagType, name: String, segment: Segment) -> Result<NbtEvent, NbtError> {
+        let event = match ty {
+            NbtTagType::Compound => {
+                self.parser.enter().map_err(|e| self.locate(e, Some(&segment)))?;
+                self.stack.push((Frame::Compound, segment));
+                return Ok(NbtEvent::StartCompound(name));
+            }
+            NbtTagType::List => {
+                let header = self.parser.enter().and_then(|_| self.parser.read_list_header());
+                let (elem_ty, len) = header.map_err(|e| self.locate(e, Some(&segment)))?;
+                self.stack.push((Frame::List { ty: elem_ty, len, next: 0 }, segment));
+                return Ok(NbtEvent::StartList(name, elem_ty, len));
+            }
+            NbtTagType::ByteArray | NbtTagType::IntArray | NbtTagType::LongArray => {
+                let len = self.parser.read_length(array_limit(ty), element_size(ty)).map_err(|e| self.locate(e, Some(&segment)))?;
+                self.stack.push((Frame::Array { ty, remaining: len }, segment));
+                NbtEvent::StartArray(name, ty, len)
+            }
+            _ => {
+                let value = self.read_scalar(ty).map_err(|e| self.locate(e, Some(&segment)))?;
+                NbtEvent::Value(name, value)
+            }
+        };
+
+        Ok(event)
+    }
+
+    fn read_scalar(&mut self, ty: NbtTagType) -> Result<NbtScalar, NbtError> {
+        Ok(match ty {
+            NbtTagType::Byte => NbtScalar::Byte(self.parser.read_i8()?),
+            NbtTagType::Short => NbtScalar::Short(self.parser.read_i16()?),
+            NbtTagType::Int => NbtScalar::Int(self.parser.read_i32()?),
+            NbtTagType::Long => NbtScalar::Long(self.parser.read_i64()?),
+            NbtTagType::Float => NbtScalar::Float(self.parser.read_f32()?),
+            NbtTagType::Double => NbtScalar::Double(self.parser.read_f64()?),
+            NbtTagType::String => NbtScalar::String(self.parser.read_string()?),
+            // Lists of End tags are always empty, so there is no value to read
+            _ => return Err(NbtError::new(NbtErrorKind::InvalidListType(ty), self.parser.offset())),
+        })
+    }
+
+    fn pop(&mut self) {
+        if let Some((frame, _)) = self.stack.pop() {
+            if !matches!(frame, Frame::Array { .. }) {
+                self.parser.leave();
+            }
+        }
+    }
+
+    /// Adds the path of the open containers (and of the tag being read, if any) to an error.
+    fn locate(&self, mut error: NbtError, current: Option<&Segment>) -> NbtError {
+        let segments = self.stack.iter().map(|(_, segment)| segment).chain(current);
+        for segment in segments.collect::<Vec<_>>().into_iter().rev() {
+            error = match segment {
+                Segment::Root => error,
+                Segment::Key(key) => error.in_key(key),
+                Segment::Index(index) => error.in_index(*index),
+            };
+        }
+        error
+    }
+
+    fn skip_compound_entries(&mut self) -> Result<(), NbtError> {
+        loop {
+            let ty = self.parser.read_tag_type()?;
+            if ty == NbtTagType::End {
+                return Ok(());
+            }
+            self.skip_string()?;
+            self.skip_payloads(ty, 1)?;
+        }
+    }
+
+    /// Skips `count` consecutive values of type `ty` without decoding them.
+    fn skip_payloads(&mut self, ty: NbtTagType, count: usize) -> Result<(), NbtError> {
+        match ty {
+            NbtTagType::End => Ok(()),
+            NbtTagType::Byte | NbtTagType::Short | NbtTagType::Int | NbtTagType::Long | NbtTagType::Float | NbtTagType::Double => {
+                self.parser.skip(count as u64 * element_size(ty) as u64)
+            }
+            NbtTagType::ByteArray | NbtTagType::IntArray | NbtTagType::LongArray => {
+                for _ in 0..count {
+                    let len = self.parser.read_length(array_limit(ty), 1)?;
+                    self.parser.skip(len as u64 * element_size(ty) as u64)?;
+                }
+                Ok(())
+            }
+            NbtTagType::String => {
+                for _ in 0..count {
+                    self.skip_string()?;
+                }
+                Ok(())
+            }
+            NbtTagType::List => {
+                for _ in 0..count {
+                    self.parser.enter()?;
+                    let (elem_ty, len) = self.parser.read_list_header()?;
+                    self.skip_payloads(elem_ty, len)?;
+                    self.parser.leave();
+                }
+                Ok(())
+            }
+            NbtTagType::Compound => {
+                for _ in 0..count {
+                    self.parser.enter()?;
+                    self.skip_compound_entries()?;
+                    self.parser.leave();
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn skip_string(&mut self) -> Result<(), NbtError> {
+        let len = self.parser.read_i16()? as u16;
+        self.parser.skip(len as u64)
+    }
+}
+
+impl<R: Read> Iterator for NbtEventReader<R> {
+    type Item = Result<NbtEvent, NbtError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_event().transpose()
+    }
+}
+
+fn element_size(ty: NbtTagType) -> usize {
+    match ty {
+        NbtTagType::Byte | NbtTagType::ByteArray => 1,
+        NbtTagType::Short => 2,
+        NbtTagType::Int | NbtTagType::Float | NbtTagType::IntArray => 4,
+        _ => 8,
+    }
+}
+
+fn array_limit(ty: NbtTagType) -> &'static str {
+    match ty {
+        NbtTagType::ByteArray => "byte array length",
+        NbtTagType::IntArray => "int array length",
+        _ => "long array length",
+    }
+}
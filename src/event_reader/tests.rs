@@ -0,0 +1,114 @@
+#[cfg(test)]
+
+use super::*;
+
+fn events(bytes: &[u8]) -> Vec<NbtEvent> {
+    NbtEventReader::new(bytes, &ParseLimits::default()).collect::<Result<_, _>>().unwrap()
+}
+
+/// `{n: "x", l: [{v: 1}, {}], b: [B; 1, 2], d: 0.5d}`
+fn sample_bytes() -> Vec<u8> {
+    let mut bytes = vec![
+        10, 0, 0,
+            8, 0, 1, b'n', 0, 1, b'x',
+            9, 0, 1, b'l', 10, 0, 0, 0, 2,
+                3, 0, 1, b'v', 0, 0, 0, 1, 0,
+                0,
+            7, 0, 1, b'b', 0, 0, 0, 2, 1, 2,
+            6, 0, 1, b'd',
+    ];
+    bytes.extend_from_slice(&0.5f64.to_be_bytes());
+    bytes.push(0);
+    bytes
+}
+
+#[test]
+fn test_events() {
+    assert_eq!(events(&sample_bytes()), vec![
+        NbtEvent::StartCompound("".to_string()),
+        NbtEvent::Value("n".to_string(), NbtScalar::String("x".to_string())),
+        NbtEvent::StartList("l".to_string(), NbtTagType::Compound, 2),
+        NbtEvent::StartCompound("".to_string()),
+        NbtEvent::Value("v".to_string(), NbtScalar::Int(1)),
+        NbtEvent::End,
+        NbtEvent::StartCompound("".to_string()),
+        NbtEvent::End,
+        NbtEvent::End,
+        NbtEvent::StartArray("b".to_string(), NbtTagType::ByteArray, 2),
+        NbtEvent::ArrayChunk(NbtArrayChunk::Byte(vec![1, 2])),
+        NbtEvent::End,
+        NbtEvent::Value("d".to_string(), NbtScalar::Double(0.5)),
+        NbtEvent::End,
+    ]);
+}
+
+#[test]
+fn test_skip() {
+    let bytes = sample_bytes();
+    let mut reader = NbtEventReader::new(bytes.as_slice(), &ParseLimits::default());
+    let mut seen = Vec::new();
+
+    while let Some(event) = reader.next_event().unwrap() {
+        if let NbtEvent::StartList(..) | NbtEvent::StartArray(..) = event {
+            reader.skip_container().unwrap();
+        }
+        seen.push(event);
+    }
+
+    assert_eq!(seen.len(), 6);
+    assert_eq!(seen[4], NbtEvent::Value("d".to_string(), NbtScalar::Double(0.5)));
+    assert_eq!(reader.offset(), bytes.len() as u64);
+    assert_eq!(reader.depth(), 0);
+}
+
+#[test]
+fn test_large_array_is_chunked() {
+    let mut bytes = vec![10, 0, 0, 12, 0, 1, b'a'];
+    bytes.extend_from_slice(&100_000i32.to_be_bytes());
+    bytes.resize(bytes.len() + 100_000 * 8, 0);
+    bytes.push(0);
+
+    let chunks: Vec<usize> = events(&bytes).into_iter().filter_map(|event| match event {
+        NbtEvent::ArrayChunk(NbtArrayChunk::Long(values)) => Some(values.len()),
+        _ => None,
+    }).collect();
+
+    assert!(chunks.len() > 1);
+    assert_eq!(chunks.iter().sum::<usize>(), 100_000);
+}
+
+#[test]
+fn test_error_has_offset_and_path() {
+    let bytes = [
+        10, 0, 1, b'r',
+            9, 0, 1, b'l', 10, 0, 0, 0, 2,
+                3, 0, 1, b'v', 0, 0, 0, 1, 0,
+                3, 0, 1, b'v', 0, 0,
+    ];
+    let mut reader = NbtEventReader::new(bytes.as_slice(), &ParseLimits::default());
+    let error = reader.by_ref().find_map(|event| event.err()).unwrap();
+
+    assert_eq!(error.offset, 26);
+    assert_eq!(error.path(), "l[1].v");
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_depth_limit() {
+    let mut bytes = vec![10, 0, 0, 9, 0, 1, b'l'];
+    for _ in 1..64 {
+        bytes.extend_from_slice(&[9, 0, 0, 0, 1]);
+    }
+    bytes.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+
+    let limits = ParseLimits { max_depth: 16, ..ParseLimits::default() };
+    let mut reader = NbtEventReader::new(bytes.as_slice(), &limits);
+    let error = reader.find_map(|event| event.err()).unwrap();
+    assert!(matches!(error.kind, NbtErrorKind::LimitExceeded { limit: "nesting depth", .. }));
+
+    // Skipping checks the depth as well
+    let mut reader = NbtEventReader::new(bytes.as_slice(), &limits);
+    reader.next_event().unwrap();
+    reader.next_event().unwrap();
+    assert!(reader.skip_container().is_err());
+}
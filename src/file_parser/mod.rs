@@ -5,17 +5,29 @@
 // - 2023-12-17
 //
 // ## File Version
-// - 1.0.1
+// - 1.0.11
 //
 // ## Changelog
 // - 1.0.0: Initial version [caelunshun:2019-07-09]
 // - 1.0.1: Splitted the file_parser logic from the nbt_tag logic [mrmarkolinus:2023-12-17]
+// - 1.0.2: Parse functions return NbtError with byte offset and tag path [mrmarkolinus:2026-10-16]
+// - 1.0.3: Decode names and strings as Java Modified UTF-8 [mrmarkolinus:2026-10-16]
+// - 1.0.4: Configurable ParseLimits replace the hard-coded length caps [mrmarkolinus:2026-10-16]
+// - 1.0.5: Parser generic over io::Read, ReadMode::Stream implemented [mrmarkolinus:2026-10-16]
+// - 1.0.6: Parser primitives shared with the event reader [mrmarkolinus:2026-10-16]
+// - 1.0.7: Little-endian Bedrock flavor [mrmarkolinus:2026-10-16]
+// - 1.0.8: Network flavors, nameless root of any type and varints [mrmarkolinus:2026-10-16]
+// - 1.0.9: Names are only stored as compound keys [mrmarkolinus:2026-10-16]
+// - 1.0.10: parse_root for roots of any type, parse_all for several roots back to back [mrmarkolinus:2026-10-16]
+// - 1.0.11: Zero padding after the last root ends a stream of several roots [mrmarkolinus:2026-10-16]
 
 use crate::nbt_tag::*;
 use crate::generic_bin;
+use crate::error::{NbtError, NbtErrorKind};
+use crate::flavor::{Endianness, NbtFlavor};
+use crate::mutf8;
 
-use byteorder::{BigEndian, ReadBytesExt};
-use std::io::Cursor;
+use pyo3::prelude::*;
 use std::path::PathBuf;
 use std::fs;
 use std::io::BufReader;
@@ -29,10 +41,63 @@ pub enum ReadMode {
     Stream,
 }
 
+/// Limits applied while decoding NBT data, so untrusted input cannot exhaust memory or
+/// overflow the stack. The defaults accept any legitimate Minecraft file.
+#[pyclass]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Maximum nesting of compounds and lists.
+    #[pyo3(get, set)]
+    pub max_depth: usize,
+    /// Maximum number of elements in a list or in a byte, int or long array.
+    #[pyo3(get, set)]
+    pub max_array_len: usize,
+    /// Maximum number of bytes allocated for the decoded tree. This is an estimate based on
+    /// the size of each tag plus the heap used by names, strings and arrays.
+    #[pyo3(get, set)]
+    pub max_total_bytes: u64,
+    /// Maximum length in bytes of a tag name or string value.
+    #[pyo3(get, set)]
+    pub max_string_len: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        ParseLimits {
+            max_depth: 512,
+            max_array_len: 1 << 26,
+            max_total_bytes: 1 << 31,
+            max_string_len: u16::MAX as usize,
+        }
+    }
+}
+
+#[pymethods]
+impl ParseLimits {
+    #[new]
+    #[pyo3(signature = (max_depth=512, max_array_len=1 << 26, max_total_bytes=1 << 31, max_string_len=65535))]
+    pub fn new(max_depth: usize, max_array_len: usize, max_total_bytes: u64, max_string_len: usize) -> Self {
+        ParseLimits { max_depth, max_array_len, max_total_bytes, max_string_len }
+    }
+
+    /// Limits that never trigger, for trusted input only.
+    #[staticmethod]
+    pub fn unlimited() -> Self {
+        ParseLimits {
+            max_depth: usize::MAX,
+            max_array_len: usize::MAX,
+            max_total_bytes: u64::MAX,
+            max_string_len: usize::MAX,
+        }
+    }
+}
+
 pub struct FileParser {
     file_path: PathBuf,
     read_mode: ReadMode,
     file_type: generic_bin::FileType,
+    limits: ParseLimits,
+    flavor: NbtFlavor,
 }
 
 impl FileParser {
@@ -40,28 +105,48 @@ impl FileParser {
         FileParser { 
             file_path: file_path.to_path_buf(), 
             read_mode,
-            file_type
+            file_type,
+            limits: ParseLimits::default(),
+            flavor: NbtFlavor::Java,
         }
 
     }
 
+    /// Replaces the default limits used by `parse`.
+    pub fn with_limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Selects the binary flavor, Java by default.
+    pub fn with_flavor(mut self, flavor: NbtFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    /// Parses the file, decompressing gzip or zlib content if needed. In `Stream` mode the
+    /// file is decompressed and decoded on the fly, without ever holding it in memory.
     pub fn parse(&self) -> std::io::Result<NbtTag> {
-        let buf = match self.read_mode {
-            ReadMode::EntireFile => self.read_entire_file()?,
-            ReadMode::Stream => self.read_stream()?,
+        let root = match self.read_mode {
+            ReadMode::EntireFile => {
+                let buf = self.read_entire_file()?;
+                parse_compressed_reader_with_flavor(buf.as_slice(), self.flavor, &self.limits)?
+            },
+            ReadMode::Stream => parse_compressed_reader_with_flavor(self.read_stream()?, self.flavor, &self.limits)?,
         };
 
-        // Handle the result from parse_bytes
-        match parse_bytes(&buf) {
-            Ok(nbt_tag) => Ok(nbt_tag),  // On success, return the NbtTag
-            Err(_) => Err(std::io::Error::new(std::io::ErrorKind::Other, "Parse error")),  // On error, return an std::io::Error
-        }
+        Ok(root)
     }
 
+    /// Returns the raw (possibly compressed) content of the file.
     pub fn read (&self) -> std::io::Result<Vec<u8>> {
         let buf = match self.read_mode {
             ReadMode::EntireFile => self.read_entire_file()?,
-            ReadMode::Stream => self.read_stream()?,
+            ReadMode::Stream => {
+                let mut buf = Vec::new();
+                self.read_stream()?.read_to_end(&mut buf)?;
+                buf
+            },
         };
 
         Ok(buf)
@@ -82,191 +167,477 @@ impl FileParser {
         Ok(buf)
     }
 
-    fn read_stream(&self) -> std::io::Result<Vec<u8>> {
-        // Implementation for streaming read
-        // ...
-        //let mut buf = Vec::new();
-        //buf = "not implemented".as_bytes().to_vec();
-        todo!("not implemented yet");
-        //Ok(buf)
+    fn read_stream(&self) -> std::io::Result<BufReader<fs::File>> {
+        let file = fs::File::open(&self.file_path)?;
+        Ok(BufReader::new(file))
     }
 
 }
 
 
+/// Vectors are never pre-allocated beyond this many elements, whatever the length prefix
+/// says, so a forged length cannot trigger a huge allocation before the data is read.
+const PREALLOC_LIMIT: usize = 1 << 16;
+
+/// Arrays are read from the input in blocks of this many bytes.
+pub(crate) const ARRAY_BLOCK_SIZE: usize = 1 << 16;
+
 //TODO: put these guys in FileParser, workaround for region file
-pub fn parse_bytes(bytes: &[u8]) -> Result<NbtTag, ()> {
-    let mut cursor = Cursor::new(bytes);
-    
+pub fn parse_bytes(bytes: &[u8]) -> Result<NbtTag, NbtError> {
+    parse_bytes_with_limits(bytes, &ParseLimits::default())
+}
+
+pub fn parse_bytes_with_limits(bytes: &[u8], limits: &ParseLimits) -> Result<NbtTag, NbtError> {
+    parse_reader(bytes, limits)
+}
+
+/// Parses uncompressed NBT data from any reader, building the tree incrementally.
+/// Pass a buffered reader: the parser issues many small reads.
+pub fn parse_reader<R: Read>(reader: R, limits: &ParseLimits) -> Result<NbtTag, NbtError> {
+    parse_reader_with_flavor(reader, NbtFlavor::Java, limits)
+}
+
+/// Parses uncompressed data of the given flavor, e.g. a Bedrock `.mcstructure` file or an
+/// NBT blob captured from the network. With `JavaNetwork` the root can be any tag.
+pub fn parse_bytes_with_flavor(bytes: &[u8], flavor: NbtFlavor, limits: &ParseLimits) -> Result<NbtTag, NbtError> {
+    parse_reader_with_flavor(bytes, flavor, limits)
+}
+
+pub fn parse_reader_with_flavor<R: Read>(reader: R, flavor: NbtFlavor, limits: &ParseLimits) -> Result<NbtTag, NbtError> {
+    let mut parser = Parser::new(reader, limits).with_flavor(flavor);
+
     // Read root compound - read type first
-    let ty = {
-        let id = cursor.read_u8().map_err(|_| ())?;
-        NbtTagType::from_id(id).ok_or_else(|| ())?
-    };
-    if ty != NbtTagType::Compound {
-        return Err(());
+    let ty = parser.read_tag_type()?;
+    if !flavor.named_root() {
+        // Network NBT: no name, and any tag can be the root
+        if ty == NbtTagType::End {
+            return Err(NbtError::new(NbtErrorKind::InvalidRoot(ty), 0));
+        }
+        return parser.parse_value(ty);
     }
-
-    let name_len = cursor.read_i16::<BigEndian>().map_err(|_| ())?;
-    let mut name = String::with_capacity(name_len as usize);
-    for _ in 0..name_len {
-        let ch = cursor.read_u8().map_err(|_| ())?;
-        name.push(ch as char);
+    if ty != NbtTagType::Compound {
+        return Err(NbtError::new(NbtErrorKind::InvalidRoot(ty), 0));
     }
 
-    let root = parse_compound(&mut cursor, name)?;
+    let name = parser.read_name()?;
+    let mut root = parser.parse_compound()?;
+    root.name = name;
 
     Ok(NbtTag::Compound(root))
 }
 
-fn parse_compound(cursor: &mut Cursor<&[u8]>, name: String) -> Result<NbtTagCompound, ()> {
-    let mut compound = NbtTagCompound::new(name.as_str());
+/// Parses one root of any type, e.g. a list root written by some tools. Only compounds
+/// keep the name of the root, in `NbtTagCompound::name`; the name of other roots is dropped.
+pub fn parse_root(bytes: &[u8]) -> Result<NbtTag, NbtError> {
+    parse_root_with_flavor(bytes, NbtFlavor::Java, &ParseLimits::default())
+}
+
+pub fn parse_root_with_flavor(bytes: &[u8], flavor: NbtFlavor, limits: &ParseLimits) -> Result<NbtTag, NbtError> {
+    let mut parser = Parser::new(bytes, limits).with_flavor(flavor);
+    match parser.read_root()? {
+        Some((_, root)) => Ok(root),
+        None => Err(NbtError::new(NbtErrorKind::UnexpectedEof, 0)),
+    }
+}
+
+/// Parses every root of a stream holding several roots back to back, e.g. an appended NBT
+/// log. Roots can be of any type, as in `parse_root`. Empty input has no roots, and zero
+/// bytes after the last root, such as padding up to a block size, are ignored.
+pub fn parse_all(bytes: &[u8]) -> Result<Vec<NbtTag>, NbtError> {
+    parse_all_with_flavor(bytes, NbtFlavor::Java, &ParseLimits::default())
+}
+
+pub fn parse_all_with_flavor(bytes: &[u8], flavor: NbtFlavor, limits: &ParseLimits) -> Result<Vec<NbtTag>, NbtError> {
+    Ok(parse_all_named(bytes, flavor, limits)?.into_iter().map(|(_, root)| root).collect())
+}
 
-    // Read values until NBT_End is reached
+/// Every root of the stream with its name, empty for flavors without root names. The
+/// limits apply to all the roots together.
+pub(crate) fn parse_all_named<R: Read>(reader: R, flavor: NbtFlavor, limits: &ParseLimits) -> Result<Vec<(String, NbtTag)>, NbtError> {
+    let mut parser = Parser::new(reader, limits).with_flavor(flavor);
+    let mut roots = Vec::new();
     loop {
-        let type_id = cursor.read_u8().map_err(|_| ())?;
+        match parser.read_root() {
+            Ok(Some(root)) => roots.push(root),
+            Ok(None) => return Ok(roots),
+            Err(e) if matches!(e.kind, NbtErrorKind::InvalidRoot(NbtTagType::End)) && !roots.is_empty() => {
+                return if parser.rest_is_zero()? { Ok(roots) } else { Err(e) };
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
 
-        let ty = NbtTagType::from_id(type_id).ok_or_else(|| ())?;
-        if ty == NbtTagType::End {
-            // Finish early - nothing more to read
-            break;
+/// Parses NBT data from any reader (file, stdin, socket...), detecting gzip or zlib
+/// compression from the first bytes and decompressing on the fly.
+/// Offsets in errors refer to the decompressed data.
+pub fn parse_compressed_reader<R: Read>(reader: R, limits: &ParseLimits) -> Result<NbtTag, NbtError> {
+    parse_compressed_reader_with_flavor(reader, NbtFlavor::Java, limits)
+}
+
+pub fn parse_compressed_reader_with_flavor<R: Read>(reader: R, flavor: NbtFlavor, limits: &ParseLimits) -> Result<NbtTag, NbtError> {
+    let decompressed = generic_bin::decompress_reader(BufReader::new(reader)).map_err(|e| NbtError::from_io(e, 0))?;
+    parse_reader_with_flavor(BufReader::new(decompressed), flavor, limits)
+}
+
+/// Decoding state shared by the parse functions. Errors are built with the offset of the
+/// read that failed; the tag path is added by the callers while the error propagates.
+pub(crate) struct Parser<R: Read> {
+    reader: R,
+    offset: u64,
+    limits: ParseLimits,
+    depth: usize,
+    allocated: u64,
+    flavor: NbtFlavor,
+}
+
+/// Picks the `from_be_bytes` or `from_le_bytes` conversion of a number type.
+macro_rules! from_bytes_fn {
+    ($self:ident, $ty:ty) => {
+        match $self.flavor.endianness() {
+            Endianness::Big => <$ty>::from_be_bytes,
+            Endianness::Little => <$ty>::from_le_bytes,
+        }
+    };
+}
+
+impl<R: Read> Parser<R> {
+    pub(crate) fn new(reader: R, limits: &ParseLimits) -> Self {
+        Parser { reader, offset: 0, limits: *limits, depth: 0, allocated: 0, flavor: NbtFlavor::Java }
+    }
+
+    pub(crate) fn with_flavor(mut self, flavor: NbtFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    pub(crate) fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Discards `len` bytes of input.
+    pub(crate) fn skip(&mut self, len: u64) -> Result<(), NbtError> {
+        let offset = self.offset;
+        let skipped = std::io::copy(&mut (&mut self.reader).take(len), &mut std::io::sink()).map_err(|e| NbtError::from_io(e, offset))?;
+        self.offset += skipped;
+        if skipped < len {
+            return Err(NbtError::new(NbtErrorKind::UnexpectedEof, self.offset));
         }
 
-        // Read name
-        let name = {
-            let len = cursor.read_i16::<BigEndian>().map_err(|_| ())?;
-            let mut name = String::with_capacity(len as usize);
+        Ok(())
+    }
+
+    pub(crate) fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), NbtError> {
+        let offset = self.offset;
+        self.reader.read_exact(buf).map_err(|e| NbtError::from_io(e, offset))?;
+        self.offset += buf.len() as u64;
+        Ok(())
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], NbtError> {
+        let mut buf = [0u8; N];
+        self.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    pub(crate) fn read_i8(&mut self) -> Result<i8, NbtError> {
+        Ok(i8::from_be_bytes(self.read_bytes()?))
+    }
+
+    pub(crate) fn read_i16(&mut self) -> Result<i16, NbtError> {
+        Ok(from_bytes_fn!(self, i16)(self.read_bytes()?))
+    }
+
+    pub(crate) fn read_i32(&mut self) -> Result<i32, NbtError> {
+        if self.flavor.varint() {
+            let value = self.read_varint(5)? as u32;
+            return Ok((value >> 1) as i32 ^ -((value & 1) as i32));
+        }
+        Ok(from_bytes_fn!(self, i32)(self.read_bytes()?))
+    }
+
+    pub(crate) fn read_i64(&mut self) -> Result<i64, NbtError> {
+        if self.flavor.varint() {
+            let value = self.read_varint(10)?;
+            return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+        }
+        Ok(from_bytes_fn!(self, i64)(self.read_bytes()?))
+    }
+
+    /// Reads an unsigned LEB128 varint of at most `max_bytes` bytes.
+    fn read_varint(&mut self, max_bytes: u32) -> Result<u64, NbtError> {
+        let offset = self.offset;
+        let mut value = 0u64;
+        for i in 0..max_bytes {
+            let [byte] = self.read_bytes()?;
+            value |= u64::from(byte & 0x7F) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(NbtError::new(NbtErrorKind::InvalidVarint, offset))
+    }
+
+    pub(crate) fn read_f32(&mut self) -> Result<f32, NbtError> {
+        Ok(from_bytes_fn!(self, f32)(self.read_bytes()?))
+    }
+
+    pub(crate) fn read_f64(&mut self) -> Result<f64, NbtError> {
+        Ok(from_bytes_fn!(self, f64)(self.read_bytes()?))
+    }
+
+    /// Reads the elements of an int array, which are varints in network Bedrock NBT.
+    fn read_i32_array(&mut self, len: usize) -> Result<Vec<i32>, NbtError> {
+        if self.flavor.varint() {
+            let mut values = Vec::with_capacity(len.min(PREALLOC_LIMIT));
             for _ in 0..len {
-                let ch = cursor.read_u8().map_err(|_| ())?;
-                name.push(ch as char);
+                values.push(self.read_i32()?);
             }
+            return Ok(values);
+        }
+        self.read_array(len, from_bytes_fn!(self, i32))
+    }
 
-            name
-        };
+    fn read_i64_array(&mut self, len: usize) -> Result<Vec<i64>, NbtError> {
+        if self.flavor.varint() {
+            let mut values = Vec::with_capacity(len.min(PREALLOC_LIMIT));
+            for _ in 0..len {
+                values.push(self.read_i64()?);
+            }
+            return Ok(values);
+        }
+        self.read_array(len, from_bytes_fn!(self, i64))
+    }
 
-        // Read value
-        let value = parse_value(cursor, ty, name.clone())?;
+    /// Reads `len` fixed-size elements in blocks rather than one by one.
+    pub(crate) fn read_array<T, const N: usize>(&mut self, len: usize, convert: fn([u8; N]) -> T) -> Result<Vec<T>, NbtError> {
+        let mut values = Vec::with_capacity(len.min(PREALLOC_LIMIT));
+        let mut block = vec![0u8; len.saturating_mul(N).min(ARRAY_BLOCK_SIZE)];
+        let mut remaining = len;
+
+        while remaining > 0 {
+            let count = remaining.min(ARRAY_BLOCK_SIZE / N);
+            let bytes = &mut block[..count * N];
+            self.read_exact(bytes)?;
+            values.extend(bytes.chunks_exact(N).map(|b| convert(b.try_into().unwrap())));
+            remaining -= count;
+        }
 
-        compound.values.insert(name, value);
+        Ok(values)
     }
 
-    Ok(compound)
-}
+    fn limit_exceeded(&self, limit: &'static str, value: u64, max: u64) -> NbtError {
+        NbtError::new(NbtErrorKind::LimitExceeded { limit, value, max }, self.offset)
+    }
 
-fn parse_list(cursor: &mut Cursor<&[u8]>, name: String) -> Result<NbtTagList, ()> {
-    // Type of values contained in the list
-    let ty = {
-        let id = cursor.read_u8().map_err(|_| ())?;
-        NbtTagType::from_id(id).ok_or_else(|| ())?
-    };
+    /// Accounts for `bytes` more bytes of decoded data.
+    pub(crate) fn allocate(&mut self, bytes: u64) -> Result<(), NbtError> {
+        self.allocated = self.allocated.saturating_add(bytes);
+        if self.allocated > self.limits.max_total_bytes {
+            return Err(self.limit_exceeded("total allocated bytes", self.allocated, self.limits.max_total_bytes));
+        }
 
-    // Length of list, in number of values (not bytes)
-    let len = cursor.read_i32::<BigEndian>().map_err(|_| ())?;
-    if len > 65536 {
-        return Err(());
+        Ok(())
     }
 
-    let mut values = Vec::with_capacity(len as usize);
+    pub(crate) fn enter(&mut self) -> Result<(), NbtError> {
+        self.depth += 1;
+        if self.depth > self.limits.max_depth {
+            return Err(self.limit_exceeded("nesting depth", self.depth as u64, self.limits.max_depth as u64));
+        }
 
-    for _ in 0..len {
-        let val = parse_value(cursor, ty, "".to_string())?;
-        // expose to python
-        //let py_val = PyNbtTag::new(&val);
-        values.push(val);
+        Ok(())
     }
 
+    pub(crate) fn leave(&mut self) {
+        self.depth -= 1;
+    }
 
-    Ok(NbtTagList::new(name, ty, values))
-}
+    pub(crate) fn read_tag_type(&mut self) -> Result<NbtTagType, NbtError> {
+        let offset = self.offset;
+        let [id] = self.read_bytes()?;
+        NbtTagType::from_id(id).ok_or_else(|| NbtError::new(NbtErrorKind::UnknownTagId(id), offset))
+    }
 
-fn parse_value(cursor: &mut Cursor<&[u8]>, ty: NbtTagType, name: String) -> Result<NbtTag, ()> {
-    Ok(match ty {
-        NbtTagType::End => unreachable!(), // Should already be covered
-        NbtTagType::Byte => {
-            let x = cursor.read_i8().map_err(|_| ())?;
-            NbtTag::Byte(NbtTagByte::new(name.clone(), x))
+    /// Reads an `i32` length prefix of a list or array and charges `element_size` bytes
+    /// per element against the allocation limit.
+    pub(crate) fn read_length(&mut self, limit: &'static str, element_size: usize) -> Result<usize, NbtError> {
+        let offset = self.offset;
+        let len = self.read_i32()?;
+        if len < 0 {
+            return Err(NbtError::new(NbtErrorKind::NegativeLength(len), offset));
         }
-        NbtTagType::Short => {
-            let x = cursor.read_i16::<BigEndian>().map_err(|_| ())?;
-            NbtTag::Short(NbtTagShort::new(name.clone(), x))
-        }
-        NbtTagType::Int => {
-            let x = cursor.read_i32::<BigEndian>().map_err(|_| ())?;
-            NbtTag::Int(NbtTagInt::new(name.clone(), x))
+
+        let len = len as usize;
+        if len > self.limits.max_array_len {
+            return Err(NbtError::new(NbtErrorKind::LimitExceeded { limit, value: len as u64, max: self.limits.max_array_len as u64 }, offset));
         }
-        NbtTagType::Long => {
-            let x = cursor.read_i64::<BigEndian>().map_err(|_| ())?;
-            NbtTag::Long(NbtTagLong::new(name.clone(), x))
+        self.allocate((len as u64).saturating_mul(element_size as u64))?;
+
+        Ok(len)
+    }
+
+    pub(crate) fn read_string(&mut self) -> Result<String, NbtError> {
+        let offset = self.offset;
+        let len = match self.flavor.varint() {
+            true => self.read_varint(5)? as u32 as usize,
+            false => from_bytes_fn!(self, u16)(self.read_bytes()?) as usize,
+        };
+        if len > self.limits.max_string_len {
+            return Err(NbtError::new(NbtErrorKind::LimitExceeded { limit: "string length", value: len as u64, max: self.limits.max_string_len as u64 }, offset));
         }
-        NbtTagType::Float => {
-            let x = cursor.read_f32::<BigEndian>().map_err(|_| ())?;
-            NbtTag::Float(NbtTagFloat::new(name.clone(), x))
+        self.allocate(len as u64)?;
+
+        let mut buf = vec![0u8; len];
+        self.read_exact(&mut buf)?;
+
+        if self.flavor.modified_utf8() {
+            Ok(mutf8::decode_owned(buf))
         }
-        NbtTagType::Double => {
-            let x = cursor.read_f64::<BigEndian>().map_err(|_| ())?;
-            NbtTag::Double(NbtTagDouble::new(name.clone(), x))
+        else {
+            Ok(String::from_utf8(buf).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()))
         }
-        NbtTagType::ByteArray => {
-            let len = cursor.read_i32::<BigEndian>().map_err(|_| ())?;
-            if len > 65536 {
-                // Yeah... no.
-                return Err(());
-            }
+    }
 
-            let mut buf = Vec::with_capacity(len as usize);
-            for _ in 0..len {
-                let x = cursor.read_i8().map_err(|_| ())?;
-                buf.push(x);
+    /// Reads the next root, of any type, with its name. Returns `None` when the input ends
+    /// before a root; input ending inside a root is an `UnexpectedEof` error.
+    fn read_root(&mut self) -> Result<Option<(String, NbtTag)>, NbtError> {
+        let offset = self.offset;
+        let mut id = [0u8];
+        loop {
+            match self.reader.read(&mut id) {
+                Ok(0) => return Ok(None),
+                Ok(_) => break,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(NbtError::from_io(e, offset)),
             }
+        }
+        self.offset += 1;
 
-            NbtTag::ByteArray(NbtTagByteArray::new(name.clone(), buf))
+        let ty = NbtTagType::from_id(id[0]).ok_or_else(|| NbtError::new(NbtErrorKind::UnknownTagId(id[0]), offset))?;
+        if ty == NbtTagType::End {
+            return Err(NbtError::new(NbtErrorKind::InvalidRoot(ty), offset));
         }
-        NbtTagType::String => {
-            let len = cursor.read_u16::<BigEndian>().map_err(|_| ())?;
-            let mut buf = String::with_capacity(len as usize);
+        let name = if self.flavor.named_root() { self.read_name()? } else { String::new() };
 
-            for _ in 0..len {
-                let ch = cursor.read_u8().map_err(|_| ())?;
-                buf.push(ch as char);
+        let root = match self.parse_value(ty)? {
+            NbtTag::Compound(mut compound) => {
+                compound.name = name.clone();
+                NbtTag::Compound(compound)
             }
+            root => root,
+        };
+        Ok(Some((name, root)))
+    }
 
-            NbtTag::String(NbtTagString::new(name.clone(), buf))
+    /// Reads the rest of the input and tells whether it only holds zero bytes.
+    fn rest_is_zero(&mut self) -> Result<bool, NbtError> {
+        let mut buffer = [0u8; 4096];
+        loop {
+            let read = match self.reader.read(&mut buffer) {
+                Ok(0) => return Ok(true),
+                Ok(read) => read,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(NbtError::from_io(e, self.offset)),
+            };
+            if buffer[..read].iter().any(|byte| *byte != 0) {
+                return Ok(false);
+            }
+            self.offset += read as u64;
         }
-        NbtTagType::List => {
-            let list = parse_list(cursor, name)?;
-            NbtTag::List(list)
+    }
+
+    pub(crate) fn read_name(&mut self) -> Result<String, NbtError> {
+        self.read_string()
+    }
+
+    /// Parses the entries of a compound. Names are only stored as keys, a nested compound
+    /// has an empty `name`.
+    fn parse_compound(&mut self) -> Result<NbtTagCompound, NbtError> {
+        self.enter()?;
+        let mut compound = NbtTagCompound::new("");
+
+        // Read values until NBT_End is reached
+        loop {
+            let ty = self.read_tag_type()?;
+            if ty == NbtTagType::End {
+                // Finish early - nothing more to read
+                break;
+            }
+
+            let name = self.read_name()?;
+            self.allocate(std::mem::size_of::<NbtTag>() as u64).map_err(|e| e.in_key(&name))?;
+            let value = self.parse_value(ty).map_err(|e| e.in_key(&name))?;
+
+            compound.values.insert(name, value);
         }
-        NbtTagType::Compound => {
-            let compound = parse_compound(cursor, name)?;
-            NbtTag::Compound(compound)
+
+        self.leave();
+        Ok(compound)
+    }
+
+    /// Reads the element type and length of a list.
+    pub(crate) fn read_list_header(&mut self) -> Result<(NbtTagType, usize), NbtError> {
+        // Type of values contained in the list
+        let ty = self.read_tag_type()?;
+
+        // Length of list, in number of values (not bytes)
+        let offset = self.offset;
+        let len = self.read_length("list length", std::mem::size_of::<NbtTag>())?;
+        if ty == NbtTagType::End && len > 0 {
+            return Err(NbtError::new(NbtErrorKind::InvalidListType(ty), offset));
         }
-        NbtTagType::IntArray => {
-            let len = cursor.read_i32::<BigEndian>().map_err(|_| ())?;
-            if len > 65536 {
-                return Err(());
-            }
 
-            let mut buf = Vec::with_capacity(len as usize);
-            for _ in 0..len {
-                let x = cursor.read_i32::<BigEndian>().map_err(|_| ())?;
-                buf.push(x);
-            }
+        Ok((ty, len))
+    }
 
-            NbtTag::IntArray(NbtTagIntArray::new(name.clone(), buf))
+    fn parse_list(&mut self) -> Result<NbtTagList, NbtError> {
+        self.enter()?;
+        let (ty, len) = self.read_list_header()?;
+
+        let mut values = Vec::with_capacity(len.min(PREALLOC_LIMIT));
+        for index in 0..len {
+            let val = self.parse_value(ty).map_err(|e| e.in_index(index))?;
+            values.push(val);
         }
-        NbtTagType::LongArray => {
-            let len = cursor.read_i32::<BigEndian>().map_err(|_| ())?;
-            if len > 65536 {
-                return Err(());
-            }
 
-            let mut buf = Vec::with_capacity(len as usize);
-            for _ in 0..len {
-                let x = cursor.read_i64::<BigEndian>().map_err(|_| ())?;
-                buf.push(x);
-            }
+        self.leave();
+        Ok(NbtTagList::new(ty, values))
+    }
 
-            NbtTag::LongArray(NbtTagLongArray::new(name.clone(), buf))
+    fn parse_value(&mut self, ty: NbtTagType) -> Result<NbtTag, NbtError> {
+        // Only lists and compounds recurse. Keeping the other types in a separate function
+        // keeps this frame small, which matters for deeply nested (or hostile) input.
+        match ty {
+            NbtTagType::List => Ok(NbtTag::List(self.parse_list()?)),
+            NbtTagType::Compound => Ok(NbtTag::Compound(self.parse_compound()?)),
+            _ => self.parse_leaf(ty),
         }
-    })
+    }
+
+    fn parse_leaf(&mut self, ty: NbtTagType) -> Result<NbtTag, NbtError> {
+        Ok(match ty {
+            NbtTagType::End | NbtTagType::List | NbtTagType::Compound => unreachable!(), // Should already be covered
+            NbtTagType::Byte => NbtTag::Byte(NbtTagByte::new(self.read_i8()?)),
+            NbtTagType::Short => NbtTag::Short(NbtTagShort::new(self.read_i16()?)),
+            NbtTagType::Int => NbtTag::Int(NbtTagInt::new(self.read_i32()?)),
+            NbtTagType::Long => NbtTag::Long(NbtTagLong::new(self.read_i64()?)),
+            NbtTagType::Float => NbtTag::Float(NbtTagFloat::new(self.read_f32()?)),
+            NbtTagType::Double => NbtTag::Double(NbtTagDouble::new(self.read_f64()?)),
+            NbtTagType::ByteArray => {
+                let len = self.read_length("byte array length", 1)?;
+                NbtTag::ByteArray(NbtTagByteArray::new(self.read_array(len, i8::from_be_bytes)?))
+            }
+            NbtTagType::String => NbtTag::String(NbtTagString::new(self.read_string()?)),
+            NbtTagType::IntArray => {
+                let len = self.read_length("int array length", 4)?;
+                NbtTag::IntArray(NbtTagIntArray::new(self.read_i32_array(len)?))
+            }
+            NbtTagType::LongArray => {
+                let len = self.read_length("long array length", 8)?;
+                NbtTag::LongArray(NbtTagLongArray::new(self.read_i64_array(len)?))
+            }
+        })
+    }
 }
@@ -32,13 +32,338 @@ fn test_read_entire_file() {
   //  assert!(file_parser.read_stream().is_err());
 //}
 
+/// Root compound "r" holding a list "l" of two compounds, the second one truncated
+/// in the middle of its int "v".
+fn truncated_list_bytes() -> Vec<u8> {
+    vec![
+        10, 0, 1, b'r',
+            9, 0, 1, b'l', 10, 0, 0, 0, 2,
+                3, 0, 1, b'v', 0, 0, 0, 1, 0,
+                3, 0, 1, b'v', 0, 0,
+    ]
+}
+
 #[test]
 fn test_parse_bytes() {
-    // Provide a byte slice and test the parse_bytes function,
-    // asserting the expected outcome.
-    // Example:
-    // let bytes = [/* byte data */];
-    // let result = parse_bytes(&bytes);
-    // assert!(result.is_ok());
-    // assert_eq!(result.unwrap(), /* expected NbtTag value */);
+    let bytes = [10, 0, 1, b'r', 3, 0, 1, b'v', 0, 0, 0, 5, 0];
+    let root = parse_bytes(&bytes).unwrap();
+    let compound = root.compound_as_ref().unwrap();
+
+    assert_eq!(compound.name, "r");
+    assert_eq!(compound.values.get("v").unwrap().int().unwrap().value, 5);
+}
+
+#[test]
+fn test_parse_bytes_truncated_reports_offset_and_path() {
+    let error = parse_bytes(&truncated_list_bytes()).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::UnexpectedEof));
+    assert_eq!(error.offset, 26);
+    assert_eq!(error.path(), "l[1].v");
+}
+
+#[test]
+fn test_parse_bytes_unknown_tag_id() {
+    let bytes = [10, 0, 0, 42];
+    let error = parse_bytes(&bytes).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::UnknownTagId(42)));
+    assert_eq!(error.offset, 3);
+}
+
+#[test]
+fn test_parse_bytes_negative_length() {
+    let bytes = [10, 0, 0, 11, 0, 1, b'a', 0xFF, 0xFF, 0xFF, 0xFF];
+    let error = parse_bytes(&bytes).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::NegativeLength(-1)));
+    assert_eq!(error.path(), "a");
+}
+
+#[test]
+fn test_parse_bytes_rejects_non_compound_root() {
+    let bytes = [1, 0, 0, 5];
+    let error = parse_bytes(&bytes).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::InvalidRoot(NbtTagType::Byte)));
+}
+
+#[test]
+fn test_parse_bytes_modified_utf8_strings() {
+    // Name "é" and value "a\0b" encoded as Modified UTF-8
+    let bytes = [10, 0, 0, 8, 0, 2, 0xC3, 0xA9, 0, 4, b'a', 0xC0, 0x80, b'b', 0];
+    let root = parse_bytes(&bytes).unwrap();
+    let compound = root.compound_as_ref().unwrap();
+
+    assert_eq!(compound.values.get("é").unwrap().string().unwrap().value, "a\0b");
+}
+
+/// Root compound holding a long array "data" with `len` zeroed elements.
+fn long_array_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![10, 0, 0, 12, 0, 4, b'd', b'a', b't', b'a'];
+    bytes.extend_from_slice(&(len as i32).to_be_bytes());
+    bytes.resize(bytes.len() + len * 8, 0);
+    bytes.push(0);
+    bytes
+}
+
+/// Root compound with `depth` nested lists of lists.
+fn nested_lists_bytes(depth: usize) -> Vec<u8> {
+    let mut bytes = vec![10, 0, 0, 9, 0, 1, b'l'];
+    for _ in 1..depth {
+        bytes.extend_from_slice(&[9, 0, 0, 0, 1]);
+    }
+    bytes.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+    bytes
+}
+
+#[test]
+fn test_parse_bytes_accepts_large_arrays() {
+    let root = parse_bytes(&long_array_bytes(100_000)).unwrap();
+    let compound = root.compound_as_ref().unwrap();
+
+    assert_eq!(compound.values.get("data").unwrap().long_array_as_ref().unwrap().values.len(), 100_000);
+}
+
+#[test]
+fn test_parse_limits_array_length() {
+    let limits = ParseLimits { max_array_len: 16, ..ParseLimits::default() };
+    let error = parse_bytes_with_limits(&long_array_bytes(17), &limits).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::LimitExceeded { value: 17, max: 16, .. }));
+    assert_eq!(error.path(), "data");
+}
+
+#[test]
+fn test_parse_limits_total_bytes() {
+    let limits = ParseLimits { max_total_bytes: 1024, ..ParseLimits::default() };
+
+    assert!(parse_bytes_with_limits(&long_array_bytes(100), &limits).is_ok());
+    let error = parse_bytes_with_limits(&long_array_bytes(200), &limits).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::LimitExceeded { limit: "total allocated bytes", .. }));
+}
+
+#[test]
+fn test_parse_limits_depth() {
+    let limits = ParseLimits { max_depth: 10, ..ParseLimits::default() };
+
+    // The root compound counts as one level
+    assert!(parse_bytes_with_limits(&nested_lists_bytes(9), &limits).is_ok());
+    let error = parse_bytes_with_limits(&nested_lists_bytes(10), &limits).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::LimitExceeded { limit: "nesting depth", .. }));
+    assert_eq!(error.path(), "l[0][0][0][0][0][0][0][0][0]");
+}
+
+#[test]
+fn test_parse_limits_depth_default_stops_deep_recursion() {
+    let error = parse_bytes(&nested_lists_bytes(100_000)).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::LimitExceeded { limit: "nesting depth", .. }));
+}
+
+#[test]
+fn test_parse_limits_string_length() {
+    let limits = ParseLimits { max_string_len: 3, ..ParseLimits::default() };
+    let bytes = [10, 0, 0, 8, 0, 1, b's', 0, 4, b'a', b'b', b'c', b'd', 0];
+    let error = parse_bytes_with_limits(&bytes, &limits).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::LimitExceeded { limit: "string length", .. }));
+}
+
+#[test]
+fn test_parse_bytes_rejects_end_list_with_elements() {
+    let bytes = [10, 0, 0, 9, 0, 1, b'l', 0, 0, 0, 0, 3, 0];
+    let error = parse_bytes(&bytes).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::InvalidListType(NbtTagType::End)));
+}
+
+/// Reader handing out a single byte per call, like a slow pipe or socket.
+struct OneByteReader<'a>(&'a [u8]);
+
+impl Read for OneByteReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = self.0[0];
+        self.0 = &self.0[1..];
+        Ok(1)
+    }
+}
+
+#[test]
+fn test_parse_reader_byte_by_byte() {
+    let bytes = long_array_bytes(1000);
+    let root = parse_reader(OneByteReader(&bytes), &ParseLimits::default()).unwrap();
+    let compound = root.compound_as_ref().unwrap();
+
+    assert_eq!(compound.values.get("data").unwrap().long_array_as_ref().unwrap().values.len(), 1000);
+
+    let error = parse_reader(OneByteReader(&truncated_list_bytes()), &ParseLimits::default()).unwrap_err();
+    assert_eq!(error.offset, 26);
+    assert_eq!(error.path(), "l[1].v");
+}
+
+#[test]
+fn test_parse_compressed_reader() {
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use flate2::Compression;
+    use std::io::Write;
+
+    let bytes = long_array_bytes(5000);
+
+    let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+    gzip.write_all(&bytes).unwrap();
+    let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+    zlib.write_all(&bytes).unwrap();
+
+    for input in [gzip.finish().unwrap(), zlib.finish().unwrap(), bytes] {
+        let root = parse_compressed_reader(OneByteReader(&input), &ParseLimits::default()).unwrap();
+        let compound = root.compound_as_ref().unwrap();
+        assert_eq!(compound.values.get("data").unwrap().long_array_as_ref().unwrap().values.len(), 5000);
+    }
+}
+
+/// Root compound "r" in the Bedrock flavor: a short, a string and an int array, with
+/// little-endian numbers and length prefixes.
+fn little_endian_bytes() -> Vec<u8> {
+    vec![
+        10, 1, 0, b'r',
+            2, 1, 0, b's', 0x34, 0x12,
+            8, 1, 0, b't', 2, 0, b'h', b'i',
+            11, 1, 0, b'a', 2, 0, 0, 0, 1, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF,
+        0,
+    ]
+}
+
+#[test]
+fn test_parse_bytes_bedrock_flavor() {
+    let root = parse_bytes_with_flavor(&little_endian_bytes(), NbtFlavor::Bedrock, &ParseLimits::default()).unwrap();
+    let compound = root.compound_as_ref().unwrap();
+
+    assert_eq!(compound.name, "r");
+    assert_eq!(compound.values.get("s").unwrap().short().unwrap().value, 0x1234);
+    assert_eq!(compound.values.get("t").unwrap().string().unwrap().value, "hi");
+    assert_eq!(compound.values.get("a").unwrap().int_array().unwrap().values, vec![1, -1]);
+}
+
+#[test]
+fn test_parse_bytes_bedrock_flavor_as_java_fails() {
+    // The name length 0x0100 runs past the end of the data
+    assert!(parse_bytes(&little_endian_bytes()).is_err());
+}
+
+#[test]
+fn test_parse_bytes_bedrock_strings_are_utf8() {
+    // U+1F600 is four bytes in UTF-8, Modified UTF-8 would escape them as raw bytes
+    let mut bytes = vec![10, 0, 0, 8, 1, 0, b's', 4, 0];
+    bytes.extend_from_slice("😀".as_bytes());
+    bytes.push(0);
+
+    let root = parse_bytes_with_flavor(&bytes, NbtFlavor::Bedrock, &ParseLimits::default()).unwrap();
+    let compound = root.compound_as_ref().unwrap();
+
+    assert_eq!(compound.values.get("s").unwrap().string().unwrap().value, "😀");
+}
+
+#[test]
+fn test_parse_bytes_java_network_nameless_root() {
+    // Since 1.20.2 a text component can be sent as a bare string root
+    let bytes = [8, 0, 5, b'h', b'e', b'l', b'l', b'o'];
+    let root = parse_bytes_with_flavor(&bytes, NbtFlavor::JavaNetwork, &ParseLimits::default()).unwrap();
+
+    assert_eq!(root.string().unwrap().value, "hello");
+
+    let bytes = [10, 3, 0, 1, b'v', 0, 0, 0, 5, 0];
+    let root = parse_bytes_with_flavor(&bytes, NbtFlavor::JavaNetwork, &ParseLimits::default()).unwrap();
+    let compound = root.compound_as_ref().unwrap();
+
+    assert_eq!(compound.name, "");
+    assert_eq!(compound.values.get("v").unwrap().int().unwrap().value, 5);
+}
+
+#[test]
+fn test_parse_bytes_bedrock_network_varints() {
+    let bytes = [
+        10, 0,
+            3, 1, b'i', 0xAC, 0x02,
+            4, 1, b'l', 0x01,
+            8, 1, b's', 2, b'h', b'i',
+            11, 1, b'a', 4, 0x02, 0x03,
+            2, 1, b'h', 0x34, 0x12,
+        0,
+    ];
+    let root = parse_bytes_with_flavor(&bytes, NbtFlavor::BedrockNetwork, &ParseLimits::default()).unwrap();
+    let compound = root.compound_as_ref().unwrap();
+
+    assert_eq!(compound.values.get("i").unwrap().int().unwrap().value, 150);
+    assert_eq!(compound.values.get("l").unwrap().long().unwrap().value, -1);
+    assert_eq!(compound.values.get("s").unwrap().string().unwrap().value, "hi");
+    assert_eq!(compound.values.get("a").unwrap().int_array().unwrap().values, vec![1, -2]);
+    assert_eq!(compound.values.get("h").unwrap().short().unwrap().value, 0x1234);
+}
+
+#[test]
+fn test_parse_bytes_bedrock_network_varint_too_long() {
+    let bytes = [10, 0, 3, 1, b'i', 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01, 0];
+    let error = parse_bytes_with_flavor(&bytes, NbtFlavor::BedrockNetwork, &ParseLimits::default()).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::InvalidVarint));
+    assert_eq!(error.offset, 5);
+    assert_eq!(error.path(), "i");
+}
+
+#[test]
+fn test_parse_root_of_any_type() {
+    // List root "l" of two ints
+    let bytes = [9, 0, 1, b'l', 3, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2];
+    let root = parse_root(&bytes).unwrap();
+
+    assert_eq!(root.as_list().unwrap().values, vec![NbtTag::from(1), NbtTag::from(2)]);
+    assert_eq!(parse_root(&[10, 0, 1, b'r', 0]).unwrap().as_compound().unwrap().name, "r");
+    assert!(matches!(parse_root(&[]).unwrap_err().kind, NbtErrorKind::UnexpectedEof));
+    assert!(matches!(parse_root(&[0]).unwrap_err().kind, NbtErrorKind::InvalidRoot(NbtTagType::End)));
+}
+
+#[test]
+fn test_parse_all_roots() {
+    let bytes = [
+        10, 0, 1, b'a', 3, 0, 1, b'v', 0, 0, 0, 1, 0,
+        8, 0, 1, b's', 0, 2, b'h', b'i',
+        10, 0, 1, b'b', 0,
+    ];
+    let roots = parse_all(&bytes).unwrap();
+
+    assert_eq!(roots.len(), 3);
+    assert_eq!(roots[0].as_compound().unwrap().get_i32("v"), Some(1));
+    assert_eq!(roots[1].as_str(), Some("hi"));
+    assert_eq!(roots[2].as_compound().unwrap().name, "b");
+    assert!(parse_all(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_all_zero_padding() {
+    let roots = parse_all(&[10, 0, 1, b'a', 0, 0, 0, 0]).unwrap();
+    assert_eq!(roots.len(), 1);
+
+    // Padding without a root, or followed by data, is still an error
+    assert!(matches!(parse_all(&[0, 0]).unwrap_err().kind, NbtErrorKind::InvalidRoot(NbtTagType::End)));
+    assert!(matches!(parse_all(&[10, 0, 1, b'a', 0, 0, 1]).unwrap_err().kind, NbtErrorKind::InvalidRoot(NbtTagType::End)));
+}
+
+#[test]
+fn test_parse_all_truncated_root() {
+    let bytes = [10, 0, 1, b'a', 0, 10, 0, 1, b'b', 3, 0, 1, b'v', 0, 0];
+    let error = parse_all(&bytes).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::UnexpectedEof));
+    assert_eq!(error.path(), "v");
+}
+
+#[test]
+fn test_parse_all_nameless_roots() {
+    let bytes = [3, 0, 0, 0, 7, 10, 0];
+    let roots = parse_all_with_flavor(&bytes, NbtFlavor::JavaNetwork, &ParseLimits::default()).unwrap();
+
+    assert_eq!(roots, vec![NbtTag::from(7), NbtTag::Compound(NbtTagCompound::new(""))]);
 }
@@ -0,0 +1,69 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.1
+//
+// ## Changelog
+// - 1.0.0: Initial version, Java and Bedrock binary flavors [mrmarkolinus:2026-10-16]
+// - 1.0.1: Network flavors, nameless root and varints [mrmarkolinus:2026-10-16]
+
+//! The binary encodings of NBT used by the two Minecraft editions.
+//!
+//! Java Edition writes numbers big-endian and strings as Java Modified UTF-8. Bedrock
+//! Edition files (`level.dat`, `.mcstructure`, block state blobs) use the same tag layout
+//! but write numbers little-endian and strings as standard UTF-8.
+//!
+//! The network protocols add two variants. Since 1.20.2 the Java protocol omits the name of
+//! the root tag, which can then be of any type. Bedrock network NBT writes ints, longs and
+//! all lengths as varints, zigzag-encoded when signed.
+
+/// Byte order of the numbers and length prefixes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    #[default]
+    Big,
+    Little,
+}
+
+/// Binary NBT dialect, selects the byte order and the string encoding.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NbtFlavor {
+    /// Java Edition: big-endian, Modified UTF-8 strings.
+    #[default]
+    Java,
+    /// Bedrock Edition files: little-endian, UTF-8 strings.
+    Bedrock,
+    /// Java protocol since 1.20.2: like `Java`, but the root tag has no name.
+    JavaNetwork,
+    /// Bedrock protocol: like `Bedrock`, but ints and longs are zigzag varints, string
+    /// lengths unsigned varints and list and array lengths zigzag varints.
+    BedrockNetwork,
+}
+
+impl NbtFlavor {
+    pub fn endianness(self) -> Endianness {
+        match self {
+            NbtFlavor::Java | NbtFlavor::JavaNetwork => Endianness::Big,
+            NbtFlavor::Bedrock | NbtFlavor::BedrockNetwork => Endianness::Little,
+        }
+    }
+
+    /// Whether strings are Java Modified UTF-8 rather than standard UTF-8.
+    pub fn modified_utf8(self) -> bool {
+        matches!(self, NbtFlavor::Java | NbtFlavor::JavaNetwork)
+    }
+
+    /// Whether the root tag type is followed by a name. A nameless root can be any tag.
+    pub fn named_root(self) -> bool {
+        self != NbtFlavor::JavaNetwork
+    }
+
+    /// Whether ints, longs and lengths are varints.
+    pub fn varint(self) -> bool {
+        self == NbtFlavor::BedrockNetwork
+    }
+}
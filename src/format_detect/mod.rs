@@ -0,0 +1,190 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.0
+//
+// ## Changelog
+// - 1.0.0: Initial version, format detection from file content [mrmarkolinus:2026-10-16]
+
+//! Detects the format of a file from its content, whatever its extension.
+//!
+//! Backup tools rename files, `level.dat_old`, `.schem` and `.schematic` files are plain
+//! NBT, and uploads often have no extension at all. `detect` looks at the first bytes of a
+//! file, in this order:
+//!
+//! - a Bedrock `level.dat` header: storage version, then the length of the rest of the file
+//! - a region file: at least 8 KiB, every chunk location of the header within the file
+//! - gzip or zlib magic, followed once decompressed by an NBT root
+//! - a JSON object
+//! - an uncompressed NBT root
+//!
+//! NBT roots are recognized by a valid tag type followed by a name that fits in the data.
+//! For a compound root the name of its first entry is checked too, which tells big-endian
+//! Java NBT from little-endian Bedrock NBT, e.g. for `.mcstructure` files.
+
+use crate::generic_bin::{self, CompressionType, BEDROCK_HEADER_SIZE};
+use crate::flavor::NbtFlavor;
+use crate::nbt_tag::NbtTagType;
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+/// Size of the region header: 1024 chunk locations, then 1024 timestamps.
+const REGION_HEADER_SIZE: usize = 8192;
+
+/// Region files are divided in sectors of this many bytes.
+const REGION_SECTOR_SIZE: u64 = 4096;
+
+/// Number of decompressed bytes looked at to recognize a compressed NBT root.
+const NBT_PREFIX_SIZE: u64 = 1024;
+
+/// Format of a file, as found by `detect`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DetectedFormat {
+    /// Anvil (`.mca`) or McRegion (`.mcr`) region file.
+    Region,
+    /// Java binary NBT: `.dat`, `.nbt`, `.litematic`, `.schem`, `.schematic`, …
+    JavaNbt(CompressionType),
+    /// Bedrock little-endian NBT without header, e.g. `.mcstructure`.
+    BedrockNbt(CompressionType),
+    /// Bedrock `level.dat`, with its 8 byte header.
+    BedrockLevelDat,
+    /// JSON export of a compound, in any of the `json` schemas.
+    Json,
+    Unknown,
+}
+
+impl DetectedFormat {
+    /// The names used by the Python API: `region`, `java_nbt`, `bedrock_nbt`,
+    /// `bedrock_level_dat`, `json` and `unknown`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DetectedFormat::Region => "region",
+            DetectedFormat::JavaNbt(_) => "java_nbt",
+            DetectedFormat::BedrockNbt(_) => "bedrock_nbt",
+            DetectedFormat::BedrockLevelDat => "bedrock_level_dat",
+            DetectedFormat::Json => "json",
+            DetectedFormat::Unknown => "unknown",
+        }
+    }
+}
+
+/// Detects the format of the file at `path`, reading only its first bytes.
+pub fn detect_file<P: AsRef<Path>>(path: P) -> io::Result<DetectedFormat> {
+    let file = fs::File::open(path)?;
+    let file_len = file.metadata()?.len();
+
+    let mut prefix = Vec::with_capacity(REGION_HEADER_SIZE);
+    file.take(REGION_HEADER_SIZE as u64).read_to_end(&mut prefix)?;
+
+    Ok(detect(&prefix, file_len))
+}
+
+/// Detects the format from the first bytes of a file, at least 8 KiB unless the file is
+/// shorter, and the length of the whole file.
+pub fn detect(prefix: &[u8], file_len: u64) -> DetectedFormat {
+    if is_bedrock_level_dat(prefix, file_len) {
+        return DetectedFormat::BedrockLevelDat;
+    }
+    if is_region(prefix, file_len) {
+        return DetectedFormat::Region;
+    }
+
+    match CompressionType::detect(prefix) {
+        CompressionType::Uncompressed => {}
+        compression => return compressed_nbt(prefix, compression),
+    }
+
+    if is_json(prefix) {
+        return DetectedFormat::Json;
+    }
+    match nbt_flavor(prefix) {
+        Some(NbtFlavor::Bedrock) => DetectedFormat::BedrockNbt(CompressionType::Uncompressed),
+        Some(_) => DetectedFormat::JavaNbt(CompressionType::Uncompressed),
+        None => DetectedFormat::Unknown,
+    }
+}
+
+/// The header of `generic_bin::detect_bedrock_header`, checked against the file length.
+fn is_bedrock_level_dat(prefix: &[u8], file_len: u64) -> bool {
+    let [_, _, _, _, l0, l1, l2, l3, first, ..] = *prefix else {
+        return false;
+    };
+    u64::from(u32::from_le_bytes([l0, l1, l2, l3])) + BEDROCK_HEADER_SIZE as u64 == file_len && first == 0x0A
+}
+
+/// Every chunk location in the header is empty or points to whole sectors inside the file,
+/// after the two sectors of the header.
+fn is_region(prefix: &[u8], file_len: u64) -> bool {
+    if prefix.len() < REGION_HEADER_SIZE {
+        return false;
+    }
+    let sectors = file_len.div_ceil(REGION_SECTOR_SIZE);
+
+    prefix[..REGION_HEADER_SIZE / 2].chunks_exact(4).all(|location| {
+        let offset = u64::from(u32::from_be_bytes([0, location[0], location[1], location[2]]));
+        let count = u64::from(location[3]);
+        (offset == 0 && count == 0) || (offset >= 2 && count > 0 && offset + count <= sectors)
+    })
+}
+
+/// Decompresses the start of the data and looks for an NBT root.
+fn compressed_nbt(prefix: &[u8], compression: CompressionType) -> DetectedFormat {
+    let mut decompressed = Vec::new();
+    if let Ok(reader) = generic_bin::decompress_reader(prefix) {
+        // The prefix ends in the middle of the stream, the bytes read before the error are kept
+        let _ = reader.take(NBT_PREFIX_SIZE).read_to_end(&mut decompressed);
+    }
+
+    match nbt_flavor(&decompressed) {
+        Some(NbtFlavor::Bedrock) => DetectedFormat::BedrockNbt(compression),
+        Some(_) => DetectedFormat::JavaNbt(compression),
+        None => DetectedFormat::Unknown,
+    }
+}
+
+/// A JSON object, after an optional byte order mark and whitespace.
+fn is_json(prefix: &[u8]) -> bool {
+    let text = prefix.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(prefix);
+    text.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{')
+}
+
+/// Tells whether the data starts with a named NBT root, and in which byte order. Java is
+/// preferred when both fit, e.g. for an empty compound with an empty name.
+fn nbt_flavor(data: &[u8]) -> Option<NbtFlavor> {
+    [NbtFlavor::Java, NbtFlavor::Bedrock].into_iter().find(|flavor| {
+        let big_endian = *flavor == NbtFlavor::Java;
+        match data.first().and_then(|id| NbtTagType::from_id(*id)) {
+            None | Some(NbtTagType::End) => false,
+            Some(NbtTagType::Compound) => {
+                named_tag_end(data, 0, big_endian).is_some_and(|end| match data.get(end) {
+                    // The first entry of the compound, or its end
+                    Some(0) => true,
+                    Some(id) => NbtTagType::from_id(*id).is_some() && named_tag_end(data, end, big_endian).is_some(),
+                    None => false,
+                })
+            }
+            Some(_) => named_tag_end(data, 0, big_endian).is_some(),
+        }
+    })
+}
+
+/// The position after the type and name of the tag at `start`, if the name fits in the data
+/// and has no control characters. Read in the wrong byte order, a length runs over binary
+/// data or past the end.
+fn named_tag_end(data: &[u8], start: usize, big_endian: bool) -> Option<usize> {
+    let length = data.get(start + 1..start + 3)?;
+    let length = if big_endian { u16::from_be_bytes([length[0], length[1]]) } else { u16::from_le_bytes([length[0], length[1]]) };
+
+    let end = start + 3 + length as usize;
+    let name = data.get(start + 3..end)?;
+    name.iter().all(|b| *b >= 0x20 && *b != 0x7F).then_some(end)
+}
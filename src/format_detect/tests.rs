@@ -0,0 +1,69 @@
+#[cfg(test)]
+
+use super::*;
+
+/// `{"": {hello: 7}}` as Java NBT: a compound named "" holding an int named "hello".
+const JAVA_NBT: [u8; 15] = [0x0A, 0x00, 0x00, 0x03, 0x00, 0x05, b'h', b'e', b'l', b'l', b'o', 0x00, 0x00, 0x00, 0x07];
+
+/// The start of a `.mcstructure`: a compound named "" holding an int named "format_version".
+const BEDROCK_NBT: [u8; 25] = [
+    0x0A, 0x00, 0x00, 0x03, 0x0E, 0x00, b'f', b'o', b'r', b'm', b'a', b't', b'_', b'v', b'e', b'r', b's', b'i', b'o', b'n',
+    0x01, 0x00, 0x00, 0x00, 0x00,
+];
+
+fn detect_bytes(data: &[u8]) -> DetectedFormat {
+    detect(data, data.len() as u64)
+}
+
+fn compressed(data: &[u8], compression: CompressionType) -> Vec<u8> {
+    let mut out = Vec::new();
+    generic_bin::compress_into(&mut out, data, compression, generic_bin::DEFAULT_COMPRESSION_LEVEL).unwrap();
+    out
+}
+
+#[test]
+fn test_detect_uncompressed_nbt() {
+    assert_eq!(detect_bytes(&JAVA_NBT), DetectedFormat::JavaNbt(CompressionType::Uncompressed));
+    assert_eq!(detect_bytes(&BEDROCK_NBT), DetectedFormat::BedrockNbt(CompressionType::Uncompressed));
+    assert_eq!(detect_bytes(&[0x0A, 0x00, 0x00, 0x00]), DetectedFormat::JavaNbt(CompressionType::Uncompressed));
+}
+
+#[test]
+fn test_detect_compressed_nbt() {
+    assert_eq!(detect_bytes(&compressed(&JAVA_NBT, CompressionType::Gzip)), DetectedFormat::JavaNbt(CompressionType::Gzip));
+    assert_eq!(detect_bytes(&compressed(&BEDROCK_NBT, CompressionType::Zlib)), DetectedFormat::BedrockNbt(CompressionType::Zlib));
+    assert_eq!(detect_file("tests/resources/bigtest.nbt").unwrap(), DetectedFormat::JavaNbt(CompressionType::Gzip));
+}
+
+#[test]
+fn test_detect_bedrock_level_dat() {
+    let mut data = vec![0x0A, 0x00, 0x00, 0x00];
+    data.extend((BEDROCK_NBT.len() as u32).to_le_bytes());
+    data.extend(BEDROCK_NBT);
+
+    assert_eq!(detect_bytes(&data), DetectedFormat::BedrockLevelDat);
+    // A truncated file is not taken for a level.dat
+    assert_ne!(detect(&data, data.len() as u64 + 1), DetectedFormat::BedrockLevelDat);
+}
+
+#[test]
+fn test_detect_region() {
+    let mut data = vec![0u8; REGION_HEADER_SIZE + REGION_SECTOR_SIZE as usize];
+    data[0..4].copy_from_slice(&[0x00, 0x00, 0x02, 0x01]);
+    assert_eq!(detect_bytes(&data), DetectedFormat::Region);
+
+    // A chunk past the end of the file
+    data[0..4].copy_from_slice(&[0x00, 0x00, 0x02, 0x02]);
+    assert_ne!(detect_bytes(&data), DetectedFormat::Region);
+
+    assert_eq!(detect_file("tests/resources/test_world/r.-1.0.mca").unwrap(), DetectedFormat::Region);
+}
+
+#[test]
+fn test_detect_json_and_unknown() {
+    assert_eq!(detect_bytes(b"\xEF\xBB\xBF\n  {\"Data\": {}}"), DetectedFormat::Json);
+    assert_eq!(detect_bytes(b"hello"), DetectedFormat::Unknown);
+    assert_eq!(detect_bytes(&[]), DetectedFormat::Unknown);
+    assert_eq!(detect_bytes(&compressed(b"not nbt", CompressionType::Gzip)), DetectedFormat::Unknown);
+    assert_eq!(DetectedFormat::BedrockNbt(CompressionType::Gzip).name(), "bedrock_nbt");
+}
@@ -5,24 +5,40 @@
 // - 2023-12-17
 //
 // ## File Version
-// - 1.0.0
+// - 1.0.9
 //
 // ## Changelog
 // - 1.0.0: Initial version
+// - 1.0.1: Propagate NbtError instead of a generic parse error [mrmarkolinus:2026-10-16]
+// - 1.0.2: Configurable ParseLimits [mrmarkolinus:2026-10-16]
+// - 1.0.3: Streaming decompression, to_tag no longer keeps a decompressed copy [mrmarkolinus:2026-10-16]
+// - 1.0.4: Bedrock flavor and level.dat header detection [mrmarkolinus:2026-10-16]
+// - 1.0.5: Atomic compressed writes, GenericBinFile::write keeps the original format [mrmarkolinus:2026-10-16]
+// - 1.0.6: to_tag_compound moves the root instead of copying it [mrmarkolinus:2026-10-16]
+// - 1.0.7: to_compounds_list returns every root of the file [mrmarkolinus:2026-10-16]
+// - 1.0.8: to_compounds_list reads every gzip member and ignores trailing zero padding [mrmarkolinus:2026-10-16]
+// - 1.0.9: Temporary files of atomic writes are unique per call [mrmarkolinus:2026-10-16]
 
 use crate::file_parser;
+use crate::file_parser::ParseLimits;
+use crate::flavor::NbtFlavor;
 use crate::nbt_tag::{NbtTag, NbtTagCompound};
+use crate::nbt_writer::NbtWriter;
+use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use flate2::read::ZlibDecoder;
 use flate2::read::GzDecoder;
-use std::io::Read;
+use flate2::write::{GzEncoder, ZlibEncoder};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 
 pub enum FileType {
     Nbt,
     Region,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CompressionType {
     Uncompressed = 0,
     Gzip = 1,
@@ -30,6 +46,16 @@ pub enum CompressionType {
 }
 
 impl CompressionType {
+    /// Guesses the compression from the first bytes of the data: the gzip magic number,
+    /// a valid zlib header, or otherwise uncompressed.
+    pub fn detect(header: &[u8]) -> Self {
+        match header {
+            [0x1F, 0x8B, ..] => CompressionType::Gzip,
+            [cmf, flg, ..] if cmf & 0x0F == 8 && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0 => CompressionType::Zlib,
+            _ => CompressionType::Uncompressed,
+        }
+    }
+
     fn from_u8(value: u8) -> Option<Self> {
         match value {
             0 => Some(CompressionType::Uncompressed),
@@ -48,32 +74,127 @@ impl CompressionType {
     }
 }
 
+/// Compression level used when none is given, the zlib default.
+pub const DEFAULT_COMPRESSION_LEVEL: u32 = 6;
+
+/// Size of the header in front of the NBT data of a Bedrock `level.dat`.
+pub const BEDROCK_HEADER_SIZE: usize = 8;
+
+/// Recognizes the header of a Bedrock `level.dat`: the storage version and the length of
+/// the rest of the file, both little-endian `i32`, followed by an uncompressed root
+/// compound. Returns the storage version.
+pub fn detect_bedrock_header(data: &[u8]) -> Option<i32> {
+    let (header, nbt) = data.split_at_checked(BEDROCK_HEADER_SIZE)?;
+    let version = i32::from_le_bytes(header[0..4].try_into().unwrap());
+    let length = u32::from_le_bytes(header[4..8].try_into().unwrap());
+
+    (length as usize == nbt.len() && nbt.first() == Some(&0x0A)).then_some(version)
+}
+
 pub struct GenericBinFile {
-    raw_data: Vec<u8>
+    raw_data: Vec<u8>,
+    limits: ParseLimits,
+    flavor: NbtFlavor,
+    bedrock_version: Option<i32>,
 }
 
 impl GenericBinFile {
+    /// Reads the file. A Bedrock `level.dat` header is detected and selects the Bedrock
+    /// flavor, other files are parsed as Java NBT unless `with_flavor` says otherwise.
     pub fn new(file_path: PathBuf, file_type: FileType) -> io::Result<Self> {
         let bin_file = file_parser::FileParser::new(file_path, file_parser::ReadMode::EntireFile, file_type).read()?;
-        Ok(GenericBinFile { raw_data: bin_file})
+        Ok(Self::from_raw_data(bin_file))
+    }
+
+    pub fn from_raw_data(raw_data: Vec<u8>) -> Self {
+        let bedrock_version = detect_bedrock_header(&raw_data);
+        let flavor = if bedrock_version.is_some() { NbtFlavor::Bedrock } else { NbtFlavor::Java };
+
+        GenericBinFile { raw_data, limits: ParseLimits::default(), flavor, bedrock_version }
+    }
+
+    /// Replaces the flavor used to parse the content, e.g. `Bedrock` for `.mcstructure` files.
+    pub fn with_flavor(mut self, flavor: NbtFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    pub fn get_flavor(&self) -> NbtFlavor {
+        self.flavor
+    }
+
+    /// Storage version from the Bedrock `level.dat` header, if the file has one.
+    pub fn get_bedrock_version(&self) -> Option<i32> {
+        self.bedrock_version
+    }
+
+    /// Replaces the default limits used when the content is parsed.
+    pub fn with_limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn get_limits(&self) -> &ParseLimits {
+        &self.limits
     }
 
     pub fn get_raw_data(&self) -> &Vec<u8> {
         &self.raw_data
     }
 
+    /// Compression of the file content, kept by `write`.
+    pub fn get_compression(&self) -> CompressionType {
+        match self.bedrock_version {
+            Some(_) => CompressionType::Uncompressed,
+            None => CompressionType::detect(&self.raw_data),
+        }
+    }
+
+    /// Writes `compound` to `path` in the format of this file: same compression, flavor
+    /// and, for a Bedrock `level.dat`, header. The file is replaced atomically.
+    pub fn write<P: AsRef<Path>>(&self, path: P, compound: &NbtTagCompound) -> io::Result<()> {
+        self.write_with_compression(path, compound, self.get_compression(), DEFAULT_COMPRESSION_LEVEL)
+    }
+
+    /// Same as `write`, with another compression. `level` goes from 0 (fastest) to 9 (smallest).
+    pub fn write_with_compression<P: AsRef<Path>>(&self, path: P, compound: &NbtTagCompound, compression: CompressionType, level: u32) -> io::Result<()> {
+        let mut data = Vec::new();
+        if let Some(version) = self.bedrock_version {
+            // The length is patched once the NBT data is written
+            data.extend_from_slice(&version.to_le_bytes());
+            data.extend_from_slice(&[0; 4]);
+        }
+
+        let mut writer = NbtWriter::new(data).with_flavor(self.flavor);
+        writer.write_compound(compound)?;
+        let mut data = writer.into_inner();
+
+        if self.bedrock_version.is_some() {
+            let length = u32::try_from(data.len() - BEDROCK_HEADER_SIZE)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "level.dat too large"))?;
+            data[4..8].copy_from_slice(&length.to_le_bytes());
+        }
+
+        write_atomic(path, |writer| compress_into(writer, &data, compression, level))
+    }
+
     pub fn to_tag(&self) -> std::io::Result<NbtTag> {
-        let uncompressed_data = self.try_decode_data()?;
-        let root = match file_parser::parse_bytes(&uncompressed_data) {
-            Ok(nbt_tag) => nbt_tag,  // On success, return the NbtTag
-            Err(_) => return Err(std::io::Error::new(std::io::ErrorKind::Other, "Invalid NBT file")),
-        };
+        // Decompressed on the fly, the uncompressed data is never held in memory
+        let root = file_parser::parse_compressed_reader_with_flavor(self.payload(), self.flavor, &self.limits)?;
 
         Ok(root)
     }
 
+    /// The NBT data of the file, after the Bedrock `level.dat` header if there is one.
+    fn payload(&self) -> &[u8] {
+        match self.bedrock_version {
+            Some(_) => &self.raw_data[BEDROCK_HEADER_SIZE..],
+            None => self.raw_data.as_slice(),
+        }
+    }
+
     pub fn to_tag_compound(&self) -> std::io::Result<NbtTagCompound> {
-        let compound = match self.to_tag()?.compound() {
+        let compound = match self.to_tag()?.into_compound() {
             Some(nbt_tag) => nbt_tag,  // On success, return the NbtTag
             None => return Err(std::io::Error::new(std::io::ErrorKind::Other, "Invalid Compound tag")),
         };
@@ -81,11 +202,23 @@ impl GenericBinFile {
         Ok(compound)
     }
 
+    /// Returns every root of the file, which can hold several roots back to back, also as
+    /// gzip members appended one after the other. A root that is not a compound is wrapped
+    /// in an unnamed compound, under the name of the root.
     pub fn to_compounds_list(&self) -> std::io::Result<Vec<NbtTagCompound>> {
-        let mut compunds_list = Vec::new();
-        compunds_list.push(self.to_tag_compound()?);
+        let decompressed = decompress_multi_reader(self.payload())?;
+        let roots = file_parser::parse_all_named(BufReader::new(decompressed), self.flavor, &self.limits)?;
+
+        let compounds_list = roots.into_iter().map(|(name, root)| match root {
+            NbtTag::Compound(compound) => compound,
+            root => {
+                let mut compound = NbtTagCompound::new("");
+                compound.values.insert(name, root);
+                compound
+            }
+        }).collect();
 
-        Ok(compunds_list)
+        Ok(compounds_list)
     }
 
 
@@ -105,30 +238,105 @@ impl GenericBinFile {
     }
 
     pub fn decode_binary_data(&self, chunk_payload: &[u8], chunk_compression_method: &[u8]) -> io::Result<Vec<u8>> {
+        let mut decoder = self.decoder(chunk_payload, chunk_compression_method)?;
+        let mut chunk_decompressed_payload = Vec::new();
+        decoder.read_to_end(&mut chunk_decompressed_payload)?;
+        Ok(chunk_decompressed_payload)
+    }
+
+    /// Returns a reader that decompresses the payload on the fly.
+    pub fn decoder<'a>(&self, chunk_payload: &'a [u8], chunk_compression_method: &[u8]) -> io::Result<Box<dyn Read + 'a>> {
         // Decompress chunk data
         // acoording to minecraft wiki case Gzip and not compressed are not used in practice
         // but they are officially supported
-        match CompressionType::from_u8(chunk_compression_method[0]) {
-            Some(CompressionType::Gzip) => {
-                // Gzip compression
-                let mut decoder = GzDecoder::new(chunk_payload);
-                let mut chunk_decompressed_payload = Vec::new();
-                decoder.read_to_end(&mut chunk_decompressed_payload)?;
-                Ok(chunk_decompressed_payload)
-            },
-            Some(CompressionType::Zlib) => { 
-                // Zlib compression
-                let mut decoder = ZlibDecoder::new(chunk_payload);
-                let mut chunk_decompressed_payload = Vec::new();
-                decoder.read_to_end(&mut chunk_decompressed_payload)?;
-                Ok(chunk_decompressed_payload)
-            },
-            Some(CompressionType::Uncompressed) => {
-                // Data is uncompressed
-                let chunk_decompressed_payload = chunk_payload.to_vec();
-                Ok(chunk_decompressed_payload)
-            },
+        match chunk_compression_method.first().and_then(|method| CompressionType::from_u8(*method)) {
+            Some(CompressionType::Gzip) => Ok(Box::new(GzDecoder::new(chunk_payload))),
+            Some(CompressionType::Zlib) => Ok(Box::new(ZlibDecoder::new(chunk_payload))),
+            Some(CompressionType::Uncompressed) => Ok(Box::new(chunk_payload)),
             _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "Unknown compression format"))
         }
     }
 }
+
+/// Wraps a reader so that gzip or zlib compressed data, detected from its first bytes,
+/// is decompressed on the fly. Uncompressed data is passed through unchanged.
+pub fn decompress_reader<'a, R: BufRead + 'a>(reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    decompress(reader, false)
+}
+
+/// Same as `decompress_reader`, but gzip data can hold several members back to back, which
+/// are decompressed as one stream. A single-member reader stops after the first member.
+pub fn decompress_multi_reader<'a, R: BufRead + 'a>(reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    decompress(reader, true)
+}
+
+fn decompress<'a, R: BufRead + 'a>(mut reader: R, multi_member: bool) -> io::Result<Box<dyn Read + 'a>> {
+    // A single fill_buf may return less than the magic bytes on slow readers, so the header
+    // is read explicitly and put back in front of the rest of the stream
+    let mut header = Vec::with_capacity(2);
+    (&mut reader).take(2).read_to_end(&mut header)?;
+    let compression = CompressionType::detect(&header);
+    let reader = io::Cursor::new(header).chain(reader);
+
+    Ok(match compression {
+        CompressionType::Gzip if multi_member => Box::new(flate2::bufread::MultiGzDecoder::new(reader)),
+        CompressionType::Gzip => Box::new(flate2::bufread::GzDecoder::new(reader)),
+        CompressionType::Zlib => Box::new(flate2::bufread::ZlibDecoder::new(reader)),
+        CompressionType::Uncompressed => Box::new(reader),
+    })
+}
+
+/// Writes `data` to `writer`, compressed with the given method and level (0 to 9).
+pub fn compress_into<W: Write>(writer: W, data: &[u8], compression: CompressionType, level: u32) -> io::Result<()> {
+    let level = flate2::Compression::new(level.min(9));
+    match compression {
+        CompressionType::Gzip => {
+            let mut encoder = GzEncoder::new(writer, level);
+            encoder.write_all(data)?;
+            encoder.finish()?.flush()
+        }
+        CompressionType::Zlib => {
+            let mut encoder = ZlibEncoder::new(writer, level);
+            encoder.write_all(data)?;
+            encoder.finish()?.flush()
+        }
+        CompressionType::Uncompressed => {
+            let mut writer = writer;
+            writer.write_all(data)?;
+            writer.flush()
+        }
+    }
+}
+
+/// Numbers the temporary files of `write_atomic` within the process.
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Replaces the file at `path` with the content produced by `write`, atomically: the content
+/// goes to a temporary file in the same directory, which is renamed over `path` only once
+/// it is complete. On failure `path` is left untouched.
+pub fn write_atomic<P, F>(path: P, write: F) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&mut BufWriter<fs::File>) -> io::Result<()>,
+{
+    let path = path.as_ref();
+    let file_name = path.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Path without file name"))?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    // Unique per call, so threads saving the same path never share a temporary file
+    let call = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    temp_name.push(format!(".{}.{}.tmp", std::process::id(), call));
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = fs::File::create(&temp_path).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        writer.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        fs::rename(&temp_path, path)
+    });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
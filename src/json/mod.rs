@@ -0,0 +1,642 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.3
+//
+// ## Changelog
+// - 1.0.0: Initial version, typed and plain JSON schemas [mrmarkolinus:2026-10-16]
+// - 1.0.1: Serde shape written by hand, tags take their names from their keys [mrmarkolinus:2026-10-16]
+// - 1.0.2: Plain import skips nulls in lists, typed detection requires a compound root [mrmarkolinus:2026-10-16]
+// - 1.0.3: Serde impls of tags wrapped in a newtype, binary formats get network NBT [mrmarkolinus:2026-10-16]
+
+//! JSON export and import of compounds in three schemas.
+//!
+//! `Serde` is the shape of the `Serialize` impl of `NbtTagCompound` in human readable formats,
+//! kept for existing files:
+//! `{"name": "Level", "values": {"intTest": {"Int": {"name": "intTest", "value": 5}}}}`.
+//! Tags do not store their names, the `name` of a tag in this shape is its key in the
+//! parent compound, and empty for list elements. Importing ignores the names of tags.
+//!
+//! `Typed` is lossless and more compact. Every tag is an object with its `type` and its
+//! `value`; the root adds its `name`:
+//!
+//! ```text
+//! {"name": "Level", "type": "compound", "value": {
+//!     "intTest": {"type": "int", "value": 5},
+//!     "pos": {"type": "list", "value": {"element_type": "double", "values": [0.5, 64.0]}},
+//!     "data": {"type": "long_array", "value": [1, -1]}
+//! }}
+//! ```
+//!
+//! The types are `byte`, `short`, `int`, `long`, `float`, `double`, `string`, `byte_array`,
+//! `int_array`, `long_array`, `list` and `compound` (`end` only as the element type of an
+//! empty list). List elements are bare values of the element type. Non-finite floats are
+//! the strings `"NaN"`, `"Infinity"` and `"-Infinity"`.
+//!
+//! `Plain` is natural JSON for analytics, in the style of the data reports: compounds are
+//! objects, lists and arrays are arrays, numbers are numbers and non-finite floats `null`.
+//! It drops the root name and the exact numeric types; importing infers them back (`int`
+//! or `long` for integers, `double` for decimals, `byte` for booleans) and skips `null`s,
+//! in compounds and in lists alike, so non-finite floats are lost.
+
+use crate::error::{NbtError, NbtErrorKind};
+use crate::nbt_tag::*;
+use crate::serde_nbt::{self, TAG_TOKEN};
+
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{MapAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// JSON schema used to export and import compounds, see the module documentation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JsonFormat {
+    #[default]
+    Serde,
+    Typed,
+    Plain,
+}
+
+impl JsonFormat {
+    /// Parses the format names used by the Python API: `serde`, `typed` and `plain`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "serde" => Some(JsonFormat::Serde),
+            "typed" => Some(JsonFormat::Typed),
+            "plain" => Some(JsonFormat::Plain),
+            _ => None,
+        }
+    }
+
+    /// Guesses the schema of a document: a root whose `type` is `"compound"` with a `value`
+    /// object is typed, one with `name` and a `values` object is the serde shape, anything
+    /// else plain.
+    pub fn detect(value: &Value) -> Self {
+        match value {
+            Value::Object(map) if map.get("type").and_then(Value::as_str) == Some("compound") && map.get("value").is_some_and(Value::is_object) => JsonFormat::Typed,
+            Value::Object(map) if map.contains_key("name") && map.get("values").is_some_and(Value::is_object) => JsonFormat::Serde,
+            _ => JsonFormat::Plain,
+        }
+    }
+}
+
+/// Writes the compound as pretty-printed JSON in the given schema.
+pub fn to_writer<W: std::io::Write>(writer: W, compound: &NbtTagCompound, format: JsonFormat) -> serde_json::Result<()> {
+    match format {
+        JsonFormat::Serde => serde_json::to_writer_pretty(writer, compound),
+        JsonFormat::Typed => serde_json::to_writer_pretty(writer, &TypedRoot(compound)),
+        JsonFormat::Plain => serde_json::to_writer_pretty(writer, &PlainCompound(compound)),
+    }
+}
+
+pub fn to_string(compound: &NbtTagCompound, format: JsonFormat) -> String {
+    let mut buf = Vec::new();
+    // Writing to a Vec only fails on map keys that are not strings, which never happens here
+    to_writer(&mut buf, compound, format).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+/// Builds a compound from a JSON document in the given schema.
+/// Errors carry the path of the offending tag; their offset is always 0.
+pub fn from_value(value: Value, format: JsonFormat) -> Result<NbtTagCompound, NbtError> {
+    match format {
+        JsonFormat::Serde => serde_root(&value),
+        JsonFormat::Typed => typed_root(&value),
+        JsonFormat::Plain => match &value {
+            Value::Object(map) => plain_compound(map),
+            _ => Err(json_error("the root must be an object".to_string())),
+        },
+    }
+}
+
+pub fn from_str(text: &str, format: JsonFormat) -> Result<NbtTagCompound, NbtError> {
+    let value = serde_json::from_str(text).map_err(|e| json_error(e.to_string()))?;
+    from_value(value, format)
+}
+
+fn json_error(msg: String) -> NbtError {
+    NbtError::new(NbtErrorKind::InvalidJson(msg), 0)
+}
+
+fn type_name(ty: NbtTagType) -> &'static str {
+    match ty {
+        NbtTagType::End => "end",
+        NbtTagType::Byte => "byte",
+        NbtTagType::Short => "short",
+        NbtTagType::Int => "int",
+        NbtTagType::Long => "long",
+        NbtTagType::Float => "float",
+        NbtTagType::Double => "double",
+        NbtTagType::ByteArray => "byte_array",
+        NbtTagType::String => "string",
+        NbtTagType::List => "list",
+        NbtTagType::Compound => "compound",
+        NbtTagType::IntArray => "int_array",
+        NbtTagType::LongArray => "long_array",
+    }
+}
+
+fn type_from_name(name: &str) -> Option<NbtTagType> {
+    (0..=12).filter_map(NbtTagType::from_id).find(|ty| type_name(*ty) == name)
+}
+
+/// Non-finite floats have no JSON number, the typed schema writes them as strings.
+fn serialize_float<S: Serializer>(serializer: S, value: f64, single: bool) -> Result<S::Ok, S::Error> {
+    match value {
+        v if v.is_nan() => serializer.serialize_str("NaN"),
+        v if v == f64::INFINITY => serializer.serialize_str("Infinity"),
+        v if v == f64::NEG_INFINITY => serializer.serialize_str("-Infinity"),
+        v if single => serializer.serialize_f32(v as f32),
+        v => serializer.serialize_f64(v),
+    }
+}
+
+struct TypedRoot<'a>(&'a NbtTagCompound);
+
+impl Serialize for TypedRoot<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("name", &self.0.name)?;
+        map.serialize_entry("type", "compound")?;
+        map.serialize_entry("value", &TypedCompound(self.0))?;
+        map.end()
+    }
+}
+
+pub(crate) struct TypedCompound<'a>(pub(crate) &'a NbtTagCompound);
+
+impl Serialize for TypedCompound<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.values.len()))?;
+        for (key, value) in &self.0.values {
+            map.serialize_entry(key, &TypedTag(value))?;
+        }
+        map.end()
+    }
+}
+
+/// A tag with its type: `{"type": ..., "value": ...}`.
+pub(crate) struct TypedTag<'a>(pub(crate) &'a NbtTag);
+
+impl Serialize for TypedTag<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("type", type_name(self.0.ty()))?;
+        map.serialize_entry("value", &TypedPayload(self.0))?;
+        map.end()
+    }
+}
+
+/// The value of a tag, without its type.
+struct TypedPayload<'a>(&'a NbtTag);
+
+impl Serialize for TypedPayload<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            NbtTag::End => serializer.serialize_unit(),
+            NbtTag::Byte(val) => serializer.serialize_i8(val.value),
+            NbtTag::Short(val) => serializer.serialize_i16(val.value),
+            NbtTag::Int(val) => serializer.serialize_i32(val.value),
+            NbtTag::Long(val) => serializer.serialize_i64(val.value),
+            NbtTag::Float(val) => serialize_float(serializer, val.value as f64, true),
+            NbtTag::Double(val) => serialize_float(serializer, val.value, false),
+            NbtTag::ByteArray(val) => val.values.serialize(serializer),
+            NbtTag::String(val) => serializer.serialize_str(&val.value),
+            NbtTag::List(val) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("element_type", type_name(val.ty))?;
+                map.serialize_entry("values", &TypedListValues(&val.values))?;
+                map.end()
+            }
+            NbtTag::Compound(val) => TypedCompound(val).serialize(serializer),
+            NbtTag::IntArray(val) => val.values.serialize(serializer),
+            NbtTag::LongArray(val) => val.values.serialize(serializer),
+        }
+    }
+}
+
+struct TypedListValues<'a>(&'a [NbtTag]);
+
+impl Serialize for TypedListValues<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for value in self.0 {
+            seq.serialize_element(&TypedPayload(value))?;
+        }
+        seq.end()
+    }
+}
+
+struct PlainCompound<'a>(&'a NbtTagCompound);
+
+impl Serialize for PlainCompound<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.values.len()))?;
+        for (key, value) in &self.0.values {
+            map.serialize_entry(key, &PlainTag(value))?;
+        }
+        map.end()
+    }
+}
+
+struct PlainTag<'a>(&'a NbtTag);
+
+impl Serialize for PlainTag<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            NbtTag::Float(val) if !val.value.is_finite() => serializer.serialize_unit(),
+            NbtTag::Double(val) if !val.value.is_finite() => serializer.serialize_unit(),
+            NbtTag::List(val) => {
+                let mut seq = serializer.serialize_seq(Some(val.values.len()))?;
+                for value in &val.values {
+                    seq.serialize_element(&PlainTag(value))?;
+                }
+                seq.end()
+            }
+            NbtTag::Compound(val) => PlainCompound(val).serialize(serializer),
+            _ => TypedPayload(self.0).serialize(serializer),
+        }
+    }
+}
+
+impl Serialize for NbtTagCompound {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(TAG_TOKEN, &SerdePayload::Compound(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for NbtTagCompound {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.deserialize_newtype_struct(TAG_TOKEN, SerdeVisitor { root: true })? {
+            NbtTag::Compound(compound) => Ok(compound),
+            other => Err(serde::de::Error::custom(format!("expected a compound, found a {:?} tag", other.ty()))),
+        }
+    }
+}
+
+/// A tag alone has no key, it is written with an empty name.
+impl Serialize for NbtTag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(TAG_TOKEN, &SerdePayload::Tag(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for NbtTag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_newtype_struct(TAG_TOKEN, SerdeVisitor { root: false })
+    }
+}
+
+/// What the `Serialize` impls put in their newtype: the serde shape for human readable
+/// formats, the tag in network NBT for the others, such as `serde_nbt`.
+enum SerdePayload<'a> {
+    Tag(&'a NbtTag),
+    Compound(&'a NbtTagCompound),
+}
+
+impl Serialize for SerdePayload<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match (self, serializer.is_human_readable()) {
+            (SerdePayload::Tag(tag), true) => SerdeTag("", tag).serialize(serializer),
+            (SerdePayload::Compound(compound), true) => SerdeCompound(&compound.name, compound).serialize(serializer),
+            (SerdePayload::Tag(tag), false) => serializer.serialize_bytes(&serde_nbt::tag_to_bytes(tag).map_err(serde::ser::Error::custom)?),
+            (SerdePayload::Compound(compound), false) => {
+                serializer.serialize_bytes(&serde_nbt::compound_to_bytes(compound).map_err(serde::ser::Error::custom)?)
+            }
+        }
+    }
+}
+
+/// Reads either form of `SerdePayload`. `root` selects the serde shape of a compound.
+struct SerdeVisitor {
+    root: bool,
+}
+
+impl SerdeVisitor {
+    fn read_value<E: serde::de::Error>(&self, value: &Value) -> Result<NbtTag, E> {
+        let tag = match self.root {
+            true => serde_root(value).map(NbtTag::Compound),
+            false => serde_tag(value),
+        };
+        tag.map_err(E::custom)
+    }
+}
+
+impl<'de> Visitor<'de> for SerdeVisitor {
+    type Value = NbtTag;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an NBT tag")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        if deserializer.is_human_readable() {
+            self.read_value(&Value::deserialize(deserializer)?)
+        } else {
+            deserializer.deserialize_byte_buf(self)
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.read_value(&Value::deserialize(MapAccessDeserializer::new(map))?)
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        serde_nbt::tag_from_bytes(v).map_err(E::custom)
+    }
+}
+
+/// Names of the `NbtTag` variants, by tag id.
+const VARIANTS: [&str; 13] = [
+    "End", "Byte", "Short", "Int", "Long", "Float", "Double", "ByteArray", "String", "List", "Compound", "IntArray", "LongArray",
+];
+
+/// A compound in the serde shape, under the given name: `{"name": ..., "values": {...}}`.
+struct SerdeCompound<'a>(&'a str, &'a NbtTagCompound);
+
+impl Serialize for SerdeCompound<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut fields = serializer.serialize_struct("NbtTagCompound", 2)?;
+        fields.serialize_field("name", self.0)?;
+        fields.serialize_field("values", &SerdeValues(self.1))?;
+        fields.end()
+    }
+}
+
+struct SerdeValues<'a>(&'a NbtTagCompound);
+
+impl Serialize for SerdeValues<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.values.len()))?;
+        for (key, value) in &self.0.values {
+            map.serialize_entry(key, &SerdeTag(key, value))?;
+        }
+        map.end()
+    }
+}
+
+/// A tag in the serde shape, under the given name: `{"Int": {"name": ..., "value": 5}}`.
+struct SerdeTag<'a>(&'a str, &'a NbtTag);
+
+impl Serialize for SerdeTag<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let id = self.1.ty().id();
+        let variant = VARIANTS[id as usize];
+        match self.1 {
+            NbtTag::End => serializer.serialize_unit_variant("NbtTag", id as u32, variant),
+            NbtTag::Compound(val) => serializer.serialize_newtype_variant("NbtTag", id as u32, variant, &SerdeCompound(self.0, val)),
+            tag => serializer.serialize_newtype_variant("NbtTag", id as u32, variant, &SerdeFields(self.0, tag)),
+        }
+    }
+}
+
+/// The fields of a tag other than a compound, after its name.
+struct SerdeFields<'a>(&'a str, &'a NbtTag);
+
+impl Serialize for SerdeFields<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len = if let NbtTag::List(_) = self.1 { 3 } else { 2 };
+        let mut fields = serializer.serialize_struct(VARIANTS[self.1.ty().id() as usize], len)?;
+        fields.serialize_field("name", self.0)?;
+        match self.1 {
+            NbtTag::Byte(val) => fields.serialize_field("value", &val.value)?,
+            NbtTag::Short(val) => fields.serialize_field("value", &val.value)?,
+            NbtTag::Int(val) => fields.serialize_field("value", &val.value)?,
+            NbtTag::Long(val) => fields.serialize_field("value", &val.value)?,
+            NbtTag::Float(val) => fields.serialize_field("value", &val.value)?,
+            NbtTag::Double(val) => fields.serialize_field("value", &val.value)?,
+            NbtTag::String(val) => fields.serialize_field("value", &val.value)?,
+            NbtTag::ByteArray(val) => fields.serialize_field("values", &val.values)?,
+            NbtTag::IntArray(val) => fields.serialize_field("values", &val.values)?,
+            NbtTag::LongArray(val) => fields.serialize_field("values", &val.values)?,
+            NbtTag::List(val) => {
+                fields.serialize_field("ty", &val.ty)?;
+                fields.serialize_field("values", &SerdeElements(&val.values))?;
+            }
+            NbtTag::End | NbtTag::Compound(_) => {}
+        }
+        fields.end()
+    }
+}
+
+struct SerdeElements<'a>(&'a [NbtTag]);
+
+impl Serialize for SerdeElements<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for value in self.0 {
+            seq.serialize_element(&SerdeTag("", value))?;
+        }
+        seq.end()
+    }
+}
+
+fn serde_root(value: &Value) -> Result<NbtTagCompound, NbtError> {
+    let mut compound = serde_compound(value)?;
+    compound.name = match value.get("name") {
+        Some(Value::String(name)) => name.clone(),
+        None => String::new(),
+        Some(_) => return Err(json_error("the root name must be a string".to_string())),
+    };
+    Ok(compound)
+}
+
+/// Reads the `values` of a compound in the serde shape; the names of tags are not read.
+fn serde_compound(value: &Value) -> Result<NbtTagCompound, NbtError> {
+    let map = value.get("values").and_then(Value::as_object).ok_or_else(|| json_error(format!("expected a compound, found {}", value)))?;
+
+    let mut compound = NbtTagCompound::new("");
+    for (key, value) in map {
+        compound.values.insert(key.clone(), serde_tag(value).map_err(|e| e.in_key(key))?);
+    }
+    Ok(compound)
+}
+
+fn serde_tag(value: &Value) -> Result<NbtTag, NbtError> {
+    let (variant, fields) = match value {
+        Value::String(variant) if variant == "End" => return Ok(NbtTag::End),
+        Value::Object(map) if map.len() == 1 => map.iter().next().unwrap(),
+        _ => return Err(json_error(format!("expected a tag, found {}", value))),
+    };
+    let ty = NbtTagType::from_name(variant).filter(|ty| *ty != NbtTagType::End).ok_or_else(|| json_error(format!("unknown tag {:?}", variant)))?;
+    let expected = || json_error(format!("expected a {} tag, found {}", variant, fields));
+    let field = |key: &str| fields.get(key).ok_or_else(expected);
+
+    Ok(match ty {
+        NbtTagType::End => return Err(expected()),
+        NbtTagType::Byte => NbtTag::Byte(NbtTagByte::new(integer(field("value")?).ok_or_else(expected)?)),
+        NbtTagType::Short => NbtTag::Short(NbtTagShort::new(integer(field("value")?).ok_or_else(expected)?)),
+        NbtTagType::Int => NbtTag::Int(NbtTagInt::new(integer(field("value")?).ok_or_else(expected)?)),
+        NbtTagType::Long => NbtTag::Long(NbtTagLong::new(integer(field("value")?).ok_or_else(expected)?)),
+        NbtTagType::Float => NbtTag::Float(NbtTagFloat::new(field("value")?.as_f64().ok_or_else(expected)? as f32)),
+        NbtTagType::Double => NbtTag::Double(NbtTagDouble::new(field("value")?.as_f64().ok_or_else(expected)?)),
+        NbtTagType::String => NbtTag::String(NbtTagString::new(field("value")?.as_str().ok_or_else(expected)?.to_string())),
+        NbtTagType::ByteArray => NbtTag::ByteArray(NbtTagByteArray::new(integers(field("values")?).ok_or_else(expected)?)),
+        NbtTagType::IntArray => NbtTag::IntArray(NbtTagIntArray::new(integers(field("values")?).ok_or_else(expected)?)),
+        NbtTagType::LongArray => NbtTag::LongArray(NbtTagLongArray::new(integers(field("values")?).ok_or_else(expected)?)),
+        NbtTagType::List => {
+            let element_type = field("ty")?.as_str().and_then(NbtTagType::from_name).ok_or_else(expected)?;
+            let elements = field("values")?.as_array().ok_or_else(expected)?;
+
+            let mut values = Vec::with_capacity(elements.len());
+            for (index, element) in elements.iter().enumerate() {
+                values.push(serde_tag(element).map_err(|e| e.in_index(index))?);
+            }
+            NbtTag::List(NbtTagList::new(element_type, values))
+        }
+        NbtTagType::Compound => NbtTag::Compound(serde_compound(fields)?),
+    })
+}
+
+fn typed_root(value: &Value) -> Result<NbtTagCompound, NbtError> {
+    let map = value.as_object().ok_or_else(|| json_error("the root must be an object".to_string()))?;
+    let name = match map.get("name") {
+        Some(Value::String(name)) => name.clone(),
+        None => String::new(),
+        Some(_) => return Err(json_error("the root name must be a string".to_string())),
+    };
+
+    match typed_tag(value)? {
+        NbtTag::Compound(mut compound) => {
+            compound.name = name;
+            Ok(compound)
+        }
+        tag => Err(NbtError::new(NbtErrorKind::InvalidRoot(tag.ty()), 0)),
+    }
+}
+
+pub(crate) fn typed_tag(value: &Value) -> Result<NbtTag, NbtError> {
+    let map = value.as_object().ok_or_else(|| json_error(format!("expected a typed tag, found {}", value)))?;
+    let ty = map.get("type").and_then(Value::as_str).ok_or_else(|| json_error("missing \"type\"".to_string()))?;
+    let ty = type_from_name(ty).filter(|ty| *ty != NbtTagType::End).ok_or_else(|| json_error(format!("unknown type {:?}", ty)))?;
+    let payload = map.get("value").ok_or_else(|| json_error("missing \"value\"".to_string()))?;
+
+    typed_payload(ty, payload)
+}
+
+fn typed_payload(ty: NbtTagType, value: &Value) -> Result<NbtTag, NbtError> {
+    let expected = || json_error(format!("expected a {} value, found {}", type_name(ty), value));
+
+    Ok(match ty {
+        NbtTagType::End => return Err(expected()),
+        NbtTagType::Byte => NbtTag::Byte(NbtTagByte::new(integer(value).ok_or_else(expected)?)),
+        NbtTagType::Short => NbtTag::Short(NbtTagShort::new(integer(value).ok_or_else(expected)?)),
+        NbtTagType::Int => NbtTag::Int(NbtTagInt::new(integer(value).ok_or_else(expected)?)),
+        NbtTagType::Long => NbtTag::Long(NbtTagLong::new(integer(value).ok_or_else(expected)?)),
+        NbtTagType::Float => NbtTag::Float(NbtTagFloat::new(float(value).ok_or_else(expected)? as f32)),
+        NbtTagType::Double => NbtTag::Double(NbtTagDouble::new(float(value).ok_or_else(expected)?)),
+        NbtTagType::String => NbtTag::String(NbtTagString::new(value.as_str().ok_or_else(expected)?.to_string())),
+        NbtTagType::ByteArray => NbtTag::ByteArray(NbtTagByteArray::new(integers(value).ok_or_else(expected)?)),
+        NbtTagType::IntArray => NbtTag::IntArray(NbtTagIntArray::new(integers(value).ok_or_else(expected)?)),
+        NbtTagType::LongArray => NbtTag::LongArray(NbtTagLongArray::new(integers(value).ok_or_else(expected)?)),
+        NbtTagType::List => {
+            let map = value.as_object().ok_or_else(expected)?;
+            let element_type = map.get("element_type").and_then(Value::as_str).and_then(type_from_name).ok_or_else(expected)?;
+            let elements = map.get("values").and_then(Value::as_array).ok_or_else(expected)?;
+
+            let mut values = Vec::with_capacity(elements.len());
+            for (index, element) in elements.iter().enumerate() {
+                values.push(typed_payload(element_type, element).map_err(|e| e.in_index(index))?);
+            }
+            NbtTag::List(NbtTagList::new(element_type, values))
+        }
+        NbtTagType::Compound => {
+            let map = value.as_object().ok_or_else(expected)?;
+            let mut compound = NbtTagCompound::new("");
+            for (key, value) in map {
+                compound.values.insert(key.clone(), typed_tag(value).map_err(|e| e.in_key(key))?);
+            }
+            NbtTag::Compound(compound)
+        }
+    })
+}
+
+fn integer<T: TryFrom<i64>>(value: &Value) -> Option<T> {
+    value.as_i64().and_then(|v| T::try_from(v).ok())
+}
+
+fn integers<T: TryFrom<i64>>(value: &Value) -> Option<Vec<T>> {
+    value.as_array()?.iter().map(integer).collect()
+}
+
+fn float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) if s == "NaN" => Some(f64::NAN),
+        Value::String(s) if s == "Infinity" => Some(f64::INFINITY),
+        Value::String(s) if s == "-Infinity" => Some(f64::NEG_INFINITY),
+        _ => None,
+    }
+}
+
+fn plain_compound(map: &Map<String, Value>) -> Result<NbtTagCompound, NbtError> {
+    let mut compound = NbtTagCompound::new("");
+    for (key, value) in map {
+        if value.is_null() {
+            continue;
+        }
+        compound.values.insert(key.clone(), plain_tag(value).map_err(|e| e.in_key(key))?);
+    }
+
+    Ok(compound)
+}
+
+/// Infers the tag type of a plain JSON value.
+fn plain_tag(value: &Value) -> Result<NbtTag, NbtError> {
+    Ok(match value {
+        Value::Null => return Err(json_error("null has no NBT type".to_string())),
+        Value::Bool(b) => NbtTag::Byte(NbtTagByte::new(*b as i8)),
+        Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(v), _) => match i32::try_from(v) {
+                Ok(v) => NbtTag::Int(NbtTagInt::new(v)),
+                Err(_) => NbtTag::Long(NbtTagLong::new(v)),
+            },
+            (None, Some(v)) => NbtTag::Double(NbtTagDouble::new(v)),
+            (None, None) => return Err(json_error(format!("number {} out of range", n))),
+        },
+        Value::String(s) => NbtTag::String(NbtTagString::new(s.clone())),
+        Value::Object(map) => NbtTag::Compound(plain_compound(map)?),
+        Value::Array(elements) => {
+            let mut values = Vec::with_capacity(elements.len());
+            for (index, element) in elements.iter().enumerate().filter(|(_, element)| !element.is_null()) {
+                values.push(plain_tag(element).map_err(|e| e.in_index(index))?);
+            }
+            NbtTag::List(plain_list(values)?)
+        }
+    })
+}
+
+/// Builds a list from inferred elements. Numbers are widened to a common type: ints to
+/// longs, and any integer to double if the list holds a decimal.
+fn plain_list(values: Vec<NbtTag>) -> Result<NbtTagList, NbtError> {
+    let ty = values.iter().map(NbtTag::ty).reduce(|a, b| match (a, b) {
+        (a, b) if a == b => a,
+        (NbtTagType::Int, NbtTagType::Long) | (NbtTagType::Long, NbtTagType::Int) => NbtTagType::Long,
+        (NbtTagType::Int | NbtTagType::Long | NbtTagType::Double, NbtTagType::Int | NbtTagType::Long | NbtTagType::Double) => NbtTagType::Double,
+        _ => NbtTagType::End,
+    });
+
+    let ty = match ty {
+        None => return Ok(NbtTagList::new(NbtTagType::End, values)),
+        Some(NbtTagType::End) => return Err(json_error("list elements of different types".to_string())),
+        Some(ty) => ty,
+    };
+
+    let values = values.into_iter().map(|value| match (ty, value) {
+        (NbtTagType::Long, NbtTag::Int(v)) => NbtTag::Long(NbtTagLong::new(v.value as i64)),
+        (NbtTagType::Double, NbtTag::Int(v)) => NbtTag::Double(NbtTagDouble::new(v.value as f64)),
+        (NbtTagType::Double, NbtTag::Long(v)) => NbtTag::Double(NbtTagDouble::new(v.value as f64)),
+        (_, value) => value,
+    }).collect();
+
+    Ok(NbtTagList::new(ty, values))
+}
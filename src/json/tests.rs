@@ -0,0 +1,133 @@
+#[cfg(test)]
+
+use super::*;
+
+fn sample() -> NbtTagCompound {
+    let mut compound = NbtTagCompound::new("Level");
+    compound.values.insert("b".to_string(), NbtTag::Byte(NbtTagByte::new(1)));
+    compound.values.insert("s".to_string(), NbtTag::Short(NbtTagShort::new(1)));
+    compound.values.insert("i".to_string(), NbtTag::Int(NbtTagInt::new(1)));
+    compound.values.insert("l".to_string(), NbtTag::Long(NbtTagLong::new(i64::MAX)));
+    compound.values.insert("f".to_string(), NbtTag::Float(NbtTagFloat::new(0.1)));
+    compound.values.insert("d".to_string(), NbtTag::Double(NbtTagDouble::new(0.1)));
+    compound.values.insert("nan".to_string(), NbtTag::Float(NbtTagFloat::new(f32::NAN)));
+    compound.values.insert("inf".to_string(), NbtTag::Double(NbtTagDouble::new(f64::NEG_INFINITY)));
+    compound.values.insert("t".to_string(), NbtTag::String(NbtTagString::new("minecraft:stone".to_string())));
+    compound.values.insert("ba".to_string(), NbtTag::ByteArray(NbtTagByteArray::new(vec![1, -1])));
+    compound.values.insert("ia".to_string(), NbtTag::IntArray(NbtTagIntArray::new(vec![1, -1])));
+    compound.values.insert("la".to_string(), NbtTag::LongArray(NbtTagLongArray::new(vec![1, -1])));
+    compound.values.insert("empty".to_string(), NbtTag::List(NbtTagList::new(NbtTagType::Short, Vec::new())));
+    let inner = vec![NbtTag::List(NbtTagList::new(NbtTagType::Byte, vec![NbtTag::Byte(NbtTagByte::new(2))]))];
+    compound.values.insert("nested".to_string(), NbtTag::List(NbtTagList::new(NbtTagType::List, inner)));
+    let mut child = NbtTagCompound::new("c");
+    child.values.insert("x".to_string(), NbtTag::Int(NbtTagInt::new(3)));
+    compound.values.insert("c".to_string(), NbtTag::Compound(child));
+    compound
+}
+
+/// NaN is never equal to itself, compare the SNBT text instead.
+fn assert_same(a: &NbtTagCompound, b: &NbtTagCompound) {
+    assert_eq!(a.name, b.name);
+    assert_eq!(a.values.keys().collect::<Vec<_>>(), b.values.keys().collect::<Vec<_>>());
+    assert_eq!(a.to_snbt(), b.to_snbt());
+}
+
+#[test]
+fn test_typed_round_trip() {
+    let compound = sample();
+    let text = to_string(&compound, JsonFormat::Typed);
+
+    assert_same(&from_str(&text, JsonFormat::Typed).unwrap(), &compound);
+}
+
+#[test]
+fn test_typed_shape() {
+    let value: Value = serde_json::from_str(&to_string(&sample(), JsonFormat::Typed)).unwrap();
+
+    assert_eq!(value["name"], "Level");
+    assert_eq!(value["type"], "compound");
+    assert_eq!(value["value"]["s"], serde_json::json!({"type": "short", "value": 1}));
+    assert_eq!(value["value"]["f"], serde_json::json!({"type": "float", "value": 0.1}));
+    assert_eq!(value["value"]["nan"]["value"], "NaN");
+    assert_eq!(value["value"]["empty"], serde_json::json!({"type": "list", "value": {"element_type": "short", "values": []}}));
+    assert_eq!(value["value"]["nested"]["value"]["values"], serde_json::json!([{"element_type": "byte", "values": [2]}]));
+}
+
+#[test]
+fn test_plain_shape() {
+    let value: Value = serde_json::from_str(&to_string(&sample(), JsonFormat::Plain)).unwrap();
+
+    assert_eq!(value["b"], 1);
+    assert_eq!(value["l"], i64::MAX);
+    assert_eq!(value["t"], "minecraft:stone");
+    assert_eq!(value["la"], serde_json::json!([1, -1]));
+    assert_eq!(value["nested"], serde_json::json!([[2]]));
+    assert_eq!(value["c"], serde_json::json!({"x": 3}));
+    assert!(value["nan"].is_null());
+}
+
+#[test]
+fn test_plain_import_infers_types() {
+    let text = r#"{"a": 1, "b": 5000000000, "c": 0.5, "d": true, "e": "x", "f": [1, 5000000000], "g": [1, 0.5], "h": [], "i": {"j": null}, "k": [null, 2.0]}"#;
+    let compound = from_str(text, JsonFormat::Plain).unwrap();
+
+    assert_eq!(compound.name, "");
+    assert_eq!(compound.values["a"].ty(), NbtTagType::Int);
+    assert_eq!(compound.values["b"].ty(), NbtTagType::Long);
+    assert_eq!(compound.values["c"].ty(), NbtTagType::Double);
+    assert_eq!(compound.values["d"].byte().unwrap().value, 1);
+    assert_eq!(compound.values["e"].ty(), NbtTagType::String);
+    assert_eq!(compound.values["f"].list().unwrap().ty, NbtTagType::Long);
+    assert_eq!(compound.values["g"].list().unwrap().ty, NbtTagType::Double);
+    assert_eq!(compound.values["h"].list().unwrap().ty, NbtTagType::End);
+    assert!(compound.values["i"].compound().unwrap().values.is_empty());
+    assert_eq!(compound.values["k"].list().unwrap().values, vec![NbtTag::Double(NbtTagDouble::new(2.0))]);
+}
+
+#[test]
+fn test_plain_import_mixed_list() {
+    let error = from_str(r#"{"a": {"b": [1, "x"]}}"#, JsonFormat::Plain).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::InvalidJson(_)));
+    assert_eq!(error.path(), "a.b");
+}
+
+#[test]
+fn test_typed_import_errors_have_path() {
+    let text = r#"{"name": "", "type": "compound", "value": {"a": {"type": "list", "value": {"element_type": "byte", "values": [1, 300]}}}}"#;
+    let error = from_str(text, JsonFormat::Typed).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::InvalidJson(_)));
+    assert_eq!(error.path(), "a[1]");
+}
+
+#[test]
+fn test_serde_round_trip() {
+    // The serde shape writes non-finite floats as null and cannot read them back
+    let mut compound = sample();
+    compound.values.shift_remove("nan");
+    compound.values.shift_remove("inf");
+    let text = to_string(&compound, JsonFormat::Serde);
+
+    assert_same(&from_str(&text, JsonFormat::Serde).unwrap(), &compound);
+}
+
+#[test]
+fn test_detect() {
+    let compound = sample();
+    for format in [JsonFormat::Serde, JsonFormat::Typed, JsonFormat::Plain] {
+        let value: Value = serde_json::from_str(&to_string(&compound, format)).unwrap();
+        assert_eq!(JsonFormat::detect(&value), format);
+    }
+
+    let plain: Value = serde_json::from_str(r#"{"type": "zombie", "value": 5}"#).unwrap();
+    assert_eq!(JsonFormat::detect(&plain), JsonFormat::Plain);
+}
+
+#[test]
+fn test_format_from_name() {
+    assert_eq!(JsonFormat::from_name("typed"), Some(JsonFormat::Typed));
+    assert_eq!(JsonFormat::from_name("plain"), Some(JsonFormat::Plain));
+    assert_eq!(JsonFormat::from_name("serde"), Some(JsonFormat::Serde));
+    assert_eq!(JsonFormat::from_name("xml"), None);
+}
@@ -5,10 +5,29 @@
 // - 2023-12-17
 //
 // ## File Version
-// - 1.0.0
+// - 1.0.19
 //
 // ## Changelog
 // - 1.0.0: Initial version
+// - 1.0.1: Raise NbtParseError with offset and tag path on corrupted input [mrmarkolinus:2026-10-16]
+// - 1.0.2: load_binary accepts ParseLimits [mrmarkolinus:2026-10-16]
+// - 1.0.3: Added the event_reader module [mrmarkolinus:2026-10-16]
+// - 1.0.4: Added the nbt_tag_ref module [mrmarkolinus:2026-10-16]
+// - 1.0.5: Added serde support, from_bytes and to_bytes at the crate root [mrmarkolinus:2026-10-16]
+// - 1.0.6: Added the snbt module [mrmarkolinus:2026-10-16]
+// - 1.0.7: Added the flavor module, read level.dat and Bedrock .mcstructure files [mrmarkolinus:2026-10-16]
+// - 1.0.8: Added the nbt_writer module [mrmarkolinus:2026-10-16]
+// - 1.0.9: Added the json module, to_json takes a format [mrmarkolinus:2026-10-16]
+// - 1.0.10: Added the nbt_path module and query [mrmarkolinus:2026-10-16]
+// - 1.0.11: Added the convert module and the nbt! macro [mrmarkolinus:2026-10-16]
+// - 1.0.12: Added the nbt_diff module and diff [mrmarkolinus:2026-10-16]
+// - 1.0.13: Added the nbt_patch module and apply_patch [mrmarkolinus:2026-10-16]
+// - 1.0.14: Added the nbt_schema module and validate [mrmarkolinus:2026-10-16]
+// - 1.0.15: Added the nbt_search module and search, search_compound walks lists of lists [mrmarkolinus:2026-10-16]
+// - 1.0.16: Python dicts take tag names from compound keys [mrmarkolinus:2026-10-16]
+// - 1.0.17: Python dicts are built from borrowed tags [mrmarkolinus:2026-10-16]
+// - 1.0.18: Files are read according to their content, added detect_format [mrmarkolinus:2026-10-16]
+// - 1.0.19: Python dicts of root compounds are built without copying them [mrmarkolinus:2026-10-16]
 
 pub mod nbt_tag;
 pub mod file_parser;
@@ -16,6 +35,33 @@ pub mod region;
 pub mod generic_bin;
 pub mod blocks;
 pub mod chunk_format;
+pub mod error;
+pub mod mutf8;
+pub mod event_reader;
+pub mod nbt_tag_ref;
+pub mod serde_nbt;
+pub mod snbt;
+pub mod flavor;
+pub mod nbt_writer;
+pub mod json;
+pub mod nbt_path;
+pub mod convert;
+pub mod nbt_diff;
+pub mod nbt_patch;
+pub mod nbt_schema;
+pub mod nbt_search;
+pub mod format_detect;
+
+pub use serde_nbt::{from_bytes, from_reader, to_bytes, ByteArray, IntArray, LongArray};
+pub use flavor::{Endianness, NbtFlavor};
+pub use nbt_writer::NbtWriter;
+pub use json::JsonFormat;
+pub use nbt_path::{NbtMatch, NbtPath};
+pub use nbt_diff::{DiffOptions, NbtChange};
+pub use nbt_patch::{NbtPatch, PatchOp};
+pub use nbt_schema::{NbtSchema, SchemaViolation};
+pub use nbt_search::{TagPredicate, Walk};
+pub use format_detect::DetectedFormat;
 
 use std::collections::HashMap;
 use std::io;
@@ -33,9 +79,12 @@ fn fastnbt(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<PyNbtTag>()?;
     m.add_class::<blocks::MinecraftBlock>()?;
     m.add_class::<blocks::Coordinates>()?;
+    m.add_class::<file_parser::ParseLimits>()?;
+    m.add("NbtParseError", py.get_type::<error::NbtParseError>())?;
     
     m.add_function(wrap_pyfunction!(load_binary, m)?)?;
     m.add_function(wrap_pyfunction!(py_log, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_format, m)?)?;
 
     Ok(())
 }
@@ -44,10 +93,19 @@ fn py_log(message: String)  {
     info!("{}", message);
 }
 
+/// Name of the format of the file, found from its content: `region`, `java_nbt`,
+/// `bedrock_nbt`, `bedrock_level_dat`, `json` or `unknown`.
 #[pyfunction]
-fn load_binary(input_path: String) -> PyResult<PyMcWorldDescriptor> {   
+fn detect_format(input_path: String) -> PyResult<String> {
+    let format = format_detect::detect_file(input_path).map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)))?;
+    Ok(format.name().to_string())
+}
+
+#[pyfunction]
+#[pyo3(signature = (input_path, limits=None))]
+fn load_binary(input_path: String, limits: Option<file_parser::ParseLimits>) -> PyResult<PyMcWorldDescriptor> {   
     let path_buf = PathBuf::from(input_path);
-    let mc_world = McWorldDescriptor::new(path_buf)?; 
+    let mc_world = McWorldDescriptor::new_with_limits(path_buf, limits.unwrap_or_default()).map_err(error::io_error_to_py)?; 
     PyMcWorldDescriptor::new(mc_world).map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)))
 }
 
@@ -68,8 +126,7 @@ impl PyMcWorldDescriptor {
         let mut py_tag_list = Vec::<Py<PyDict>>::new();
         
         rust_mc_world_descriptor.tag_compounds_list.iter().for_each(|item| {
-            let tag_root = nbt_tag::NbtTag::Compound(item.clone());
-            py_tag_list.push(PyNbtTag::new(&tag_root).python_dict)
+            py_tag_list.push(PyNbtTag::from_compound(&item.name, item).python_dict)
         });
 
         Ok(PyMcWorldDescriptor{ 
@@ -78,8 +135,13 @@ impl PyMcWorldDescriptor {
         })
     }
 
-    pub fn to_json(&self, path: String) -> PyResult<()> {
-        self.mc_world_descriptor.to_json(path).map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)))
+    /// Writes the first compound as JSON. `format` is `"serde"`, `"typed"` (lossless) or
+    /// `"plain"` (natural JSON), see the `json` module.
+    #[pyo3(signature = (path, format="serde"))]
+    pub fn to_json(&self, path: String, format: &str) -> PyResult<()> {
+        let format = json::JsonFormat::from_name(format)
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unknown JSON format {}", format)))?;
+        self.mc_world_descriptor.to_json_with_format(path, format).map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)))
     }
 
     pub fn get_mc_version(&self) -> String {
@@ -94,8 +156,8 @@ impl PyMcWorldDescriptor {
         
         if compound_found {
             for item in compound_tag_list {
-                let tag_root = nbt_tag::NbtTag::Compound(item.clone());
-                py_tag_list.push(PyNbtTag::new(&tag_root).python_dict);
+                // Every found compound is named `key`, as a root or as a key in its parent
+                py_tag_list.push(PyNbtTag::from_compound(key, item).python_dict);
             }
             (true, py_tag_list)
         } else {
@@ -108,6 +170,91 @@ impl PyMcWorldDescriptor {
         self.mc_world_descriptor.search_blocks(block_resource_location)
     }
 
+    /// Runs an NBT path such as `sections[].block_states.palette[{Name:"minecraft:chest"}]`
+    /// on every compound. Returns `(compound index, concrete path, tag as dict)` tuples.
+    pub fn query(&self, path: &str) -> PyResult<Vec<(usize, String, Py<PyDict>)>> {
+        let matches = self.mc_world_descriptor.query(path)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("{}", e)))?;
+
+        Ok(matches.into_iter().map(|(index, found)| {
+            let python_dict = PyNbtTag::new(&found.key(), &found.tag).python_dict;
+            (index, found.path, python_dict)
+        }).collect())
+    }
+
+    /// Finds tags at any depth. `key` is a glob on the tag name (`*`, `?`), `tag_type` a type
+    /// name such as `"Long"`, `value` SNBT the tag must contain and `regex` a pattern matched
+    /// in `String` values. Returns `(compound index, path, tag as dict)` tuples.
+    #[pyo3(signature = (key=None, tag_type=None, value=None, regex=None))]
+    pub fn search(&self, key: Option<&str>, tag_type: Option<&str>, value: Option<&str>, regex: Option<&str>) -> PyResult<Vec<(usize, String, Py<PyDict>)>> {
+        let value_error = |message: String| PyErr::new::<pyo3::exceptions::PyValueError, _>(message);
+        let mut predicate = TagPredicate::new();
+
+        if let Some(key) = key {
+            predicate = predicate.with_key(key);
+        }
+        if let Some(name) = tag_type {
+            predicate = predicate.with_type(nbt_tag::NbtTagType::from_name(name).ok_or_else(|| value_error(format!("Unknown tag type {}", name)))?);
+        }
+        if let Some(value) = value {
+            predicate = predicate.with_value(nbt_tag::NbtTag::from_snbt(value).map_err(|e| value_error(format!("{}", e)))?);
+        }
+        if let Some(regex) = regex {
+            predicate = predicate.with_regex(regex).map_err(|e| value_error(format!("{}", e)))?;
+        }
+
+        let matches = self.mc_world_descriptor.search(&predicate);
+        Ok(matches.into_iter().map(|(index, found)| {
+            let python_dict = PyNbtTag::new(&found.key(), &found.tag).python_dict;
+            (index, found.path, python_dict)
+        }).collect())
+    }
+
+    /// Readable report of what changed from this file to `other`, one change per line.
+    /// Floats within `float_tolerance` are equal, lists of compounds holding one of
+    /// `list_keys` (e.g. `["UUID"]`) are matched by that key instead of by position.
+    #[pyo3(signature = (other, float_tolerance=0.0, list_keys=Vec::new()))]
+    pub fn diff(&self, other: &PyMcWorldDescriptor, float_tolerance: f64, list_keys: Vec<String>) -> String {
+        let options = list_keys.iter().fold(nbt_diff::DiffOptions::default().with_float_tolerance(float_tolerance), |options, key| options.with_list_key(key));
+        let changes = self.mc_world_descriptor.diff(&other.mc_world_descriptor, &options);
+
+        if changes.is_empty() {
+            return "No differences\n".to_string();
+        }
+
+        // Only region files have more than one compound worth numbering
+        let numbered = self.mc_world_descriptor.tag_compounds_list.len().max(other.mc_world_descriptor.tag_compounds_list.len()) > 1;
+        changes.iter().map(|(index, change)| match numbered {
+            true => format!("[{}] {}\n", index, change),
+            false => format!("{}\n", change),
+        }).collect()
+    }
+
+    /// Checks every compound against a built-in schema: `chunk`, `section`, `block_states`,
+    /// `litematic` or `structure`. Returns one line per violation, prefixed with the index of
+    /// the compound.
+    pub fn validate(&self, schema: &str) -> PyResult<Vec<String>> {
+        let schema = nbt_schema::NbtSchema::from_name(schema)
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unknown schema {}", schema)))?;
+
+        Ok(self.mc_world_descriptor.validate(&schema).iter().map(|(index, violation)| format!("[{}] {}", index, violation)).collect())
+    }
+
+    /// Applies a JSON patch, see `nbt_patch`, to every compound and returns the inverse
+    /// patch of each compound as JSON. On error no compound is changed.
+    pub fn apply_patch(&mut self, patch: &str) -> PyResult<Vec<String>> {
+        let patch = nbt_patch::NbtPatch::from_json(patch)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("{}", e)))?;
+        let inverses = self.mc_world_descriptor.apply_patch(&patch)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("{}", e)))?;
+
+        self.tag_compounds_list = self.mc_world_descriptor.tag_compounds_list.iter()
+            .map(|item| PyNbtTag::from_compound(&item.name, item).python_dict)
+            .collect();
+
+        Ok(inverses.iter().map(nbt_patch::NbtPatch::to_json).collect())
+    }
+
 }
 
 #[pyclass]
@@ -120,24 +267,23 @@ pub struct McWorldDescriptor {
 
 impl McWorldDescriptor {
     pub fn new(input_path: PathBuf) -> std::io::Result<Self> {
+        Self::new_with_limits(input_path, file_parser::ParseLimits::default())
+    }
+
+    /// Same as `new`, parsing the input with the given limits (e.g. for untrusted uploads).
+    pub fn new_with_limits(input_path: PathBuf, limits: file_parser::ParseLimits) -> std::io::Result<Self> {
         let cloned_input_path = input_path.clone();
-        
-        if let Ok(nbt_tag_compounds_list) = Self::read_input_path(input_path) {
-            Ok(McWorldDescriptor {
-                input_path: cloned_input_path,
-                version: "0.0.0".to_string(),
-                tag_compounds_list: nbt_tag_compounds_list,
-            })
-        }
-        else{
-            //TODO: read a file not only based on the extension, but checking the internal format
-            Err(std::io::Error::new(std::io::ErrorKind::Other, "McWorldDescriptor not created because of input file error"))
-        } 
 
-        
+        let nbt_tag_compounds_list = Self::read_input_path(input_path, &limits)?;
+
+        Ok(McWorldDescriptor {
+            input_path: cloned_input_path,
+            version: "0.0.0".to_string(),
+            tag_compounds_list: nbt_tag_compounds_list,
+        })
     }
 
-    fn read_input_path(input_path: PathBuf) -> std::io::Result<Vec<nbt_tag::NbtTagCompound>> {
+    fn read_input_path(input_path: PathBuf, limits: &file_parser::ParseLimits) -> std::io::Result<Vec<nbt_tag::NbtTagCompound>> {
         
         /* #10: The use can give in input either a folder path to Minecraft world or directly a file path.
         *  Here the path is checked, if a folder is found, the subfolder "region" is searched.
@@ -164,7 +310,7 @@ impl McWorldDescriptor {
                     for entry in entries {
                         if let Ok(entry) = entry {
                             let file_path = PathBuf::from(entry.path().to_string_lossy().into_owned());
-                            nbt_tag_compounds_list.append(&mut Self::read_file_format(file_path)?);
+                            nbt_tag_compounds_list.append(&mut Self::read_file_format(file_path, limits)?);
                         }
                     }
                 },
@@ -172,7 +318,7 @@ impl McWorldDescriptor {
             }
         }
         else {
-            nbt_tag_compounds_list.append(&mut Self::read_file_format(input_path)?);
+            nbt_tag_compounds_list.append(&mut Self::read_file_format(input_path, limits)?);
         }
         
 
@@ -180,36 +326,32 @@ impl McWorldDescriptor {
 
     }
 
-    fn read_file_format(input_path: PathBuf) -> std::io::Result<Vec<nbt_tag::NbtTagCompound>> {
-        
-        if let Some(ext) = input_path.extension().and_then(|e| e.to_str()) {
-
-            //let mut nbt_tag_compounds_list = Vec::<nbt_tag::NbtTagCompound>::new();
-
-            if ext == "mcr" || ext == "mca" {
-                let region_file = region::RegionFile::new(input_path)?;
-                let nbt_tag_compounds_list = region_file.to_compounds_list()?;
-                Ok(nbt_tag_compounds_list)
+    fn read_file_format(input_path: PathBuf, limits: &file_parser::ParseLimits) -> std::io::Result<Vec<nbt_tag::NbtTagCompound>> {
+        // Chosen from the content, backups and exports often have another extension or none
+        match format_detect::detect_file(&input_path)? {
+            format_detect::DetectedFormat::Region => {
+                let region_file = region::RegionFile::new(input_path)?.with_limits(*limits);
+                region_file.to_compounds_list()
             }
-            else if ext == "nbt" || ext == "litematic" {
-                let bin_content = generic_bin::GenericBinFile::new(input_path, generic_bin::FileType::Nbt)?;
-                let nbt_tag_compounds_list = bin_content.to_compounds_list()?;
-                Ok(nbt_tag_compounds_list)   
+            format_detect::DetectedFormat::JavaNbt(_) | format_detect::DetectedFormat::BedrockLevelDat => {
+                // A Bedrock level.dat is recognized from its header
+                let bin_content = generic_bin::GenericBinFile::new(input_path, generic_bin::FileType::Nbt)?.with_limits(*limits);
+                bin_content.to_compounds_list()
             }
-            else if ext == "json" {
-                let json_content = nbt_tag::NbtTagCompound::from_json(input_path)?;//Self::from_json(input_path)?;
-                let mut nbt_tag_compounds_list = Vec::<nbt_tag::NbtTagCompound>::new();
-                nbt_tag_compounds_list.push(json_content);
-                Ok(nbt_tag_compounds_list)
+            format_detect::DetectedFormat::BedrockNbt(_) => {
+                let bin_content = generic_bin::GenericBinFile::new(input_path, generic_bin::FileType::Nbt)?
+                    .with_limits(*limits)
+                    .with_flavor(flavor::NbtFlavor::Bedrock);
+                bin_content.to_compounds_list()
             }
-            else {
-                Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid file extension"))
+            format_detect::DetectedFormat::Json => {
+                let json_content = nbt_tag::NbtTagCompound::from_json(input_path)?;
+                Ok(vec![json_content])
+            }
+            format_detect::DetectedFormat::Unknown => {
+                Err(io::Error::new(io::ErrorKind::InvalidInput, "Unknown file format"))
             }
         }
-        else {
-            Err(io::Error::new(io::ErrorKind::InvalidInput, "File without extension"))
-        }
-
     }
 
     pub fn get_mc_version(&self) -> String {
@@ -217,7 +359,74 @@ impl McWorldDescriptor {
     }
 
     pub fn to_json<P: AsRef<std::path::Path>>(&self, path: P) -> io::Result<()> {
-        Ok(self.tag_compounds_list.get(0).unwrap().to_json(path)?)
+        self.to_json_with_format(path, json::JsonFormat::Serde)
+    }
+
+    pub fn to_json_with_format<P: AsRef<std::path::Path>>(&self, path: P, format: json::JsonFormat) -> io::Result<()> {
+        Ok(self.tag_compounds_list.get(0).unwrap().to_json_with_format(path, format)?)
+    }
+
+    /// Runs an NBT path on every compound, see `nbt_path`. Matches are paired with the index
+    /// of the compound they were found in.
+    pub fn query(&self, path: &str) -> Result<Vec<(usize, NbtMatch<'_>)>, error::NbtError> {
+        let path = NbtPath::parse(path)?;
+        let mut matches = Vec::new();
+
+        for (index, compound) in self.tag_compounds_list.iter().enumerate() {
+            matches.extend(path.query_compound(compound).into_iter().map(|found| (index, found)));
+        }
+
+        Ok(matches)
+    }
+
+    /// Compares the compounds of both descriptors pairwise, by position, and returns the
+    /// changes with the index of their compound. A compound present on one side only is
+    /// reported as a single added or removed root.
+    pub fn diff(&self, other: &McWorldDescriptor, options: &nbt_diff::DiffOptions) -> Vec<(usize, NbtChange)> {
+        let (old, new) = (&self.tag_compounds_list, &other.tag_compounds_list);
+        let mut changes = Vec::new();
+
+        for index in 0..old.len().max(new.len()) {
+            let found = match (old.get(index), new.get(index)) {
+                (Some(a), Some(b)) => nbt_diff::diff_compounds(a, b, options),
+                (Some(a), None) => vec![NbtChange::Removed { path: String::new(), value: nbt_tag::NbtTag::Compound(a.clone()) }],
+                (None, Some(b)) => vec![NbtChange::Added { path: String::new(), value: nbt_tag::NbtTag::Compound(b.clone()) }],
+                (None, None) => unreachable!(),
+            };
+            changes.extend(found.into_iter().map(|change| (index, change)));
+        }
+
+        changes
+    }
+
+    /// Checks every compound against `schema`, e.g. `nbt_schema::chunk()` for a region, and
+    /// returns the violations with the index of their compound.
+    pub fn validate(&self, schema: &NbtSchema) -> Vec<(usize, SchemaViolation)> {
+        self.tag_compounds_list.iter()
+            .enumerate()
+            .flat_map(|(index, compound)| compound.validate(schema).into_iter().map(move |violation| (index, violation)))
+            .collect()
+    }
+
+    /// Applies `patch` to every compound and returns the inverse patch of each one. If the
+    /// patch fails on a compound, the compounds already patched are restored.
+    pub fn apply_patch(&mut self, patch: &NbtPatch) -> Result<Vec<NbtPatch>, error::NbtError> {
+        let mut inverses = Vec::new();
+
+        for index in 0..self.tag_compounds_list.len() {
+            match patch.apply(&mut self.tag_compounds_list[index]) {
+                Ok(inverse) => inverses.push(inverse),
+                Err(e) => {
+                    for (compound, inverse) in self.tag_compounds_list.iter_mut().zip(inverses.iter()) {
+                        // The inverse restores values that were just there, this does not fail
+                        let _ = inverse.apply(compound);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(inverses)
     }
 
     pub fn search_blocks<'a>(&self, block_resource_location: Vec::<String>) -> HashMap::<String, Vec::<blocks::MinecraftBlock>> {
@@ -225,71 +434,41 @@ impl McWorldDescriptor {
     } 
 
 
+    /// Finds the compounds named `key`: roots by their name, nested compounds by their key,
+    /// at any depth. The compounds inside a found compound are not searched.
     pub fn search_compound(&self, key: &str, stop_at_first: bool) ->  (bool, Vec::<&nbt_tag::NbtTagCompound>) {
         
         let mut result_list = Vec::<&nbt_tag::NbtTagCompound>::new();
 
         for tag_compound in self.tag_compounds_list.iter() {
-            let compound_found = self.recursive_compound_search(tag_compound, &mut result_list, key, stop_at_first);
-            
-            if compound_found && stop_at_first {
-                return (true, result_list);
+            if tag_compound.name == key {
+                result_list.push(tag_compound);
             }
-        }
-
-        if result_list.is_empty() {
-            return (false, result_list);
-        }
-        else {
-            return (true, result_list);
-        }
-    }
-        
-    fn recursive_compound_search<'a>(&self, tag_compound: &'a nbt_tag::NbtTagCompound, 
-                                            result_list: &mut Vec<&'a nbt_tag::NbtTagCompound>, 
-                                            key: &str, 
-                                            stop_at_first: bool) 
-                                            -> bool {
-            
-        //End condition: a compound matches the key
-        if tag_compound.name == key {
-            result_list.push(tag_compound);
-            return true;
-        }
-        
-        //Recursion
-        for (_, v) in tag_compound.values.iter() {
-            if v.ty() == nbt_tag::NbtTagType::Compound {
-                let compound_option = v.compound_as_ref();
-                
-                if let Some(compound) = compound_option {
-                    let compound_found = self.recursive_compound_search(&compound, result_list, key, stop_at_first);
-                    
-                    if compound_found && stop_at_first {
-                        return true;
+            else {
+                nbt_search::walk_compound("", tag_compound, &mut |_, tag_key, tag| match tag.as_compound() {
+                    Some(compound) if tag_key == key => {
+                        result_list.push(compound);
+                        if stop_at_first { Walk::Stop } else { Walk::Skip }
                     }
-                }
+                    _ => Walk::Continue,
+                });
             }
-            else if v.ty() == nbt_tag::NbtTagType::List {
-                let list_option = v.list_as_ref();
-                if let Some(list_option) = list_option {
-                    for item in list_option.values.iter() {
-                        if item.ty() == nbt_tag::NbtTagType::Compound {
-                            let compound_option = item.compound_as_ref();
-                            if let Some(compound) = compound_option {
-                                let compound_found = self.recursive_compound_search(&compound, result_list, key, stop_at_first);
-                                    if compound_found && stop_at_first {
-                                        return true;
-                                    } 
-                            }
-                        }
-                        
-                    }
-                }
+
+            if stop_at_first && !result_list.is_empty() {
+                return (true, result_list);
             }
         }
-        
-        false
+
+        (!result_list.is_empty(), result_list)
+    }
+
+    /// Finds the tags matching `predicate` in every compound, see `nbt_search`. Matches are
+    /// paired with the index of the compound they were found in.
+    pub fn search(&self, predicate: &TagPredicate) -> Vec<(usize, NbtMatch<'_>)> {
+        self.tag_compounds_list.iter()
+            .enumerate()
+            .flat_map(|(index, compound)| compound.search(predicate).into_iter().map(move |found| (index, found)))
+            .collect()
     }
 
     /* fn read_from_binary_file(input_path: PathBuf) -> std::io::Result<Vec<nbt_tag::NbtTagCompound>> {
@@ -387,8 +566,10 @@ pub struct PyNbtTag {
 //https://github.com/PyO3/pyo3/pull/3582 
 impl PyNbtTag {
 
-    pub fn new(nbt_tag: &nbt_tag::NbtTag) -> Self {
-        let python_dict = Self::to_python_dictionary(&nbt_tag);
+    /// Converts the tag to `{name: value}`. Tags do not store their names, `name` is the
+    /// key of the tag in its compound, or the name of a root compound.
+    pub fn new(name: &str, nbt_tag: &nbt_tag::NbtTag) -> Self {
+        let python_dict = Self::to_python_dictionary(name, nbt_tag);
         //let ser_py_dict = Self::to_ser_python_dictionary(python_dict);
         Self {
             //python_dict,
@@ -396,11 +577,18 @@ impl PyNbtTag {
         }
     }
 
+    /// Same as `new` for a compound, which does not have to be wrapped in an `NbtTag`.
+    pub fn from_compound(name: &str, compound: &nbt_tag::NbtTagCompound) -> Self {
+        Self {
+            python_dict: Self::compound_to_python_dictionary(name, compound)
+        }
+    }
+
     /* fn to_ser_python_dictionary(py_dict: Py<PyDict>) -> SerializablePyDict {
         SerializablePyDict(py_dict)
     } */
 
-    fn to_python_dictionary(nbt_tag: & nbt_tag::NbtTag) -> Py<PyDict> {
+    fn to_python_dictionary(name: &str, nbt_tag: & nbt_tag::NbtTag) -> Py<PyDict> {
         
         Python::with_gil(|py| {
             let dict: Py<PyDict> = PyDict::new(py).into();
@@ -416,89 +604,89 @@ impl PyNbtTag {
                     dict
                 },
                 nbt_tag::NbtTagType::Byte => {
-                    let tag_byte = nbt_tag.byte().unwrap();
+                    let tag_byte = nbt_tag.as_byte().unwrap();
 
                     //let log_msg = format!("tag_byte: Name: {}, Value: {}", tag_byte.name, tag_byte.value);
                     //crate::py_log(log_msg);
 
-                    dict.as_ref(py).set_item(tag_byte.name, tag_byte.value).unwrap();
+                    dict.as_ref(py).set_item(name, tag_byte.value).unwrap();
                     dict
 
                 },
                 nbt_tag::NbtTagType::Short => {
-                    let tag_short = nbt_tag.short().unwrap();
+                    let tag_short = nbt_tag.as_short().unwrap();
 
                     //let log_msg = format!("tag_short: Name: {}, Value: {}", tag_short.name, tag_short.value);
                     //crate::py_log(log_msg);
 
 
-                    dict.as_ref(py).set_item(tag_short.name, tag_short.value).unwrap();
+                    dict.as_ref(py).set_item(name, tag_short.value).unwrap();
                     dict
 
                 },
                 nbt_tag::NbtTagType::Int => {
-                    let tag_int = nbt_tag.int().unwrap_or_default(); //error without default.
+                    let tag_int = nbt_tag.as_int().unwrap();
 
                     //let log_msg = format!("tag_int: Name: {}, Value: {}", tag_int.name, tag_int.value);
                     //crate::py_log(log_msg);
 
-                    dict.as_ref(py).set_item(tag_int.name, tag_int.value).unwrap();
+                    dict.as_ref(py).set_item(name, tag_int.value).unwrap();
                     dict
 
                 },
                 nbt_tag::NbtTagType::Long => {
-                    let tag_long = nbt_tag.long().unwrap();
+                    let tag_long = nbt_tag.as_long().unwrap();
 
                     //let log_msg = format!("tag_long: Name: {}, Value: {}", tag_long.name, tag_long.value);
                     //crate::py_log(log_msg);
 
-                    dict.as_ref(py).set_item(tag_long.name, tag_long.value).unwrap();
+                    dict.as_ref(py).set_item(name, tag_long.value).unwrap();
                     dict
 
                 },
                 nbt_tag::NbtTagType::Float => {
-                    let tag_float = nbt_tag.float().unwrap();
+                    let tag_float = nbt_tag.as_float().unwrap();
 
                     //let log_msg = format!("tag_float: Name: {}, Value: {}", tag_float.name, tag_float.value);
                     //crate::py_log(log_msg);
 
 
-                    dict.as_ref(py).set_item(tag_float.name, tag_float.value).unwrap();
+                    dict.as_ref(py).set_item(name, tag_float.value).unwrap();
                     dict
 
                 },
                 nbt_tag::NbtTagType::Double => {
-                    let tag_double = nbt_tag.double().unwrap();
+                    let tag_double = nbt_tag.as_double().unwrap();
 
                     //let log_msg = format!("tag_double: Name: {}, Value: {}", tag_double.name, tag_double.value);
                     //crate::py_log(log_msg);
 
-                    dict.as_ref(py).set_item(tag_double.name, tag_double.value).unwrap();
+                    dict.as_ref(py).set_item(name, tag_double.value).unwrap();
                     dict
 
                 },
                 nbt_tag::NbtTagType::ByteArray => {
-                    let tag_byte_array = nbt_tag.byte_array().unwrap();
+                    let tag_byte_array = nbt_tag.as_byte_array().unwrap();
 
                     //let log_msg = format!("tag_byte_array: Name: {}, Value: {}", tag_byte_array.name, "[Values]");
                     //crate::py_log(log_msg);
 
-                    dict.as_ref(py).set_item(tag_byte_array.name, tag_byte_array.values).unwrap();
+                    dict.as_ref(py).set_item(name, &tag_byte_array.values).unwrap();
                     dict
 
                 },
                 nbt_tag::NbtTagType::String => {
-                    let tag_string = nbt_tag.string().unwrap();
+                    let tag_string = nbt_tag.as_string().unwrap();
 
                     //let log_msg = format!("tag_string: Name: {}, Value: {}", tag_string.name, tag_string.value);
                     //crate::py_log(log_msg);
 
-                    dict.as_ref(py).set_item(tag_string.name, tag_string.value).unwrap();
+                    dict.as_ref(py).set_item(name, &tag_string.value).unwrap();
                     dict
 
                 },
                 nbt_tag::NbtTagType::List => {
-                    let tag_list = nbt_tag.list().unwrap();
+                    let tag_list = nbt_tag.as_list().unwrap();
                     let empty_object_array: &[PyObject] = &[];
                     let py_list: &PyList = PyList::new(py, empty_object_array);
 
@@ -507,58 +695,62 @@ impl PyNbtTag {
 
                     //not efficient, i am processind the data two times, but for now make it work
                     for list_element in &tag_list.values {
-                        let py_list_element = PyNbtTag::new(list_element);
+                        let py_list_element = PyNbtTag::new("", list_element);
                         let _ = py_list.append(py_list_element.python_dict);
 
                         //let log_msg = format!("tag_list: parsed");
                         //crate::py_log(log_msg);
                     }
 
-                    dict.as_ref(py).set_item(tag_list.name, py_list).unwrap();
-                    dict
-
-                },
-                nbt_tag::NbtTagType::Compound => {
-                    let tag_compound = nbt_tag.compound().unwrap();
-                    //let empty_object_array: &[PyObject] = &[];
-                    let py_dict: &PyDict = PyDict::new(py);
-
-                    //let log_msg = format!("tag_compound: Name: {}, Value: {}", tag_compound.name, "[HashMap]");
-                    //crate::py_log(log_msg);
-
-                    for (key, value) in tag_compound.values.iter() {
-                        let py_tag = PyNbtTag::new(value);
-                        let _ = py_dict.set_item(key, py_tag.python_dict);
-
-                        //let log_msg = format!("tag_compound_hashmap: Name: {}, Value: {}", key, "[NbtTag]");
-                        //crate::py_log(log_msg);
-                    }
-
-                    dict.as_ref(py).set_item(tag_compound.name, py_dict).unwrap();
+                    dict.as_ref(py).set_item(name, py_list).unwrap();
                     dict
 
                 },
+                nbt_tag::NbtTagType::Compound => Self::compound_to_python_dictionary(name, nbt_tag.as_compound().unwrap()),
                 nbt_tag::NbtTagType::IntArray => {
-                    let tag_int_array = nbt_tag.int_array().unwrap();
+                    let tag_int_array = nbt_tag.as_int_array().unwrap();
 
                     //let log_msg = format!("tag_int_array: Name: {}, Value: {}", tag_int_array.name, "[Values]");
                     //crate::py_log(log_msg);
 
-                    dict.as_ref(py).set_item(tag_int_array.name, tag_int_array.values).unwrap();
+                    dict.as_ref(py).set_item(name, &tag_int_array.values).unwrap();
                     dict
 
                 },
                 nbt_tag::NbtTagType::LongArray => {
-                    let tag_long_array = nbt_tag.long_array().unwrap();
+                    let tag_long_array = nbt_tag.as_long_array().unwrap();
 
                     //let log_msg = format!("tag_long_array: Name: {}, Value: {}", tag_long_array.name, "[Values]");
                     //crate::py_log(log_msg);
 
-                    dict.as_ref(py).set_item(tag_long_array.name, tag_long_array.values).unwrap();
+                    dict.as_ref(py).set_item(name, &tag_long_array.values).unwrap();
                     dict
 
                 }
             }
         })
     }
+
+    fn compound_to_python_dictionary(name: &str, tag_compound: &nbt_tag::NbtTagCompound) -> Py<PyDict> {
+
+        Python::with_gil(|py| {
+            let dict: Py<PyDict> = PyDict::new(py).into();
+            //let empty_object_array: &[PyObject] = &[];
+            let py_dict: &PyDict = PyDict::new(py);
+
+            //let log_msg = format!("tag_compound: Name: {}, Value: {}", tag_compound.name, "[HashMap]");
+            //crate::py_log(log_msg);
+
+            for (key, value) in tag_compound.values.iter() {
+                let py_tag = PyNbtTag::new(key, value);
+                let _ = py_dict.set_item(key, py_tag.python_dict);
+
+                //let log_msg = format!("tag_compound_hashmap: Name: {}, Value: {}", key, "[NbtTag]");
+                //crate::py_log(log_msg);
+            }
+
+            dict.as_ref(py).set_item(name, py_dict).unwrap();
+            dict
+        })
+    }
 }
@@ -0,0 +1,187 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.0
+//
+// ## Changelog
+// - 1.0.0: Initial version, Java Modified UTF-8 decoding and encoding [mrmarkolinus:2026-10-16]
+
+//! Java Modified UTF-8, the string encoding used by NBT for tag names and string values.
+//!
+//! It differs from standard UTF-8 in two ways: the NUL character is written as the two bytes
+//! `C0 80`, and characters outside the Basic Multilingual Plane are written as a UTF-16
+//! surrogate pair, each surrogate encoded on three bytes (CESU-8).
+//!
+//! Decoding never fails. Every byte that is not part of a canonical Modified UTF-8 sequence
+//! is mapped to the character `U+10FF00 + byte` (the last 256 code points of the
+//! Supplementary Private Use Area-B), and the encoder maps those characters back to the raw
+//! byte. This keeps `encode(decode(bytes)) == bytes` for any input, so files with broken
+//! strings survive a read/write round trip unchanged.
+
+use std::borrow::Cow;
+
+#[cfg(test)]
+mod tests;
+
+/// First code point of the range used to escape bytes that are not valid Modified UTF-8.
+pub const ESCAPE_BASE: u32 = 0x10FF00;
+
+/// Decodes Modified UTF-8 bytes. Plain UTF-8 input without NUL and supplementary
+/// characters, which is the vast majority of NBT strings, is returned without copying.
+pub fn decode(bytes: &[u8]) -> Cow<'_, str> {
+    if is_plain(bytes) {
+        if let Ok(s) = std::str::from_utf8(bytes) {
+            return Cow::Borrowed(s);
+        }
+    }
+
+    Cow::Owned(decode_slow(bytes))
+}
+
+/// Same as `decode`, but takes ownership of the buffer so the common case does not copy.
+pub fn decode_owned(bytes: Vec<u8>) -> String {
+    if is_plain(&bytes) {
+        match String::from_utf8(bytes) {
+            Ok(s) => s,
+            Err(e) => decode_slow(e.as_bytes()),
+        }
+    }
+    else {
+        decode_slow(&bytes)
+    }
+}
+
+/// Encodes a string as Modified UTF-8. Strings without NUL and supplementary characters
+/// are returned without copying, since for them the encoding is identical to UTF-8.
+pub fn encode(s: &str) -> Cow<'_, [u8]> {
+    if is_plain(s.as_bytes()) {
+        return Cow::Borrowed(s.as_bytes());
+    }
+
+    let mut out = Vec::with_capacity(s.len() + 8);
+    for ch in s.chars() {
+        let cp = ch as u32;
+        if cp >= ESCAPE_BASE {
+            out.push((cp - ESCAPE_BASE) as u8);
+        }
+        else if cp == 0 {
+            out.extend_from_slice(&[0xC0, 0x80]);
+        }
+        else if cp < 0x80 {
+            out.push(cp as u8);
+        }
+        else if cp < 0x800 {
+            out.push(0xC0 | (cp >> 6) as u8);
+            out.push(0x80 | (cp & 0x3F) as u8);
+        }
+        else if cp < 0x10000 {
+            push_three_bytes(&mut out, cp);
+        }
+        else {
+            let cp = cp - 0x10000;
+            push_three_bytes(&mut out, 0xD800 + (cp >> 10));
+            push_three_bytes(&mut out, 0xDC00 + (cp & 0x3FF));
+        }
+    }
+
+    Cow::Owned(out)
+}
+
+/// Bytes without NUL and without 4-byte UTF-8 lead bytes decode the same way as UTF-8.
+fn is_plain(bytes: &[u8]) -> bool {
+    bytes.iter().all(|&b| b != 0 && b < 0xF0)
+}
+
+fn push_three_bytes(out: &mut Vec<u8>, cp: u32) {
+    out.push(0xE0 | (cp >> 12) as u8);
+    out.push(0x80 | ((cp >> 6) & 0x3F) as u8);
+    out.push(0x80 | (cp & 0x3F) as u8);
+}
+
+fn is_continuation(b: u8) -> bool {
+    b & 0xC0 == 0x80
+}
+
+/// Decodes a canonical three byte sequence starting at `i`, if there is one.
+fn three_bytes_at(bytes: &[u8], i: usize) -> Option<u32> {
+    match bytes.get(i..i + 3) {
+        Some(&[b0, b1, b2]) if b0 & 0xF0 == 0xE0 && is_continuation(b1) && is_continuation(b2) => {
+            let cp = ((b0 as u32 & 0x0F) << 12) | ((b1 as u32 & 0x3F) << 6) | (b2 as u32 & 0x3F);
+            if cp >= 0x800 { Some(cp) } else { None }
+        }
+        _ => None,
+    }
+}
+
+fn push_escaped(out: &mut String, bytes: &[u8]) {
+    for &b in bytes {
+        // ESCAPE_BASE + u8 is always a valid code point
+        out.push(char::from_u32(ESCAPE_BASE + b as u32).unwrap());
+    }
+}
+
+fn decode_slow(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b0 = bytes[i];
+
+        match b0 {
+            0x01..=0x7F => {
+                out.push(b0 as char);
+                i += 1;
+                continue;
+            }
+            0xC0..=0xDF => {
+                if let Some(&b1) = bytes.get(i + 1) {
+                    let cp = ((b0 as u32 & 0x1F) << 6) | (b1 as u32 & 0x3F);
+                    // Only the canonical form is accepted, plus C0 80 for NUL
+                    if is_continuation(b1) && (cp >= 0x80 || (b0 == 0xC0 && b1 == 0x80)) {
+                        out.push(char::from_u32(cp).unwrap());
+                        i += 2;
+                        continue;
+                    }
+                }
+            }
+            0xE0..=0xEF => {
+                if let Some(cp) = three_bytes_at(bytes, i) {
+                    match cp {
+                        0xD800..=0xDBFF => {
+                            if let Some(low @ 0xDC00..=0xDFFF) = three_bytes_at(bytes, i + 3) {
+                                let cp = 0x10000 + ((cp - 0xD800) << 10) + (low - 0xDC00);
+                                if cp >= ESCAPE_BASE {
+                                    // A real character in the escape range would not survive
+                                    // encoding, keep its bytes instead
+                                    push_escaped(&mut out, &bytes[i..i + 6]);
+                                }
+                                else {
+                                    out.push(char::from_u32(cp).unwrap());
+                                }
+                                i += 6;
+                                continue;
+                            }
+                        }
+                        0xDC00..=0xDFFF => {}
+                        _ => {
+                            out.push(char::from_u32(cp).unwrap());
+                            i += 3;
+                            continue;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Not the start of a valid sequence: escape this byte and resync on the next one
+        push_escaped(&mut out, &bytes[i..i + 1]);
+        i += 1;
+    }
+
+    out
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+
+use super::*;
+
+#[test]
+fn test_ascii_is_borrowed() {
+    assert!(matches!(decode(b"minecraft:stone"), Cow::Borrowed("minecraft:stone")));
+    assert!(matches!(encode("minecraft:stone"), Cow::Borrowed(b"minecraft:stone")));
+}
+
+#[test]
+fn test_decode_multi_byte() {
+    let text = "Grüße, 世界";
+    assert_eq!(decode(text.as_bytes()), text);
+    assert_eq!(encode(text).as_ref(), text.as_bytes());
+}
+
+#[test]
+fn test_nul_is_two_bytes() {
+    assert_eq!(encode("a\0b").as_ref(), &[b'a', 0xC0, 0x80, b'b']);
+    assert_eq!(decode(&[b'a', 0xC0, 0x80, b'b']), "a\0b");
+}
+
+#[test]
+fn test_supplementary_characters_use_surrogate_pairs() {
+    // U+1F600 as CESU-8: D83D DE00
+    let cesu = [0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80];
+    assert_eq!(encode("\u{1F600}").as_ref(), &cesu);
+    assert_eq!(decode(&cesu), "\u{1F600}");
+}
+
+#[test]
+fn test_invalid_sequences_are_escaped() {
+    // Standard UTF-8 emoji, a lone surrogate, a raw NUL and an overlong 'A'
+    let inputs: [&[u8]; 4] = [
+        "\u{1F600}".as_bytes(),
+        &[0xED, 0xA0, 0xBD, b'x'],
+        &[b'a', 0x00, b'b'],
+        &[0xC1, 0x81],
+    ];
+
+    for input in inputs {
+        let decoded = decode(input);
+        assert!(decoded.chars().any(|c| c as u32 >= ESCAPE_BASE));
+        assert_eq!(encode(&decoded).as_ref(), input);
+    }
+}
+
+#[test]
+fn test_round_trip_is_lossless_for_arbitrary_bytes() {
+    // Small LCG, enough to cover a wide mix of valid and broken sequences
+    let mut state: u32 = 0x1234_5678;
+    for _ in 0..2000 {
+        let len = (state % 24) as usize;
+        let mut bytes = Vec::with_capacity(len);
+        for _ in 0..len {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let b = (state >> 16) as u8;
+            // Bias towards lead and continuation bytes of multi-byte sequences
+            bytes.push(if state & 1 == 0 { b | 0x80 } else { b });
+        }
+
+        let decoded = decode_owned(bytes.clone());
+        assert_eq!(encode(&decoded).as_ref(), bytes.as_slice(), "bytes {:x?}", bytes);
+    }
+}
@@ -0,0 +1,273 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.1
+//
+// ## Changelog
+// - 1.0.0: Initial version, structural diff of two trees [mrmarkolinus:2026-10-16]
+// - 1.0.1: Lists with another element type are one change, list keys must be unique [mrmarkolinus:2026-10-16]
+
+//! Structural diff between two trees, e.g. a `level.dat` before and after a plugin ran.
+//!
+//! Changes are reported by NBT path in tree order. Tag names are ignored, only keys and
+//! values are compared. Lists whose element types differ are reported as one change. Lists
+//! and arrays are compared by position, unless a list key is configured: lists of compounds
+//! that all hold the key, with no value twice, are then matched by its value, and their
+//! elements are reported with a filter path such as `Entities[{UUID:[I;1,2,3,4]}]`.
+
+use crate::nbt_path::{child_path, element_path};
+use crate::nbt_tag::*;
+
+use indexmap::IndexMap;
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// Longest SNBT shown for a value in `Display`, longer values are cut.
+const DISPLAY_LIMIT: usize = 80;
+
+/// A difference between the old and the new tree.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NbtChange {
+    /// A key or element only present in the new tree.
+    Added { path: String, value: NbtTag },
+    /// A key or element only present in the old tree.
+    Removed { path: String, value: NbtTag },
+    /// A value that differs, with the same type in both trees.
+    Changed { path: String, old: NbtTag, new: NbtTag },
+    /// A value whose type differs, or a list whose element type differs.
+    TypeChanged { path: String, old: NbtTag, new: NbtTag },
+}
+
+impl NbtChange {
+    pub fn path(&self) -> &str {
+        match self {
+            NbtChange::Added { path, .. } | NbtChange::Removed { path, .. } => path,
+            NbtChange::Changed { path, .. } | NbtChange::TypeChanged { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for NbtChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NbtChange::Added { path, value } => write!(f, "+ {}: {}", display_path(path), short_snbt(value)),
+            NbtChange::Removed { path, value } => write!(f, "- {}: {}", display_path(path), short_snbt(value)),
+            NbtChange::Changed { path, old, new } => write!(f, "~ {}: {} -> {}", display_path(path), short_snbt(old), short_snbt(new)),
+            NbtChange::TypeChanged { path, old, new } => {
+                write!(f, "! {}: {:?} {} -> {:?} {}", display_path(path), old.ty(), short_snbt(old), new.ty(), short_snbt(new))
+            }
+        }
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() { "<root>" } else { path }
+}
+
+fn short_snbt(tag: &NbtTag) -> String {
+    let snbt = tag.to_snbt();
+    match snbt.char_indices().nth(DISPLAY_LIMIT) {
+        Some((end, _)) => format!("{}…", &snbt[..end]),
+        None => snbt,
+    }
+}
+
+/// Settings of `diff_with_options`.
+#[derive(Clone, Debug, Default)]
+pub struct DiffOptions {
+    float_tolerance: f64,
+    list_keys: Vec<String>,
+}
+
+impl DiffOptions {
+    /// Floats and doubles that differ by at most `tolerance` are equal.
+    pub fn with_float_tolerance(mut self, tolerance: f64) -> Self {
+        self.float_tolerance = tolerance;
+        self
+    }
+
+    /// Matches the elements of lists of compounds by the value of `key`, e.g. `UUID` for
+    /// entities. With several keys, the first one held by every element of both lists wins.
+    pub fn with_list_key(mut self, key: &str) -> Self {
+        self.list_keys.push(key.to_string());
+        self
+    }
+}
+
+/// Compares two trees with the default options: exact floats, lists by position.
+pub fn diff(old: &NbtTag, new: &NbtTag) -> Vec<NbtChange> {
+    diff_with_options(old, new, &DiffOptions::default())
+}
+
+pub fn diff_with_options(old: &NbtTag, new: &NbtTag, options: &DiffOptions) -> Vec<NbtChange> {
+    let mut changes = Vec::new();
+    Differ { options, changes: &mut changes }.tags("", old, new);
+    changes
+}
+
+/// Compares two root compounds, such as the chunks of two versions of a region.
+pub fn diff_compounds(old: &NbtTagCompound, new: &NbtTagCompound, options: &DiffOptions) -> Vec<NbtChange> {
+    let mut changes = Vec::new();
+    Differ { options, changes: &mut changes }.compounds("", old, new);
+    changes
+}
+
+/// Renders changes one per line, for people to read.
+pub fn report(changes: &[NbtChange]) -> String {
+    changes.iter().map(|change| format!("{}\n", change)).collect()
+}
+
+/// Index of each element of a list by the SNBT of its key value, in list order.
+type KeyIndex = IndexMap<String, usize>;
+
+/// Indexes the elements of a list by the value of `key`, unless an element is not a
+/// compound holding the key or two elements share a value.
+fn key_index(list: &NbtTagList, key: &str) -> Option<KeyIndex> {
+    let mut index = KeyIndex::with_capacity(list.values.len());
+    for (position, value) in list.values.iter().enumerate() {
+        let key_value = value.as_compound()?.get(key)?;
+        if index.insert(key_value.to_snbt(), position).is_some() {
+            return None;
+        }
+    }
+    Some(index)
+}
+
+struct Differ<'o> {
+    options: &'o DiffOptions,
+    changes: &'o mut Vec<NbtChange>,
+}
+
+impl<'o> Differ<'o> {
+    fn tags(&mut self, path: &str, old: &NbtTag, new: &NbtTag) {
+        if old.ty() != new.ty() {
+            self.changes.push(NbtChange::TypeChanged { path: path.to_string(), old: old.clone(), new: new.clone() });
+            return;
+        }
+
+        match (old, new) {
+            (NbtTag::Compound(a), NbtTag::Compound(b)) => self.compounds(path, a, b),
+            (NbtTag::List(a), NbtTag::List(b)) => self.lists(path, a, b),
+            (NbtTag::ByteArray(a), NbtTag::ByteArray(b)) => self.arrays(path, &a.values, &b.values),
+            (NbtTag::IntArray(a), NbtTag::IntArray(b)) => self.arrays(path, &a.values, &b.values),
+            (NbtTag::LongArray(a), NbtTag::LongArray(b)) => self.arrays(path, &a.values, &b.values),
+            _ => {
+                if !self.leaves_equal(old, new) {
+                    self.changes.push(NbtChange::Changed { path: path.to_string(), old: old.clone(), new: new.clone() });
+                }
+            }
+        }
+    }
+
+    fn compounds(&mut self, path: &str, old: &NbtTagCompound, new: &NbtTagCompound) {
+        for (key, value) in old.values.iter() {
+            match new.values.get(key) {
+                Some(new_value) => self.tags(&child_path(path, key), value, new_value),
+                None => self.changes.push(NbtChange::Removed { path: child_path(path, key), value: value.clone() }),
+            }
+        }
+
+        for (key, value) in new.values.iter() {
+            if !old.values.contains_key(key) {
+                self.changes.push(NbtChange::Added { path: child_path(path, key), value: value.clone() });
+            }
+        }
+    }
+
+    fn lists(&mut self, path: &str, old: &NbtTagList, new: &NbtTagList) {
+        if old.ty != new.ty {
+            self.changes.push(NbtChange::TypeChanged {
+                path: path.to_string(),
+                old: NbtTag::List(old.clone()),
+                new: NbtTag::List(new.clone()),
+            });
+            return;
+        }
+        if let Some((key, old_keys, new_keys)) = self.list_key(old, new) {
+            return self.keyed_lists(path, key, old, new, &old_keys, &new_keys);
+        }
+
+        for (index, (a, b)) in old.values.iter().zip(new.values.iter()).enumerate() {
+            self.tags(&element_path(path, index), a, b);
+        }
+        for (index, value) in old.values.iter().enumerate().skip(new.values.len()) {
+            self.changes.push(NbtChange::Removed { path: element_path(path, index), value: value.clone() });
+        }
+        for (index, value) in new.values.iter().enumerate().skip(old.values.len()) {
+            self.changes.push(NbtChange::Added { path: element_path(path, index), value: value.clone() });
+        }
+    }
+
+    /// First configured key held by every element of both lists with unique values, along
+    /// with the index of each key value in both lists.
+    fn list_key(&self, old: &NbtTagList, new: &NbtTagList) -> Option<(&'o str, KeyIndex, KeyIndex)> {
+        if old.values.is_empty() && new.values.is_empty() {
+            return None;
+        }
+
+        let options: &'o DiffOptions = self.options;
+        options.list_keys.iter().find_map(|key| Some((key.as_str(), key_index(old, key)?, key_index(new, key)?)))
+    }
+
+    fn keyed_lists(&mut self, path: &str, key: &str, old: &NbtTagList, new: &NbtTagList, old_keys: &KeyIndex, new_keys: &KeyIndex) {
+        let keyed_path = |value: &NbtTag| {
+            let mut filter = NbtTagCompound::new("");
+            if let Some(key_value) = value.as_compound().and_then(|c| c.get(key)) {
+                filter.insert(key, key_value.clone());
+            }
+            format!("{}[{}]", path, filter.to_snbt())
+        };
+
+        for (key_value, &index) in old_keys.iter() {
+            let a = &old.values[index];
+            match new_keys.get(key_value) {
+                Some(&new_index) => self.tags(&keyed_path(a), a, &new.values[new_index]),
+                None => self.changes.push(NbtChange::Removed { path: keyed_path(a), value: a.clone() }),
+            }
+        }
+
+        for (key_value, &index) in new_keys.iter() {
+            if !old_keys.contains_key(key_value) {
+                let b = &new.values[index];
+                self.changes.push(NbtChange::Added { path: keyed_path(b), value: b.clone() });
+            }
+        }
+    }
+
+    fn arrays<T: Copy + PartialEq + Into<NbtTag>>(&mut self, path: &str, old: &[T], new: &[T]) {
+        for (index, (a, b)) in old.iter().zip(new.iter()).enumerate() {
+            if a != b {
+                self.changes.push(NbtChange::Changed { path: element_path(path, index), old: (*a).into(), new: (*b).into() });
+            }
+        }
+        for (index, value) in old.iter().enumerate().skip(new.len()) {
+            self.changes.push(NbtChange::Removed { path: element_path(path, index), value: (*value).into() });
+        }
+        for (index, value) in new.iter().enumerate().skip(old.len()) {
+            self.changes.push(NbtChange::Added { path: element_path(path, index), value: (*value).into() });
+        }
+    }
+
+    /// Compares two scalars of the same type, floats within the tolerance.
+    fn leaves_equal(&self, old: &NbtTag, new: &NbtTag) -> bool {
+        let floats_equal = |a: f64, b: f64| a == b || (a - b).abs() <= self.options.float_tolerance || (a.is_nan() && b.is_nan());
+
+        match (old, new) {
+            (NbtTag::Byte(a), NbtTag::Byte(b)) => a.value == b.value,
+            (NbtTag::Short(a), NbtTag::Short(b)) => a.value == b.value,
+            (NbtTag::Int(a), NbtTag::Int(b)) => a.value == b.value,
+            (NbtTag::Long(a), NbtTag::Long(b)) => a.value == b.value,
+            (NbtTag::Float(a), NbtTag::Float(b)) => floats_equal(a.value as f64, b.value as f64),
+            (NbtTag::Double(a), NbtTag::Double(b)) => floats_equal(a.value, b.value),
+            (NbtTag::String(a), NbtTag::String(b)) => a.value == b.value,
+            (NbtTag::End, NbtTag::End) => true,
+            _ => unreachable!(), // Containers are compared by tags
+        }
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+
+use super::*;
+use crate::nbt;
+
+#[test]
+fn test_identical_trees() {
+    let tag = NbtTag::Compound(nbt!{ "Health": 20.0f, "Pos": [0.5, 64.0, 0.5] });
+    let mut renamed = tag.clone();
+    renamed.as_compound_mut().unwrap().name = "other".to_string();
+
+    assert!(diff(&tag, &renamed).is_empty());
+}
+
+#[test]
+fn test_added_removed_changed() {
+    let old = NbtTag::Compound(nbt!{
+        "XpLevel": 3,
+        "Inventory": [{ "Slot": 0i8, "Count": 1b }],
+        "Heights": [L; 1L, 2L],
+    });
+    let mut new = old.clone();
+    let compound = new.as_compound_mut().unwrap();
+    compound.set_at("XpLevel", NbtTag::from(4)).unwrap();
+    compound.set_at("Inventory[0].Count", NbtTag::from_snbt("2b").unwrap()).unwrap();
+    compound.remove("Heights");
+    compound.set_at("Score", NbtTag::from(10)).unwrap();
+
+    let changes = diff(&old, &new);
+
+    assert_eq!(changes, vec![
+        NbtChange::Changed { path: "XpLevel".to_string(), old: NbtTag::Int(NbtTagInt::new(3)), new: NbtTag::Int(NbtTagInt::new(4)) },
+        NbtChange::Changed { path: "Inventory[0].Count".to_string(), old: NbtTag::Byte(NbtTagByte::new(1)), new: NbtTag::Byte(NbtTagByte::new(2)) },
+        NbtChange::Removed { path: "Heights".to_string(), value: NbtTag::LongArray(NbtTagLongArray::new(vec![1, 2])) },
+        NbtChange::Added { path: "Score".to_string(), value: NbtTag::Int(NbtTagInt::new(10)) },
+    ]);
+    assert_eq!(report(&changes), "~ XpLevel: 3 -> 4\n~ Inventory[0].Count: 1b -> 2b\n- Heights: [L;1L,2L]\n+ Score: 10\n");
+}
+
+#[test]
+fn test_type_changes_and_arrays() {
+    let old = NbtTag::Compound(nbt!{ "a": 1, "b": [I; 1, 2, 3] });
+    let new = NbtTag::Compound(nbt!{ "a": 1L, "b": [I; 1, 5] });
+
+    let lines: Vec<_> = diff(&old, &new).iter().map(ToString::to_string).collect();
+
+    assert_eq!(lines, vec!["! a: Int 1 -> Long 1L", "~ b[1]: 2 -> 5", "- b[2]: 3"]);
+}
+
+#[test]
+fn test_float_tolerance() {
+    let old = NbtTag::Compound(nbt!{ "Pos": [0.5, 64.0, 0.5] });
+    let mut new = old.clone();
+    new.as_compound_mut().unwrap().set_at("Pos[1]", NbtTag::from(64.0000001)).unwrap();
+
+    assert_eq!(diff(&old, &new).len(), 1);
+    assert!(diff_with_options(&old, &new, &DiffOptions::default().with_float_tolerance(1e-6)).is_empty());
+}
+
+#[test]
+fn test_lists_matched_by_key() {
+    let old = NbtTag::Compound(nbt!{ "Entities": [{ "UUID": [I; 1, 1, 1, 1], "Health": 5.0f }, { "UUID": [I; 2, 2, 2, 2], "Health": 8.0f }] });
+    let new = NbtTag::Compound(nbt!{ "Entities": [{ "UUID": [I; 2, 2, 2, 2], "Health": 6.0f }, { "UUID": [I; 3, 3, 3, 3], "Health": 1.0f }] });
+
+    // By position, both UUIDs and healths differ
+    assert_eq!(diff(&old, &new).len(), 10);
+
+    let options = DiffOptions::default().with_list_key("id").with_list_key("UUID");
+    let lines: Vec<_> = diff_with_options(&old, &new, &options).iter().map(ToString::to_string).collect();
+    assert_eq!(lines, vec![
+        "- Entities[{UUID:[I;1,1,1,1]}]: {UUID:[I;1,1,1,1],Health:5.0f}",
+        "~ Entities[{UUID:[I;2,2,2,2]}].Health: 8.0f -> 6.0f",
+        "+ Entities[{UUID:[I;3,3,3,3]}]: {UUID:[I;3,3,3,3],Health:1.0f}",
+    ]);
+}
+
+#[test]
+fn test_lists_with_duplicate_keys_are_matched_by_position() {
+    let old = NbtTag::Compound(nbt!{ "E": [{ "U": 1, "h": 1 }, { "U": 1, "h": 2 }] });
+    let new = NbtTag::Compound(nbt!{ "E": [{ "U": 1, "h": 1 }, { "U": 1, "h": 3 }] });
+
+    let lines: Vec<_> = diff_with_options(&old, &new, &DiffOptions::default().with_list_key("U")).iter().map(ToString::to_string).collect();
+
+    assert_eq!(lines, vec!["~ E[1].h: 2 -> 3"]);
+}
+
+#[test]
+fn test_list_element_type_changes() {
+    let old = NbtTag::Compound(nbt!{ "l": [1, 2], "e": (NbtTagList::new(NbtTagType::End, vec![])) });
+    let new = NbtTag::Compound(nbt!{ "l": ["a", "b"], "e": (NbtTagList::new(NbtTagType::Int, vec![])) });
+
+    let lines: Vec<_> = diff(&old, &new).iter().map(ToString::to_string).collect();
+
+    assert_eq!(lines, vec!["! l: List [1,2] -> List [\"a\",\"b\"]", "! e: List [] -> List []"]);
+}
+
+#[test]
+fn test_long_values_are_cut() {
+    let old = NbtTag::Compound(nbt!{ "data": (vec![0i64; 100]) });
+    let new = NbtTag::Compound(nbt!{ "data": "x" });
+
+    let line = diff(&old, &new)[0].to_string();
+    assert!(line.starts_with("! data: LongArray [L;0L,0L"));
+    assert!(line.contains("…"));
+}
@@ -0,0 +1,286 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.1
+//
+// ## Changelog
+// - 1.0.0: Initial version, invertible patches of NBT path operations [mrmarkolinus:2026-10-16]
+// - 1.0.1: Values are written in the typed JSON schema, SNBT is still read [mrmarkolinus:2026-10-16]
+
+//! Declarative edits, in the spirit of JSON Patch (RFC 6902) with NBT paths.
+//!
+//! A patch is a list of operations applied in order:
+//!
+//! ```json
+//! [
+//!     {"op": "test", "path": "DataVersion", "value": "3465"},
+//!     {"op": "set", "path": "Attributes[{Name:\"custom:luck\"}].Base", "value": "0.0d"},
+//!     {"op": "insert", "path": "Tags", "index": -1, "value": "\"vip\""},
+//!     {"op": "merge", "path": "", "value": "{abilities:{mayfly:1b}}"},
+//!     {"op": "remove", "path": "Inventory[{id:\"minecraft:bedrock\"}]"}
+//! ]
+//! ```
+//!
+//! Values are SNBT strings, convenient to write by hand, or tags in the typed JSON schema of
+//! `json`, e.g. `{"type": "float", "value": "NaN"}`. `to_json` writes the typed schema,
+//! which keeps every value exactly; SNBT has no syntax for non-finite floats.
+//!
+//! `set`, `insert` and `remove` behave like `NbtPath::set`, `insert` and `remove`, `merge`
+//! merges into every compound selected by the path (the root for an empty path) like
+//! `NbtTagCompound::merge`, and `test` fails the patch unless a tag at the path contains
+//! the value.
+//!
+//! Applying a patch returns its inverse, the patch that restores the previous content,
+//! built from the values that were actually replaced or removed. If an operation fails,
+//! the operations already applied are undone before the error is returned. Keys removed and
+//! restored by an inverse move to the end of their compound.
+
+use crate::error::{NbtError, NbtErrorKind};
+use crate::nbt_diff::NbtChange;
+use crate::nbt_path::{self, child_path, EditRecord, NbtPath};
+use crate::nbt_tag::*;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// One operation of a patch. Values are written in the typed JSON schema.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    Set {
+        path: String,
+        #[serde(with = "json_tag")]
+        value: NbtTag,
+    },
+    Insert {
+        path: String,
+        index: i32,
+        #[serde(with = "json_tag")]
+        value: NbtTag,
+    },
+    Remove {
+        path: String,
+    },
+    Merge {
+        path: String,
+        #[serde(with = "json_compound")]
+        value: NbtTagCompound,
+    },
+    Test {
+        path: String,
+        #[serde(with = "json_tag")]
+        value: NbtTag,
+    },
+}
+
+/// A list of operations, see the module documentation.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NbtPatch {
+    pub ops: Vec<PatchOp>,
+}
+
+impl NbtPatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_op(mut self, op: PatchOp) -> Self {
+        self.ops.push(op);
+        self
+    }
+
+    /// Builds the patch that turns the old tree of a diff into the new one. Elements added
+    /// to lists matched by key are appended.
+    pub fn from_diff(changes: &[NbtChange]) -> Result<Self, NbtError> {
+        let mut ops = Vec::new();
+        // Trailing elements removed from a list are listed first to last, they are removed
+        // last to first so the indices stay valid
+        let mut removals: Vec<PatchOp> = Vec::new();
+        let mut removals_parent = None;
+
+        for change in changes {
+            if change.path().is_empty() {
+                return Err(NbtError::new(NbtErrorKind::InvalidPath("the root cannot be patched".to_string()), 0));
+            }
+
+            if let NbtChange::Removed { path, .. } = change {
+                let parent = split_index(path).map(|(parent, _)| parent);
+                if parent.is_none() || parent != removals_parent {
+                    ops.extend(removals.drain(..).rev());
+                }
+                removals_parent = parent;
+                removals.push(PatchOp::Remove { path: path.clone() });
+                continue;
+            }
+
+            ops.extend(removals.drain(..).rev());
+            removals_parent = None;
+            ops.push(match change {
+                NbtChange::Added { path, value } => match split_index(path) {
+                    Some((parent, index)) => PatchOp::Insert { path: parent.to_string(), index: index as i32, value: value.clone() },
+                    None if path.ends_with("}]") => {
+                        let parent = &path[..path.rfind("[{").unwrap_or(0)];
+                        PatchOp::Insert { path: parent.to_string(), index: -1, value: value.clone() }
+                    }
+                    None => PatchOp::Set { path: path.clone(), value: value.clone() },
+                },
+                NbtChange::Changed { path, new, .. } | NbtChange::TypeChanged { path, new, .. } => PatchOp::Set { path: path.clone(), value: new.clone() },
+                NbtChange::Removed { .. } => unreachable!(), // Handled above
+            });
+        }
+        ops.extend(removals.drain(..).rev());
+
+        Ok(NbtPatch { ops })
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, NbtError> {
+        serde_json::from_str(text).map_err(|e| NbtError::new(NbtErrorKind::InvalidJson(e.to_string()), 0))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("patches always serialize")
+    }
+
+    /// Applies the operations in order and returns the inverse patch. On error, `root` is
+    /// restored before the error is returned.
+    pub fn apply(&self, root: &mut NbtTagCompound) -> Result<NbtPatch, NbtError> {
+        let mut undo: Vec<Vec<PatchOp>> = Vec::new();
+
+        for op in self.ops.iter() {
+            let mut record = EditRecord::default();
+            let result = apply_op(op, root, &mut record);
+
+            // A failed operation may have edited part of the tree before failing
+            undo.push(inverse_ops(op, &record));
+            if let Err(e) = result {
+                for op in undo.into_iter().rev().flatten() {
+                    // Restores values that were just there, this does not fail
+                    let _ = apply_op(&op, root, &mut EditRecord::default());
+                }
+                return Err(e);
+            }
+        }
+
+        Ok(NbtPatch { ops: undo.into_iter().rev().flatten().collect() })
+    }
+}
+
+fn apply_op(op: &PatchOp, root: &mut NbtTagCompound, record: &mut EditRecord) -> Result<(), NbtError> {
+    match op {
+        PatchOp::Set { path, value } => {
+            let path = NbtPath::parse(path)?;
+            nbt_path::with_compound_root(root, |root| path.set_recorded(root, value.clone(), Some(record))).map(|_| ())
+        }
+        PatchOp::Insert { path, index, value } => {
+            let path = NbtPath::parse(path)?;
+            nbt_path::with_compound_root(root, |root| path.insert_recorded(root, *index, value.clone(), Some(record))).map(|_| ())
+        }
+        PatchOp::Remove { path } => {
+            let path = NbtPath::parse(path)?;
+            nbt_path::with_compound_root(root, |root| path.remove_recorded(root, Some(record))).map(|_| ())
+        }
+        PatchOp::Merge { path, value } if path.is_empty() => {
+            merge_recorded(root, "", value, record);
+            Ok(())
+        }
+        PatchOp::Merge { path, value } => {
+            let path = NbtPath::parse(path)?;
+            nbt_path::with_compound_root(root, |root| {
+                path.for_each_mut(root, &mut |path, target| {
+                    if let Some(target) = target.as_compound_mut() {
+                        merge_recorded(target, path, value, record);
+                    }
+                    Ok(())
+                })
+            })
+        }
+        PatchOp::Test { path, value } => {
+            let parsed = NbtPath::parse(path)?;
+            let found = nbt_path::with_compound_root(root, |root| parsed.query(root).iter().any(|m| nbt_path::contains(&m.tag, value)));
+            if !found {
+                return Err(NbtError::new(NbtErrorKind::PatchTestFailed(path.clone()), 0));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Merges `value` into `target`, found at `path`, recording the keys it replaces or adds.
+fn merge_recorded(target: &mut NbtTagCompound, path: &str, value: &NbtTagCompound, record: &mut EditRecord) {
+    for key in value.values.keys() {
+        match target.values.get(key) {
+            Some(previous) => record.previous.push((child_path(path, key), previous.clone())),
+            None => record.created.push(child_path(path, key)),
+        }
+    }
+    target.merge(value);
+}
+
+/// Operations that undo `op`: created tags are removed and previous values restored.
+/// Elements removed from a list are inserted back first to last, which restores their
+/// indices; elements that were replaced are set back.
+fn inverse_ops(op: &PatchOp, record: &EditRecord) -> Vec<PatchOp> {
+    let mut ops: Vec<PatchOp> = record.created.iter().rev().map(|path| PatchOp::Remove { path: path.clone() }).collect();
+
+    for (path, previous) in record.previous.iter() {
+        ops.push(match (op, split_index(path)) {
+            (PatchOp::Remove { .. }, Some((parent, index))) => PatchOp::Insert { path: parent.to_string(), index: index as i32, value: previous.clone() },
+            _ => PatchOp::Set { path: path.clone(), value: previous.clone() },
+        });
+    }
+
+    ops
+}
+
+/// Splits a path ending with an element index, `Items[3]`, into `Items` and 3.
+fn split_index(path: &str) -> Option<(&str, usize)> {
+    let open = path.strip_suffix(']')?.rfind('[')?;
+    let index = path[open + 1..path.len() - 1].parse().ok()?;
+    Some((&path[..open], index))
+}
+
+/// Values of operations are written as typed JSON tags, and read from typed tags or SNBT.
+mod json_tag {
+    use crate::json::{self, TypedTag};
+    use crate::nbt_tag::NbtTag;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+
+    pub fn serialize<S: Serializer>(tag: &NbtTag, serializer: S) -> Result<S::Ok, S::Error> {
+        TypedTag(tag).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NbtTag, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::String(text) => NbtTag::from_snbt(&text).map_err(D::Error::custom),
+            value => json::typed_tag(&value).map_err(D::Error::custom),
+        }
+    }
+}
+
+mod json_compound {
+    use crate::json::TypedCompound;
+    use crate::nbt_tag::{NbtTag, NbtTagCompound};
+    use serde::{de::Error, ser::SerializeMap, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(compound: &NbtTagCompound, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("type", "compound")?;
+        map.serialize_entry("value", &TypedCompound(compound))?;
+        map.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NbtTagCompound, D::Error> {
+        match super::json_tag::deserialize(deserializer)? {
+            NbtTag::Compound(compound) => Ok(compound),
+            tag => Err(D::Error::custom(format!("expected a compound, found {:?}", tag.ty()))),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+
+use super::*;
+use crate::nbt;
+use crate::nbt_diff::{self, DiffOptions};
+
+fn compound(snbt: &str) -> NbtTagCompound {
+    NbtTag::from_snbt(snbt).unwrap().into_compound().unwrap()
+}
+
+fn set(path: &str, snbt: &str) -> PatchOp {
+    PatchOp::Set { path: path.to_string(), value: NbtTag::from_snbt(snbt).unwrap() }
+}
+
+fn test(path: &str, snbt: &str) -> PatchOp {
+    PatchOp::Test { path: path.to_string(), value: NbtTag::from_snbt(snbt).unwrap() }
+}
+
+#[test]
+fn test_apply_and_invert() {
+    let patch = NbtPatch::new()
+        .with_op(test("DataVersion", "3465"))
+        .with_op(set("Health", "10.0f"))
+        .with_op(set("display.Name", "\"Steve\""))
+        .with_op(PatchOp::Insert { path: "Tags".to_string(), index: 1, value: NbtTag::from("x") })
+        .with_op(PatchOp::Remove { path: "Inventory[{id:\"minecraft:stone\"}]".to_string() })
+        .with_op(PatchOp::Merge { path: "abilities".to_string(), value: nbt!{ "mayfly": 1b, "walkSpeed": 0.1f } });
+
+    let original = compound(concat!(
+        "{DataVersion:3465,Health:20.0f,Tags:[\"a\",\"b\",\"c\"],",
+        "Inventory:[{Slot:0b,id:\"minecraft:stone\"},{Slot:1b,id:\"minecraft:dirt\"}],abilities:{flying:0b,mayfly:0b}}"
+    ));
+    let mut patched = original.clone();
+    let inverse = patch.apply(&mut patched).unwrap();
+
+    assert_eq!(patched.to_snbt(), concat!(
+        "{DataVersion:3465,Health:10.0f,Tags:[\"a\",\"x\",\"b\",\"c\"],Inventory:[{Slot:1b,id:\"minecraft:dirt\"}],",
+        "abilities:{flying:0b,mayfly:1b,walkSpeed:0.1f},display:{Name:\"Steve\"}}"
+    ));
+
+    inverse.apply(&mut patched).unwrap();
+    assert_eq!(patched.to_snbt(), original.to_snbt());
+}
+
+#[test]
+fn test_failed_patch_is_rolled_back() {
+    let patch = NbtPatch::new()
+        .with_op(set("Health", "1.0f"))
+        .with_op(PatchOp::Remove { path: "Tags[]".to_string() })
+        .with_op(test("DataVersion", "1"));
+
+    let original = compound("{DataVersion:3465,Health:20.0f,Tags:[\"a\",\"b\"]}");
+    let mut patched = original.clone();
+    let error = patch.apply(&mut patched).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::PatchTestFailed(ref path) if path == "DataVersion"));
+    assert_eq!(patched.to_snbt(), original.to_snbt());
+}
+
+#[test]
+fn test_rollback_restores_the_type_of_empty_lists() {
+    let patch = NbtPatch::new()
+        .with_op(PatchOp::Insert { path: "Tags".to_string(), index: -1, value: NbtTag::from("vip") })
+        .with_op(test("x", "2"));
+
+    let original = compound("{Tags:[],x:1}");
+    let mut patched = original.clone();
+    assert!(patch.apply(&mut patched).is_err());
+
+    assert_eq!(patched, original);
+    assert_eq!(patched.get("Tags").and_then(NbtTag::as_list).unwrap().ty, NbtTagType::End);
+}
+
+#[test]
+fn test_from_diff() {
+    let old = nbt!{
+        "Health": 20.0f,
+        "Tags": ["a", "b", "c"],
+        "Inventory": [{ "Slot": 0i8, "Count": 1b }, { "Slot": 1b, "Count": 5b }],
+        "abilities": { "flying": 0i8 },
+    };
+    let mut new = old.clone();
+    new.set_at("Health", NbtTag::from(5.0f32)).unwrap();
+    new.set_at("Inventory[1].Count", NbtTag::from(6i8)).unwrap();
+    new.remove_at("Tags[1]").unwrap();
+    new.remove_at("Tags[1]").unwrap();
+    new.remove("abilities");
+    new.set_at("Score", NbtTag::from(7)).unwrap();
+
+    let changes = nbt_diff::diff_compounds(&old, &new, &DiffOptions::default());
+    let patch = NbtPatch::from_diff(&changes).unwrap();
+
+    let mut patched = old.clone();
+    patch.apply(&mut patched).unwrap();
+    assert!(nbt_diff::diff_compounds(&patched, &new, &DiffOptions::default()).is_empty());
+}
+
+#[test]
+fn test_from_diff_of_retyped_lists() {
+    let old = nbt!{ "l": [1, 2], "e": (NbtTagList::new(NbtTagType::End, vec![])) };
+    let new = nbt!{ "l": ["a", "b"], "e": (NbtTagList::new(NbtTagType::Int, vec![])) };
+
+    let patch = NbtPatch::from_diff(&nbt_diff::diff_compounds(&old, &new, &DiffOptions::default())).unwrap();
+
+    let mut patched = old.clone();
+    patch.apply(&mut patched).unwrap();
+    assert_eq!(patched, new);
+}
+
+#[test]
+fn test_from_keyed_diff() {
+    let old = nbt!{ "Entities": [{ "UUID": [I; 1, 1, 1, 1], "Health": 5.0f }, { "UUID": [I; 2, 2, 2, 2], "Health": 8.0f }] };
+    let new = nbt!{ "Entities": [{ "UUID": [I; 2, 2, 2, 2], "Health": 6.0f }, { "UUID": [I; 3, 3, 3, 3], "Health": 1.0f }] };
+    let options = DiffOptions::default().with_list_key("UUID");
+
+    let patch = NbtPatch::from_diff(&nbt_diff::diff_compounds(&old, &new, &options)).unwrap();
+
+    let mut patched = old.clone();
+    patch.apply(&mut patched).unwrap();
+    assert_eq!(patched.to_snbt(), new.to_snbt());
+}
+
+#[test]
+fn test_json_round_trip() {
+    let json = r#"[
+        {"op": "test", "path": "DataVersion", "value": "3465"},
+        {"op": "insert", "path": "Tags", "index": -1, "value": "\"vip\""},
+        {"op": "merge", "path": "", "value": "{abilities:{mayfly:1b}}"},
+        {"op": "remove", "path": "Inventory[0]"}
+    ]"#;
+
+    let patch = NbtPatch::from_json(json).unwrap();
+    assert_eq!(patch.ops.len(), 4);
+    assert_eq!(patch.ops[1], PatchOp::Insert { path: "Tags".to_string(), index: -1, value: NbtTag::from("vip") });
+    assert_eq!(NbtPatch::from_json(&patch.to_json()).unwrap(), patch);
+
+    let mut patched = compound("{DataVersion:3465,Tags:[\"a\"],Inventory:[{Slot:0b}],abilities:{flying:0b}}");
+    patch.apply(&mut patched).unwrap();
+    assert_eq!(patched.get("abilities").unwrap().as_compound().unwrap().to_snbt(), "{flying:0b,mayfly:1b}");
+
+    assert!(matches!(NbtPatch::from_json(r#"[{"op": "merge", "path": "", "value": "1"}]"#).unwrap_err().kind, NbtErrorKind::InvalidJson(_)));
+}
+
+#[test]
+fn test_json_keeps_value_types() {
+    let patch = NbtPatch::new()
+        .with_op(PatchOp::Set { path: "f".to_string(), value: NbtTag::from(f32::NAN) })
+        .with_op(PatchOp::Set { path: "d".to_string(), value: NbtTag::from(f64::INFINITY) })
+        .with_op(PatchOp::Merge { path: "".to_string(), value: nbt!{ "s": "NaNf" } });
+
+    let json = patch.to_json();
+    assert!(json.contains(r#""type": "float""#));
+
+    let imported = NbtPatch::from_json(&json).unwrap();
+    assert!(matches!(&imported.ops[0], PatchOp::Set { value: NbtTag::Float(f), .. } if f.value.is_nan()));
+    assert_eq!(imported.ops[1..], patch.ops[1..]);
+}
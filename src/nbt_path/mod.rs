@@ -0,0 +1,780 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.6
+//
+// ## Changelog
+// - 1.0.0: Initial version, NBT path parser and query engine [mrmarkolinus:2026-10-16]
+// - 1.0.1: set and remove by path [mrmarkolinus:2026-10-16]
+// - 1.0.2: Path rendering helpers shared with nbt_diff [mrmarkolinus:2026-10-16]
+// - 1.0.3: insert, edits can record what they change [mrmarkolinus:2026-10-16]
+// - 1.0.4: Tags are not renamed when they are moved, NbtMatch::key [mrmarkolinus:2026-10-16]
+// - 1.0.5: Uses the as_* accessors [mrmarkolinus:2026-10-16]
+// - 1.0.6: Inserting into an empty list of another type records the previous list [mrmarkolinus:2026-10-16]
+
+//! NBT paths, the syntax used by the `/data` command to point at tags inside a tree, e.g.
+//! `sections[].block_states.palette[{Name:"minecraft:chest"}].Properties.facing`.
+//!
+//! A path is a sequence of nodes, separated by dots before keys:
+//!
+//! - `key` or `"quoted key"`: the child of a compound
+//! - `key{Name:"x"}`: the child, if it is a compound that contains the filter
+//! - `{Name:"x"}`, only as the first node: the root itself, if it contains the filter
+//! - `[3]`, `[-1]`: an element of a list or array, negative indices count from the end
+//! - `[]`: every element of a list or array
+//! - `[{Name:"x"}]`: every compound element of a list that contains the filter
+//!
+//! A filter is an SNBT compound. A tag contains it when every key of the filter is present
+//! with an equal value; nested compounds are compared the same way, and a list in the
+//! filter matches when each of its elements is contained in some element of the list.
+//!
+//! `set`, `insert` and `remove` edit every tag selected by a path, like `/data modify … set`,
+//! `/data modify … insert` and `/data remove`. While setting, missing compounds along the path are created, so
+//! `set_at("display.Name", …)` works on an item without a `display` compound.
+
+use crate::error::{quote_key, NbtError, NbtErrorKind};
+use crate::nbt_tag::*;
+use crate::snbt;
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Key(String),
+    KeyFilter(String, NbtTagCompound),
+    RootFilter(NbtTagCompound),
+    Index(i32),
+    AllElements,
+    ElementFilter(NbtTagCompound),
+}
+
+/// A parsed NBT path, reusable across trees.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NbtPath {
+    nodes: Vec<Node>,
+}
+
+/// A tag selected by a query, with its concrete path, e.g. `sections[3].block_states`.
+///
+/// Tags are borrowed from the queried tree. The only exception are elements of byte, int
+/// and long arrays, which are not tags in the tree and are returned as owned tags.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NbtMatch<'a> {
+    pub path: String,
+    pub tag: Cow<'a, NbtTag>,
+}
+
+impl NbtMatch<'_> {
+    /// The key of the tag in its compound, read from the path. Empty for list and array
+    /// elements and for the root, which have no key.
+    pub fn key(&self) -> String {
+        match NbtPath::parse(&self.path).ok().and_then(|path| path.nodes.into_iter().last()) {
+            Some(Node::Key(key)) | Some(Node::KeyFilter(key, _)) => key,
+            _ => String::new(),
+        }
+    }
+}
+
+impl NbtPath {
+    /// Parses a path. Errors report the byte offset in `path`.
+    pub fn parse(path: &str) -> Result<Self, NbtError> {
+        PathParser { text: path, pos: 0 }.parse()
+    }
+
+    /// Returns every tag under `root` selected by the path, in tree order.
+    pub fn query<'a>(&self, root: &'a NbtTag) -> Vec<NbtMatch<'a>> {
+        run(&self.nodes, vec![NbtMatch { path: String::new(), tag: Cow::Borrowed(root) }])
+    }
+
+    /// Like `query`, for a root compound that is not wrapped in an `NbtTag`, such as the
+    /// chunks of a region file. A path made of a root filter only returns a copy of `root`.
+    pub fn query_compound<'a>(&self, root: &'a NbtTagCompound) -> Vec<NbtMatch<'a>> {
+        let mut nodes = &self.nodes[..];
+        if let Some(Node::RootFilter(filter)) = nodes.first() {
+            if !compound_contains(root, filter) {
+                return Vec::new();
+            }
+            nodes = &nodes[1..];
+        }
+
+        let Some((first, rest)) = nodes.split_first() else {
+            return vec![NbtMatch { path: String::new(), tag: Cow::Owned(NbtTag::Compound(root.clone())) }];
+        };
+
+        // Index nodes select nothing in a compound, only a key can come first
+        let mut matches = Vec::new();
+        apply_key(first, "", root, &mut matches);
+        run(rest, matches)
+    }
+}
+
+/// What an edit changed, with concrete paths, so that it can be undone.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EditRecord {
+    /// Tags that were replaced or removed, with their previous value.
+    pub(crate) previous: Vec<(String, NbtTag)>,
+    /// Tags that did not exist before, created compounds and inserted keys or elements.
+    /// Tags created inside a created compound are not listed.
+    pub(crate) created: Vec<String>,
+}
+
+impl EditRecord {
+    /// Drops the created paths that lie inside another created path.
+    fn drop_nested_creations(&mut self) {
+        let created = std::mem::take(&mut self.created);
+        for path in created.iter() {
+            let nested = created.iter().any(|outer| {
+                outer != path && path.starts_with(outer.as_str()) && matches!(path.as_bytes()[outer.len()], b'.' | b'[')
+            });
+            if !nested {
+                self.created.push(path.clone());
+            }
+        }
+    }
+}
+
+impl NbtPath {
+    /// Replaces every tag selected by the path with a copy of `tag`, or inserts it when the
+    /// last node is a missing key. Compounds missing along the path are created. Returns the
+    /// number of tags set.
+    ///
+    /// List elements can only be replaced by a tag of the list type, array elements by a
+    /// byte, int or long matching the array.
+    pub fn set(&self, root: &mut NbtTag, tag: NbtTag) -> Result<usize, NbtError> {
+        self.set_recorded(root, tag, None)
+    }
+
+    pub(crate) fn set_recorded(&self, root: &mut NbtTag, tag: NbtTag, mut record: Option<&mut EditRecord>) -> Result<usize, NbtError> {
+        if let NbtTag::End = tag {
+            return Err(NbtError::new(NbtErrorKind::EndTagValue, 0));
+        }
+
+        let (last, parents) = self.split_last()?;
+        let mut count = 0;
+        let mut created = Vec::new();
+        let result = visit_mut(parents, root, "", Some(last), &mut created, &mut |path, parent| {
+            if let Some(record) = record.as_deref_mut() {
+                let start = record.previous.len();
+                record_children(last, path, parent, record);
+                if let (Node::Key(key), NbtTag::Compound(_), true) = (last, &*parent, record.previous.len() == start) {
+                    record.created.push(child_path(path, key));
+                }
+            }
+            count += set_child(last, parent, &tag)?;
+            Ok(())
+        });
+
+        if let Some(record) = record {
+            record.created.append(&mut created);
+            record.drop_nested_creations();
+        }
+        result.map(|_| count)
+    }
+
+    /// Removes every tag selected by the path and returns how many were removed. Compound
+    /// keys are removed with `shift_remove`, so the remaining keys keep their order.
+    pub fn remove(&self, root: &mut NbtTag) -> Result<usize, NbtError> {
+        self.remove_recorded(root, None)
+    }
+
+    pub(crate) fn remove_recorded(&self, root: &mut NbtTag, mut record: Option<&mut EditRecord>) -> Result<usize, NbtError> {
+        let (last, parents) = self.split_last()?;
+        let mut count = 0;
+        visit_mut(parents, root, "", None, &mut Vec::new(), &mut |path, parent| {
+            if let Some(record) = record.as_deref_mut() {
+                record_children(last, path, parent, record);
+            }
+            count += remove_child(last, parent);
+            Ok(())
+        })?;
+
+        Ok(count)
+    }
+
+    /// Inserts a copy of `tag` at `index` in every list or array selected by the path, like
+    /// `/data modify … insert`. A negative index counts from the end, -1 appends. Lists and
+    /// arrays where the index is out of range are skipped. Returns the number of insertions.
+    pub fn insert(&self, root: &mut NbtTag, index: i32, tag: NbtTag) -> Result<usize, NbtError> {
+        self.insert_recorded(root, index, tag, None)
+    }
+
+    pub(crate) fn insert_recorded(&self, root: &mut NbtTag, index: i32, tag: NbtTag, mut record: Option<&mut EditRecord>) -> Result<usize, NbtError> {
+        if let NbtTag::End = tag {
+            return Err(NbtError::new(NbtErrorKind::EndTagValue, 0));
+        }
+
+        let mut count = 0;
+        self.for_each_mut(root, &mut |path, target| {
+            let len = element_count(target) as i64;
+            let position = if index < 0 { len + index as i64 + 1 } else { index as i64 };
+            let sequence = matches!(target, NbtTag::List(_) | NbtTag::ByteArray(_) | NbtTag::IntArray(_) | NbtTag::LongArray(_));
+            if !sequence || !(0..=len).contains(&position) {
+                return Ok(());
+            }
+
+            // An empty list that takes the type of the tag is restored as a whole
+            let retyped = match target {
+                NbtTag::List(list) if list.values.is_empty() && list.ty != tag.ty() => Some(target.clone()),
+                _ => None,
+            };
+            insert_element(target, position as usize, &tag)?;
+            if let Some(record) = record.as_deref_mut() {
+                match retyped {
+                    Some(previous) => record.previous.push((path.to_string(), previous)),
+                    None => record.created.push(element_path(path, position as usize)),
+                }
+            }
+            count += 1;
+            Ok(())
+        })?;
+
+        Ok(count)
+    }
+
+    /// Calls `f` on every tag selected by the path, with its concrete path.
+    pub(crate) fn for_each_mut(&self, root: &mut NbtTag, f: &mut dyn FnMut(&str, &mut NbtTag) -> Result<(), NbtError>) -> Result<(), NbtError> {
+        visit_mut(&self.nodes, root, "", None, &mut Vec::new(), f)
+    }
+
+    /// `set` on a root compound.
+    pub fn set_compound(&self, root: &mut NbtTagCompound, tag: NbtTag) -> Result<usize, NbtError> {
+        with_compound_root(root, |root| self.set(root, tag))
+    }
+
+    /// `remove` on a root compound.
+    pub fn remove_compound(&self, root: &mut NbtTagCompound) -> Result<usize, NbtError> {
+        with_compound_root(root, |root| self.remove(root))
+    }
+
+    /// `insert` on a root compound.
+    pub fn insert_compound(&self, root: &mut NbtTagCompound, index: i32, tag: NbtTag) -> Result<usize, NbtError> {
+        with_compound_root(root, |root| self.insert(root, index, tag))
+    }
+
+    /// Splits the node that selects the edited tags from the nodes that lead to their parents.
+    fn split_last(&self) -> Result<(&Node, &[Node]), NbtError> {
+        match self.nodes.split_last() {
+            Some((Node::RootFilter(_), _)) | None => {
+                Err(NbtError::new(NbtErrorKind::InvalidPath("the root cannot be set or removed".to_string()), 0))
+            }
+            Some(split) => Ok(split),
+        }
+    }
+}
+
+/// Runs `edit` on `root` moved into an `NbtTag`, which is cheap, and moves it back.
+pub(crate) fn with_compound_root<T>(root: &mut NbtTagCompound, edit: impl FnOnce(&mut NbtTag) -> T) -> T {
+    let mut tag = NbtTag::Compound(std::mem::take(root));
+    let result = edit(&mut tag);
+    if let NbtTag::Compound(compound) = tag {
+        *root = compound;
+    }
+    result
+}
+
+/// Records the current value of the children of `parent` selected by `node`.
+fn record_children(node: &Node, path: &str, parent: &NbtTag, record: &mut EditRecord) {
+    let mut children = Vec::new();
+    apply(node, path, parent, &mut children);
+    record.previous.extend(children.into_iter().map(|child| (child.path, child.tag.into_owned())));
+}
+
+/// Calls `f` on every tag selected by `nodes`, with its concrete path. When `create` holds
+/// the node that will be applied to these tags, missing keys followed by a key node are
+/// created as compounds, from the filter for a filtered key, and listed in `created`.
+fn visit_mut(
+    nodes: &[Node],
+    tag: &mut NbtTag,
+    path: &str,
+    create: Option<&Node>,
+    created: &mut Vec<String>,
+    f: &mut dyn FnMut(&str, &mut NbtTag) -> Result<(), NbtError>,
+) -> Result<(), NbtError> {
+    let Some((node, rest)) = nodes.split_first() else {
+        return f(path, tag);
+    };
+
+    match node {
+        Node::Key(key) | Node::KeyFilter(key, _) => {
+            let Some(compound) = tag.as_compound_mut() else {
+                return Ok(());
+            };
+            let filter = match node {
+                Node::KeyFilter(_, filter) => Some(filter),
+                _ => None,
+            };
+
+            let child_path = child_path(path, key);
+            let next = rest.first().or(create);
+            if create.is_some() && !compound.values.contains_key(key) && matches!(next, Some(Node::Key(_)) | Some(Node::KeyFilter(_, _))) {
+                let child = filter.cloned().unwrap_or_default();
+                compound.values.insert(key.clone(), NbtTag::Compound(child));
+                created.push(child_path.clone());
+            }
+
+            match compound.values.get_mut(key) {
+                Some(child) if filter.is_none_or(|filter| tag_contains(child, filter)) => visit_mut(rest, child, &child_path, create, created, f),
+                _ => Ok(()),
+            }
+        }
+        Node::RootFilter(filter) => {
+            if tag_contains(tag, filter) {
+                return visit_mut(rest, tag, path, create, created, f);
+            }
+            Ok(())
+        }
+        Node::Index(index) => {
+            let Some(list) = tag.as_list_mut() else {
+                return Ok(());
+            };
+            match resolve_index(*index, list.values.len()) {
+                Some(index) => visit_mut(rest, &mut list.values[index], &element_path(path, index), create, created, f),
+                None => Ok(()),
+            }
+        }
+        Node::AllElements | Node::ElementFilter(_) => {
+            let Some(list) = tag.as_list_mut() else {
+                return Ok(());
+            };
+            for (index, value) in list.values.iter_mut().enumerate() {
+                if let Node::ElementFilter(filter) = node {
+                    if !tag_contains(value, filter) {
+                        continue;
+                    }
+                }
+                visit_mut(rest, value, &element_path(path, index), create, created, f)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Sets the children of `parent` selected by `node` to `tag`.
+fn set_child(node: &Node, parent: &mut NbtTag, tag: &NbtTag) -> Result<usize, NbtError> {
+    match node {
+        Node::Key(key) | Node::KeyFilter(key, _) => {
+            let Some(compound) = parent.as_compound_mut() else {
+                return Ok(0);
+            };
+            if let Node::KeyFilter(_, filter) = node {
+                if !compound.values.get(key).is_some_and(|child| tag_contains(child, filter)) {
+                    return Ok(0);
+                }
+            }
+            compound.insert(key, tag.clone());
+            Ok(1)
+        }
+        Node::RootFilter(_) => unreachable!(), // Rejected by split_last
+        Node::Index(index) => {
+            let len = element_count(parent);
+            match resolve_index(*index, len) {
+                Some(index) => set_element(parent, index, tag).map(|_| 1),
+                None => Ok(0),
+            }
+        }
+        Node::AllElements => {
+            let len = element_count(parent);
+            for index in 0..len {
+                set_element(parent, index, tag)?;
+            }
+            Ok(len)
+        }
+        Node::ElementFilter(filter) => {
+            let Some(list) = parent.as_list() else {
+                return Ok(0);
+            };
+            let selected: Vec<_> = (0..list.values.len()).filter(|index| tag_contains(&list.values[*index], filter)).collect();
+            for index in selected.iter() {
+                set_element(parent, *index, tag)?;
+            }
+            Ok(selected.len())
+        }
+    }
+}
+
+/// Replaces element `index`, which must be in range, of a list or array.
+fn set_element(parent: &mut NbtTag, index: usize, tag: &NbtTag) -> Result<(), NbtError> {
+    let mismatch = |expected: NbtTagType| {
+        NbtError::new(NbtErrorKind::ListTypeMismatch { expected, found: tag.ty() }, 0).in_index(index)
+    };
+
+    match (parent, tag) {
+        (NbtTag::List(list), _) => {
+            if list.ty != tag.ty() {
+                return Err(mismatch(list.ty));
+            }
+            list.values[index] = tag.clone();
+        }
+        (NbtTag::ByteArray(array), NbtTag::Byte(value)) => array.values[index] = value.value,
+        (NbtTag::IntArray(array), NbtTag::Int(value)) => array.values[index] = value.value,
+        (NbtTag::LongArray(array), NbtTag::Long(value)) => array.values[index] = value.value,
+        (NbtTag::ByteArray(_), _) => return Err(mismatch(NbtTagType::Byte)),
+        (NbtTag::IntArray(_), _) => return Err(mismatch(NbtTagType::Int)),
+        (NbtTag::LongArray(_), _) => return Err(mismatch(NbtTagType::Long)),
+        _ => unreachable!(), // element_count is 0 for other tags
+    }
+    Ok(())
+}
+
+/// Inserts a copy of `tag` at `index`, at most the length, in a list or array. An empty
+/// list takes the type of the tag.
+fn insert_element(target: &mut NbtTag, index: usize, tag: &NbtTag) -> Result<(), NbtError> {
+    let mismatch = |expected: NbtTagType| {
+        NbtError::new(NbtErrorKind::ListTypeMismatch { expected, found: tag.ty() }, 0).in_index(index)
+    };
+
+    match (target, tag) {
+        (NbtTag::List(list), _) => {
+            if list.values.is_empty() {
+                list.ty = tag.ty();
+            }
+            else if list.ty != tag.ty() {
+                return Err(mismatch(list.ty));
+            }
+            list.values.insert(index, tag.clone());
+        }
+        (NbtTag::ByteArray(array), NbtTag::Byte(value)) => array.values.insert(index, value.value),
+        (NbtTag::IntArray(array), NbtTag::Int(value)) => array.values.insert(index, value.value),
+        (NbtTag::LongArray(array), NbtTag::Long(value)) => array.values.insert(index, value.value),
+        (NbtTag::ByteArray(_), _) => return Err(mismatch(NbtTagType::Byte)),
+        (NbtTag::IntArray(_), _) => return Err(mismatch(NbtTagType::Int)),
+        (NbtTag::LongArray(_), _) => return Err(mismatch(NbtTagType::Long)),
+        _ => unreachable!(), // Only called on lists and arrays
+    }
+    Ok(())
+}
+
+/// Removes the children of `parent` selected by `node`.
+fn remove_child(node: &Node, parent: &mut NbtTag) -> usize {
+    match (node, parent) {
+        (Node::Key(key), NbtTag::Compound(compound)) => compound.remove(key).map_or(0, |_| 1),
+        (Node::KeyFilter(key, filter), NbtTag::Compound(compound)) => {
+            if compound.values.get(key).is_some_and(|child| tag_contains(child, filter)) {
+                compound.remove(key);
+                return 1;
+            }
+            0
+        }
+        (Node::Index(index), parent) => {
+            let Some(index) = resolve_index(*index, element_count(parent)) else {
+                return 0;
+            };
+            match parent {
+                NbtTag::List(list) => drop(list.values.remove(index)),
+                NbtTag::ByteArray(array) => drop(array.values.remove(index)),
+                NbtTag::IntArray(array) => drop(array.values.remove(index)),
+                NbtTag::LongArray(array) => drop(array.values.remove(index)),
+                _ => unreachable!(), // element_count is 0 for other tags
+            }
+            1
+        }
+        (Node::AllElements, parent) => {
+            let len = element_count(parent);
+            match parent {
+                NbtTag::List(list) => list.values.clear(),
+                NbtTag::ByteArray(array) => array.values.clear(),
+                NbtTag::IntArray(array) => array.values.clear(),
+                NbtTag::LongArray(array) => array.values.clear(),
+                _ => {}
+            }
+            len
+        }
+        (Node::ElementFilter(filter), NbtTag::List(list)) => {
+            let len = list.values.len();
+            list.values.retain(|value| !tag_contains(value, filter));
+            len - list.values.len()
+        }
+        _ => 0,
+    }
+}
+
+/// Turns a possibly negative index into a position in a sequence of `len` elements.
+fn resolve_index(index: i32, len: usize) -> Option<usize> {
+    let index = if index < 0 { len as i64 + index as i64 } else { index as i64 };
+    (0..len as i64).contains(&index).then_some(index as usize)
+}
+
+/// Applies `nodes` in turn to the current matches.
+fn run<'a>(nodes: &[Node], mut matches: Vec<NbtMatch<'a>>) -> Vec<NbtMatch<'a>> {
+    for node in nodes {
+        let mut next = Vec::new();
+        for current in matches {
+            // Array elements are leaves, no node selects anything below them
+            if let Cow::Borrowed(tag) = current.tag {
+                apply(node, &current.path, tag, &mut next);
+            }
+        }
+        matches = next;
+    }
+
+    matches
+}
+
+impl FromStr for NbtPath {
+    type Err = NbtError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        NbtPath::parse(path)
+    }
+}
+
+impl fmt::Display for NbtPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, node) in self.nodes.iter().enumerate() {
+            match node {
+                Node::Key(key) | Node::KeyFilter(key, _) => {
+                    if i > 0 {
+                        f.write_str(".")?;
+                    }
+                    f.write_str(&quote_key(key))?;
+                    if let Node::KeyFilter(_, filter) = node {
+                        f.write_str(&filter.to_snbt())?;
+                    }
+                }
+                Node::RootFilter(filter) => f.write_str(&filter.to_snbt())?,
+                Node::Index(index) => write!(f, "[{}]", index)?,
+                Node::AllElements => f.write_str("[]")?,
+                Node::ElementFilter(filter) => write!(f, "[{}]", filter.to_snbt())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn child_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        quote_key(key)
+    }
+    else {
+        format!("{}.{}", path, quote_key(key))
+    }
+}
+
+pub(crate) fn element_path(path: &str, index: usize) -> String {
+    format!("{}[{}]", path, index)
+}
+
+fn apply<'a>(node: &Node, path: &str, tag: &'a NbtTag, out: &mut Vec<NbtMatch<'a>>) {
+    match node {
+        Node::Key(_) | Node::KeyFilter(_, _) => {
+            if let Some(compound) = tag.as_compound() {
+                apply_key(node, path, compound, out);
+            }
+        }
+        Node::RootFilter(filter) => {
+            if tag_contains(tag, filter) {
+                out.push(NbtMatch { path: path.to_string(), tag: Cow::Borrowed(tag) });
+            }
+        }
+        Node::Index(index) => {
+            if let Some(index) = resolve_index(*index, element_count(tag)) {
+                out.push(element(path, tag, index));
+            }
+        }
+        Node::AllElements => {
+            for index in 0..element_count(tag) {
+                out.push(element(path, tag, index));
+            }
+        }
+        Node::ElementFilter(filter) => {
+            if let Some(list) = tag.as_list() {
+                for (index, value) in list.values.iter().enumerate() {
+                    if tag_contains(value, filter) {
+                        out.push(NbtMatch { path: element_path(path, index), tag: Cow::Borrowed(value) });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Applies a key node, with or without a filter, to a compound.
+fn apply_key<'a>(node: &Node, path: &str, compound: &'a NbtTagCompound, out: &mut Vec<NbtMatch<'a>>) {
+    let (key, filter) = match node {
+        Node::Key(key) => (key, None),
+        Node::KeyFilter(key, filter) => (key, Some(filter)),
+        _ => return,
+    };
+
+    if let Some(child) = compound.values.get(key) {
+        if filter.is_none_or(|filter| tag_contains(child, filter)) {
+            out.push(NbtMatch { path: child_path(path, key), tag: Cow::Borrowed(child) });
+        }
+    }
+}
+
+fn element_count(tag: &NbtTag) -> usize {
+    match tag {
+        NbtTag::List(list) => list.values.len(),
+        NbtTag::ByteArray(array) => array.values.len(),
+        NbtTag::IntArray(array) => array.values.len(),
+        NbtTag::LongArray(array) => array.values.len(),
+        _ => 0,
+    }
+}
+
+/// Element `index` of a list or array, which must be in range.
+fn element<'a>(path: &str, tag: &'a NbtTag, index: usize) -> NbtMatch<'a> {
+    let tag = match tag {
+        NbtTag::List(list) => Cow::Borrowed(&list.values[index]),
+        NbtTag::ByteArray(array) => Cow::Owned(NbtTag::Byte(NbtTagByte::new(array.values[index]))),
+        NbtTag::IntArray(array) => Cow::Owned(NbtTag::Int(NbtTagInt::new(array.values[index]))),
+        NbtTag::LongArray(array) => Cow::Owned(NbtTag::Long(NbtTagLong::new(array.values[index]))),
+        _ => unreachable!(), // element_count is 0 for other tags
+    };
+
+    NbtMatch { path: element_path(path, index), tag }
+}
+
+fn compound_contains(compound: &NbtTagCompound, filter: &NbtTagCompound) -> bool {
+    filter.values.iter().all(|(key, expected)| compound.values.get(key).is_some_and(|value| contains(value, expected)))
+}
+
+fn tag_contains(tag: &NbtTag, filter: &NbtTagCompound) -> bool {
+    tag.as_compound().is_some_and(|compound| compound_contains(compound, filter))
+}
+
+/// Whether `tag` matches `filter`, like `/data` does: compounds may hold more keys than the
+/// filter and lists more elements. Tag names are ignored, only values are compared.
+pub fn contains(tag: &NbtTag, filter: &NbtTag) -> bool {
+    match (filter, tag) {
+        (NbtTag::Compound(filter), _) => tag_contains(tag, filter),
+        (NbtTag::List(filter), NbtTag::List(list)) => {
+            if filter.values.is_empty() {
+                return list.values.is_empty();
+            }
+            filter.values.iter().all(|expected| list.values.iter().any(|value| contains(value, expected)))
+        }
+        (NbtTag::Byte(a), NbtTag::Byte(b)) => a.value == b.value,
+        (NbtTag::Short(a), NbtTag::Short(b)) => a.value == b.value,
+        (NbtTag::Int(a), NbtTag::Int(b)) => a.value == b.value,
+        (NbtTag::Long(a), NbtTag::Long(b)) => a.value == b.value,
+        (NbtTag::Float(a), NbtTag::Float(b)) => a.value == b.value,
+        (NbtTag::Double(a), NbtTag::Double(b)) => a.value == b.value,
+        (NbtTag::String(a), NbtTag::String(b)) => a.value == b.value,
+        (NbtTag::ByteArray(a), NbtTag::ByteArray(b)) => a.values == b.values,
+        (NbtTag::IntArray(a), NbtTag::IntArray(b)) => a.values == b.values,
+        (NbtTag::LongArray(a), NbtTag::LongArray(b)) => a.values == b.values,
+        _ => false,
+    }
+}
+
+/// Characters that end an unquoted key, as in the game.
+fn ends_unquoted_key(c: char) -> bool {
+    matches!(c, ' ' | '"' | '\'' | '[' | ']' | '.' | '{' | '}')
+}
+
+struct PathParser<'t> {
+    text: &'t str,
+    pos: usize,
+}
+
+impl PathParser<'_> {
+    fn error(&self, message: &str) -> NbtError {
+        NbtError::new(NbtErrorKind::InvalidPath(message.to_string()), self.pos as u64)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), NbtError> {
+        if self.peek() != Some(c) {
+            return Err(self.error(&format!("expected '{}'", c)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn parse(mut self) -> Result<NbtPath, NbtError> {
+        let mut nodes = Vec::new();
+
+        while self.pos < self.text.len() {
+            let node = self.parse_node(nodes.is_empty())?;
+            nodes.push(node);
+
+            match self.peek() {
+                None | Some('[') | Some('{') => {}
+                Some(_) => {
+                    self.expect('.')?;
+                    if self.pos == self.text.len() {
+                        return Err(self.error("expected a key after '.'"));
+                    }
+                }
+            }
+        }
+
+        if nodes.is_empty() {
+            return Err(self.error("empty path"));
+        }
+        Ok(NbtPath { nodes })
+    }
+
+    fn parse_node(&mut self, first: bool) -> Result<Node, NbtError> {
+        match self.peek() {
+            Some('{') if first => Ok(Node::RootFilter(self.parse_filter()?)),
+            Some('{') => Err(self.error("a filter must follow a key or be in brackets")),
+            Some('[') => {
+                self.pos += 1;
+                let node = match self.peek() {
+                    Some('{') => Node::ElementFilter(self.parse_filter()?),
+                    Some(']') => Node::AllElements,
+                    _ => Node::Index(self.parse_index()?),
+                };
+                self.expect(']')?;
+                Ok(node)
+            }
+            Some('"') | Some('\'') => {
+                let start = self.pos;
+                let (key, len) = snbt::parse_quoted_prefix(&self.text[start..]).map_err(|e| snbt_error(start, e))?;
+                self.pos += len;
+                self.parse_key_node(key)
+            }
+            _ => {
+                let rest = &self.text[self.pos..];
+                let len = rest.find(ends_unquoted_key).unwrap_or(rest.len());
+                if len == 0 {
+                    return Err(self.error("expected a key"));
+                }
+                self.pos += len;
+                self.parse_key_node(rest[..len].to_string())
+            }
+        }
+    }
+
+    fn parse_key_node(&mut self, key: String) -> Result<Node, NbtError> {
+        if self.peek() == Some('{') {
+            return Ok(Node::KeyFilter(key, self.parse_filter()?));
+        }
+        Ok(Node::Key(key))
+    }
+
+    fn parse_index(&mut self) -> Result<i32, NbtError> {
+        let rest = &self.text[self.pos..];
+        let len = rest.find(|c: char| !(c.is_ascii_digit() || c == '-')).unwrap_or(rest.len());
+        let index = rest[..len].parse().map_err(|_| self.error("expected an index"))?;
+        self.pos += len;
+        Ok(index)
+    }
+
+    fn parse_filter(&mut self) -> Result<NbtTagCompound, NbtError> {
+        let start = self.pos;
+        let (filter, len) = snbt::parse_compound_prefix(&self.text[start..]).map_err(|e| snbt_error(start, e))?;
+        self.pos += len;
+        Ok(filter)
+    }
+}
+
+/// Moves an error of the SNBT parser, which started at byte `start`, into the path.
+fn snbt_error(start: usize, error: NbtError) -> NbtError {
+    NbtError::new(NbtErrorKind::InvalidPath(error.kind.to_string()), start as u64 + error.offset)
+}
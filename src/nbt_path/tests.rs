@@ -0,0 +1,167 @@
+#[cfg(test)]
+
+use super::*;
+
+fn chunk() -> NbtTag {
+    NbtTag::from_snbt(concat!(
+        "{DataVersion:3465,sections:[",
+        "{Y:-4b,block_states:{palette:[{Name:\"minecraft:stone\"},{Name:\"minecraft:chest\",Properties:{facing:\"north\"}}]}},",
+        "{Y:-3b,block_states:{palette:[{Name:\"minecraft:air\"}]}},",
+        "{Y:-2b,block_states:{palette:[{Name:\"minecraft:chest\",Properties:{facing:\"east\"}}]}}",
+        "],Heights:[L;1L,2L,3L],\"odd key\":{\"a.b\":1}}"
+    ))
+    .unwrap()
+}
+
+/// Concrete paths of the matches, also used by the `nbt_search` tests.
+pub(crate) fn match_paths(found: &[NbtMatch]) -> Vec<String> {
+    found.iter().map(|m| m.path.clone()).collect()
+}
+
+fn paths(path: &str, root: &NbtTag) -> Vec<String> {
+    match_paths(&NbtPath::parse(path).unwrap().query(root))
+}
+
+#[test]
+fn test_query_filters_and_wildcards() {
+    let root = chunk();
+    let matches = NbtPath::parse("sections[].block_states.palette[{Name:\"minecraft:chest\"}].Properties.facing").unwrap().query(&root);
+
+    let found: Vec<_> = matches.iter().map(|m| (m.path.as_str(), m.tag.string().unwrap().value)).collect();
+    assert_eq!(found, vec![
+        ("sections[0].block_states.palette[1].Properties.facing", "north".to_string()),
+        ("sections[2].block_states.palette[0].Properties.facing", "east".to_string()),
+    ]);
+}
+
+#[test]
+fn test_query_borrows_from_tree() {
+    let root = chunk();
+    let matches = NbtPath::parse("DataVersion").unwrap().query(&root);
+
+    assert_eq!(matches.len(), 1);
+    assert!(matches!(matches[0].tag, Cow::Borrowed(_)));
+    assert_eq!(matches[0].tag.int().unwrap().value, 3465);
+}
+
+#[test]
+fn test_query_indices() {
+    let root = chunk();
+
+    assert_eq!(paths("sections[1].Y", &root), vec!["sections[1].Y"]);
+    assert_eq!(paths("sections[-1].Y", &root), vec!["sections[2].Y"]);
+    assert!(paths("sections[3]", &root).is_empty());
+    assert!(paths("sections[-4]", &root).is_empty());
+
+    let matches = NbtPath::parse("Heights[-1]").unwrap().query(&root);
+    assert_eq!(matches[0].path, "Heights[2]");
+    assert_eq!(matches[0].tag.long().unwrap().value, 3);
+    assert_eq!(paths("Heights[]", &root).len(), 3);
+    assert!(paths("Heights[0].x", &root).is_empty());
+}
+
+#[test]
+fn test_query_key_and_root_filters() {
+    let root = chunk();
+
+    assert_eq!(paths("sections[].block_states.palette[].Properties{facing:\"east\"}", &root).len(), 1);
+    assert_eq!(paths("{DataVersion:3465}.DataVersion", &root), vec!["DataVersion"]);
+    assert!(paths("{DataVersion:3465s}", &root).is_empty());
+    assert_eq!(paths("{sections:[{Y:-3b}]}", &root), vec![""]);
+    assert!(paths("{sections:[]}", &root).is_empty());
+}
+
+#[test]
+fn test_query_quoted_keys() {
+    let root = chunk();
+
+    assert_eq!(paths("\"odd key\".\"a.b\"", &root), vec!["\"odd key\".\"a.b\""]);
+    assert_eq!(paths("'odd key'.'a.b'", &root).len(), 1);
+}
+
+#[test]
+fn test_display_round_trip() {
+    for text in ["sections[].block_states.palette[{Name:\"minecraft:chest\"}].Properties.facing", "a[-1][0].\"b c\"{d:1b}", "{x:1}.y"] {
+        let path = NbtPath::parse(text).unwrap();
+        assert_eq!(NbtPath::parse(&path.to_string()).unwrap(), path);
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    for (text, offset) in [("", 0), ("a..b", 2), ("a.", 2), ("a[x]", 2), ("a[0", 3), ("a.{b:1}", 2), ("a{b:}", 4), ("\"a", 2), ("a]", 1)] {
+        let error = NbtPath::parse(text).unwrap_err();
+        assert!(matches!(error.kind, NbtErrorKind::InvalidPath(_)), "{}", text);
+        assert_eq!(error.offset, offset, "{}", text);
+    }
+}
+
+#[test]
+fn test_set_selected_tags() {
+    let mut root = chunk();
+    let path = NbtPath::parse("sections[].block_states.palette[{Name:\"minecraft:chest\"}].Properties.facing").unwrap();
+
+    let count = path.set(&mut root, NbtTag::from_snbt("\"south\"").unwrap()).unwrap();
+
+    assert_eq!(count, 2);
+    let facings: Vec<_> = path.query(&root).into_iter().map(|m| m.tag.string().unwrap()).collect();
+    assert!(facings.iter().all(|tag| tag.value == "south"));
+    assert!(path.query(&root).iter().all(|m| m.key() == "facing"));
+}
+
+#[test]
+fn test_set_elements() {
+    let mut root = chunk();
+
+    assert_eq!(NbtPath::parse("Heights[]").unwrap().set(&mut root, NbtTag::from_snbt("0L").unwrap()).unwrap(), 3);
+    assert_eq!(NbtPath::parse("sections[-1]").unwrap().set(&mut root, NbtTag::from_snbt("{Y:5b}").unwrap()).unwrap(), 1);
+    assert_eq!(root.query("Heights").unwrap()[0].tag.to_snbt(), "[L;0L,0L,0L]");
+    assert_eq!(root.query("sections[2]").unwrap()[0].tag.to_snbt(), "{Y:5b}");
+
+    let error = NbtPath::parse("Heights[0]").unwrap().set(&mut root, NbtTag::from_snbt("0").unwrap()).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::ListTypeMismatch { expected: NbtTagType::Long, found: NbtTagType::Int }));
+    let error = NbtPath::parse("sections[0]").unwrap().set(&mut root, NbtTag::from_snbt("1b").unwrap()).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::ListTypeMismatch { expected: NbtTagType::Compound, found: NbtTagType::Byte }));
+}
+
+#[test]
+fn test_set_creates_compounds() {
+    let mut root = NbtTagCompound::new("");
+
+    let count = NbtPath::parse("a.b{x:1}.c").unwrap().set_compound(&mut root, NbtTag::from_snbt("1b").unwrap()).unwrap();
+
+    assert_eq!(count, 1);
+    assert_eq!(root.to_snbt(), "{a:{b:{x:1,c:1b}}}");
+    // Nothing is created in front of an index
+    assert_eq!(NbtPath::parse("l[0]").unwrap().set_compound(&mut root, NbtTag::from_snbt("1b").unwrap()).unwrap(), 0);
+    assert!(NbtPath::parse("{}").unwrap().set_compound(&mut root, NbtTag::from_snbt("1b").unwrap()).is_err());
+}
+
+#[test]
+fn test_remove() {
+    let mut root = chunk();
+
+    assert_eq!(NbtPath::parse("sections[].block_states.palette[{Name:\"minecraft:chest\"}]").unwrap().remove(&mut root).unwrap(), 2);
+    assert!(root.query("sections[].block_states.palette[{Name:\"minecraft:chest\"}]").unwrap().is_empty());
+    assert_eq!(NbtPath::parse("Heights[-1]").unwrap().remove(&mut root).unwrap(), 1);
+    assert_eq!(NbtPath::parse("DataVersion").unwrap().remove(&mut root).unwrap(), 1);
+    assert_eq!(NbtPath::parse("DataVersion").unwrap().remove(&mut root).unwrap(), 0);
+    assert_eq!(NbtPath::parse("sections[]").unwrap().remove(&mut root).unwrap(), 3);
+
+    assert_eq!(root.to_snbt(), "{sections:[],Heights:[L;1L,2L],\"odd key\":{a.b:1}}");
+}
+
+#[test]
+fn test_insert() {
+    let mut root = chunk();
+
+    assert_eq!(NbtPath::parse("Heights").unwrap().insert(&mut root, -1, NbtTag::from(9i64)).unwrap(), 1);
+    assert_eq!(NbtPath::parse("Heights").unwrap().insert(&mut root, 0, NbtTag::from(0i64)).unwrap(), 1);
+    assert_eq!(root.query("Heights").unwrap()[0].tag.to_snbt(), "[L;0L,1L,2L,3L,9L]");
+    // Out of range and non-list targets are skipped
+    assert_eq!(NbtPath::parse("Heights").unwrap().insert(&mut root, 9, NbtTag::from(1i64)).unwrap(), 0);
+    assert_eq!(NbtPath::parse("DataVersion").unwrap().insert(&mut root, 0, NbtTag::from(1)).unwrap(), 0);
+
+    let error = NbtPath::parse("Heights").unwrap().insert(&mut root, 0, NbtTag::from(1)).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::ListTypeMismatch { expected: NbtTagType::Long, found: NbtTagType::Int }));
+}
@@ -0,0 +1,342 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.0
+//
+// ## Changelog
+// - 1.0.0: Initial version, declarative schemas and the built-in vanilla schemas [mrmarkolinus:2026-10-16]
+
+//! Declarative descriptions of the shape a tree is expected to have, e.g. before reading a
+//! chunk without checking every key by hand.
+//!
+//! A schema sets the tag type, the range of a number, the length of a string, list or
+//! array, the required and optional keys of a compound, the schema of the other keys and
+//! the schema of list elements. Everything not set is accepted, and keys the schema does
+//! not mention are allowed. Validation does not stop at the first problem, it returns every
+//! violation with its NBT path:
+//!
+//! ```ignore
+//! let schema = NbtSchema::of(NbtTagType::Compound)
+//!     .with_required("id", NbtSchema::of(NbtTagType::String))
+//!     .with_optional("Count", NbtSchema::of(NbtTagType::Byte).with_range(1.0, 64.0));
+//! for violation in item.validate(&schema) {
+//!     println!("{}", violation);
+//! }
+//! ```
+//!
+//! `chunk`, `section`, `block_states`, `litematic` and `structure` describe the vanilla
+//! files read by this crate, see the Minecraft wiki for the formats.
+
+use crate::nbt_path::{child_path, element_path};
+use crate::nbt_tag::*;
+
+use indexmap::IndexMap;
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// Expected shape of a tag, see the module documentation.
+#[derive(Clone, Debug, Default)]
+pub struct NbtSchema {
+    ty: Option<NbtTagType>,
+    range: Option<(f64, f64)>,
+    length: Option<(usize, usize)>,
+    /// Keys named by the schema, with their schema and whether they are required.
+    keys: IndexMap<String, (NbtSchema, bool)>,
+    /// Schema of the keys not named in `keys`.
+    values: Option<Box<NbtSchema>>,
+    elements: Option<Box<NbtSchema>>,
+}
+
+/// A place where a tree does not match its schema.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaViolation {
+    /// A required key is missing, the path is the one the key would have.
+    MissingKey { path: String },
+    WrongType { path: String, expected: NbtTagType, found: NbtTagType },
+    /// A number outside the range of the schema, as `f64` whatever its type.
+    OutOfRange { path: String, value: f64, min: f64, max: f64 },
+    /// A string, list or array with a length outside the range of the schema.
+    WrongLength { path: String, length: usize, min: usize, max: usize },
+}
+
+impl SchemaViolation {
+    pub fn path(&self) -> &str {
+        match self {
+            SchemaViolation::MissingKey { path } | SchemaViolation::WrongType { path, .. } => path,
+            SchemaViolation::OutOfRange { path, .. } | SchemaViolation::WrongLength { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path().is_empty() { "<root>" } else { self.path() };
+        match self {
+            SchemaViolation::MissingKey { .. } => write!(f, "{}: missing", path),
+            SchemaViolation::WrongType { expected, found, .. } => write!(f, "{}: expected {:?}, found {:?}", path, expected, found),
+            SchemaViolation::OutOfRange { value, min, max, .. } => write!(f, "{}: {} is not in {}..={}", path, value, min, max),
+            SchemaViolation::WrongLength { length, min, max, .. } => write!(f, "{}: length {} is not in {}..={}", path, length, min, max),
+        }
+    }
+}
+
+impl NbtSchema {
+    /// Accepts any tag.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Accepts tags of type `ty`.
+    pub fn of(ty: NbtTagType) -> Self {
+        NbtSchema { ty: Some(ty), ..Self::default() }
+    }
+
+    /// Parses the names of the built-in schemas used by the Python API: `chunk`, `section`,
+    /// `block_states`, `litematic` and `structure`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "chunk" => Some(chunk()),
+            "section" => Some(section()),
+            "block_states" => Some(block_states()),
+            "litematic" => Some(litematic()),
+            "structure" => Some(structure()),
+            _ => None,
+        }
+    }
+
+    /// Numbers must lie in `min..=max`.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Strings, lists and arrays must have `min..=max` characters or elements.
+    pub fn with_length(mut self, min: usize, max: usize) -> Self {
+        self.length = Some((min, max));
+        self
+    }
+
+    pub fn with_required(mut self, key: &str, schema: NbtSchema) -> Self {
+        self.keys.insert(key.to_string(), (schema, true));
+        self
+    }
+
+    pub fn with_optional(mut self, key: &str, schema: NbtSchema) -> Self {
+        self.keys.insert(key.to_string(), (schema, false));
+        self
+    }
+
+    /// Schema of the keys of a compound not named by `with_required` or `with_optional`,
+    /// e.g. the regions of a litematic, named by the player.
+    pub fn with_values(mut self, schema: NbtSchema) -> Self {
+        self.values = Some(Box::new(schema));
+        self
+    }
+
+    /// Schema of the elements of a list.
+    pub fn with_elements(mut self, schema: NbtSchema) -> Self {
+        self.elements = Some(Box::new(schema));
+        self
+    }
+
+    /// Returns every violation of the schema by `tag`, in tree order.
+    pub fn validate(&self, tag: &NbtTag) -> Vec<SchemaViolation> {
+        let mut violations = Vec::new();
+        self.check("", tag, &mut violations);
+        violations
+    }
+
+    /// `validate` on a root compound.
+    pub fn validate_compound(&self, compound: &NbtTagCompound) -> Vec<SchemaViolation> {
+        let mut violations = Vec::new();
+        if let Some(ty) = self.ty.filter(|ty| *ty != NbtTagType::Compound) {
+            violations.push(SchemaViolation::WrongType { path: String::new(), expected: ty, found: NbtTagType::Compound });
+            return violations;
+        }
+        self.check_compound("", compound, &mut violations);
+        violations
+    }
+
+    fn check(&self, path: &str, tag: &NbtTag, out: &mut Vec<SchemaViolation>) {
+        if let Some(expected) = self.ty.filter(|ty| *ty != tag.ty()) {
+            out.push(SchemaViolation::WrongType { path: path.to_string(), expected, found: tag.ty() });
+            return;
+        }
+
+        if let (Some((min, max)), Some(value)) = (self.range, number(tag)) {
+            if !(min..=max).contains(&value) {
+                out.push(SchemaViolation::OutOfRange { path: path.to_string(), value, min, max });
+            }
+        }
+
+        if let (Some((min, max)), Some(length)) = (self.length, length(tag)) {
+            if !(min..=max).contains(&length) {
+                out.push(SchemaViolation::WrongLength { path: path.to_string(), length, min, max });
+            }
+        }
+
+        match tag {
+            NbtTag::Compound(compound) => self.check_compound(path, compound, out),
+            NbtTag::List(list) => {
+                if let Some(elements) = self.elements.as_deref() {
+                    for (index, value) in list.values.iter().enumerate() {
+                        elements.check(&element_path(path, index), value, out);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_compound(&self, path: &str, compound: &NbtTagCompound, out: &mut Vec<SchemaViolation>) {
+        for (key, (schema, required)) in self.keys.iter() {
+            match compound.values.get(key) {
+                Some(value) => schema.check(&child_path(path, key), value, out),
+                None if *required => out.push(SchemaViolation::MissingKey { path: child_path(path, key) }),
+                None => {}
+            }
+        }
+
+        if let Some(values) = self.values.as_deref() {
+            for (key, value) in compound.values.iter().filter(|(key, _)| !self.keys.contains_key(*key)) {
+                values.check(&child_path(path, key), value, out);
+            }
+        }
+    }
+}
+
+fn number(tag: &NbtTag) -> Option<f64> {
+    match tag {
+        NbtTag::Byte(x) => Some(x.value as f64),
+        NbtTag::Short(x) => Some(x.value as f64),
+        NbtTag::Int(x) => Some(x.value as f64),
+        NbtTag::Long(x) => Some(x.value as f64),
+        NbtTag::Float(x) => Some(x.value as f64),
+        NbtTag::Double(x) => Some(x.value),
+        _ => None,
+    }
+}
+
+fn length(tag: &NbtTag) -> Option<usize> {
+    match tag {
+        NbtTag::String(x) => Some(x.value.chars().count()),
+        NbtTag::List(x) => Some(x.values.len()),
+        NbtTag::ByteArray(x) => Some(x.values.len()),
+        NbtTag::IntArray(x) => Some(x.values.len()),
+        NbtTag::LongArray(x) => Some(x.values.len()),
+        _ => None,
+    }
+}
+
+fn list_of(schema: NbtSchema) -> NbtSchema {
+    NbtSchema::of(NbtTagType::List).with_elements(schema)
+}
+
+/// `{x, y, z}` compound of ints, as used by litematics.
+fn xyz() -> NbtSchema {
+    ["x", "y", "z"].iter().fold(NbtSchema::of(NbtTagType::Compound), |schema, key| schema.with_required(key, NbtSchema::of(NbtTagType::Int)))
+}
+
+/// Palette entry: a block name and its string properties.
+fn block_state() -> NbtSchema {
+    NbtSchema::of(NbtTagType::Compound)
+        .with_required("Name", NbtSchema::of(NbtTagType::String))
+        .with_optional("Properties", NbtSchema::of(NbtTagType::Compound).with_values(NbtSchema::of(NbtTagType::String)))
+}
+
+/// Root of a chunk in a region file, in the format of 1.18 and later.
+pub fn chunk() -> NbtSchema {
+    NbtSchema::of(NbtTagType::Compound)
+        .with_required("DataVersion", NbtSchema::of(NbtTagType::Int))
+        .with_required("xPos", NbtSchema::of(NbtTagType::Int))
+        .with_required("zPos", NbtSchema::of(NbtTagType::Int))
+        .with_optional("yPos", NbtSchema::of(NbtTagType::Int))
+        .with_required("Status", NbtSchema::of(NbtTagType::String))
+        .with_optional("LastUpdate", NbtSchema::of(NbtTagType::Long))
+        .with_optional("InhabitedTime", NbtSchema::of(NbtTagType::Long))
+        .with_required("sections", list_of(section()))
+        .with_optional("block_entities", list_of(NbtSchema::of(NbtTagType::Compound)))
+        .with_optional("Heightmaps", NbtSchema::of(NbtTagType::Compound).with_values(NbtSchema::of(NbtTagType::LongArray)))
+}
+
+/// A 16x16x16 section of a chunk.
+pub fn section() -> NbtSchema {
+    let biomes = NbtSchema::of(NbtTagType::Compound)
+        .with_required("palette", list_of(NbtSchema::of(NbtTagType::String)).with_length(1, 64))
+        .with_optional("data", NbtSchema::of(NbtTagType::LongArray));
+
+    NbtSchema::of(NbtTagType::Compound)
+        .with_required("Y", NbtSchema::of(NbtTagType::Byte))
+        .with_optional("block_states", block_states())
+        .with_optional("biomes", biomes)
+        .with_optional("BlockLight", NbtSchema::of(NbtTagType::ByteArray).with_length(2048, 2048))
+        .with_optional("SkyLight", NbtSchema::of(NbtTagType::ByteArray).with_length(2048, 2048))
+}
+
+/// Block palette and packed block indices of a section.
+pub fn block_states() -> NbtSchema {
+    NbtSchema::of(NbtTagType::Compound)
+        .with_required("palette", list_of(block_state()).with_length(1, 4096))
+        .with_optional("data", NbtSchema::of(NbtTagType::LongArray))
+}
+
+/// Root of a Litematica `.litematic` schematic.
+pub fn litematic() -> NbtSchema {
+    let metadata = NbtSchema::of(NbtTagType::Compound)
+        .with_required("Name", NbtSchema::of(NbtTagType::String))
+        .with_required("Author", NbtSchema::of(NbtTagType::String))
+        .with_optional("Description", NbtSchema::of(NbtTagType::String))
+        .with_required("EnclosingSize", xyz())
+        .with_required("RegionCount", NbtSchema::of(NbtTagType::Int).with_range(0.0, i32::MAX as f64))
+        .with_optional("TimeCreated", NbtSchema::of(NbtTagType::Long))
+        .with_optional("TimeModified", NbtSchema::of(NbtTagType::Long))
+        .with_optional("TotalBlocks", NbtSchema::of(NbtTagType::Int))
+        .with_optional("TotalVolume", NbtSchema::of(NbtTagType::Int));
+
+    let region = NbtSchema::of(NbtTagType::Compound)
+        .with_required("Position", xyz())
+        .with_required("Size", xyz())
+        .with_required("BlockStatePalette", list_of(block_state()).with_length(1, usize::MAX))
+        .with_required("BlockStates", NbtSchema::of(NbtTagType::LongArray))
+        .with_optional("TileEntities", list_of(NbtSchema::of(NbtTagType::Compound)))
+        .with_optional("Entities", list_of(NbtSchema::of(NbtTagType::Compound)))
+        .with_optional("PendingBlockTicks", list_of(NbtSchema::of(NbtTagType::Compound)))
+        .with_optional("PendingFluidTicks", list_of(NbtSchema::of(NbtTagType::Compound)));
+
+    NbtSchema::of(NbtTagType::Compound)
+        .with_required("Version", NbtSchema::of(NbtTagType::Int))
+        .with_optional("SubVersion", NbtSchema::of(NbtTagType::Int))
+        .with_optional("MinecraftDataVersion", NbtSchema::of(NbtTagType::Int))
+        .with_required("Metadata", metadata)
+        .with_required("Regions", NbtSchema::of(NbtTagType::Compound).with_values(region))
+}
+
+/// Root of a structure block `.nbt` file. Structures with several palettes, such as
+/// shipwrecks, have `palettes` instead of `palette`.
+pub fn structure() -> NbtSchema {
+    let int_xyz = list_of(NbtSchema::of(NbtTagType::Int)).with_length(3, 3);
+
+    let block = NbtSchema::of(NbtTagType::Compound)
+        .with_required("state", NbtSchema::of(NbtTagType::Int).with_range(0.0, i32::MAX as f64))
+        .with_required("pos", int_xyz.clone())
+        .with_optional("nbt", NbtSchema::of(NbtTagType::Compound));
+
+    let entity = NbtSchema::of(NbtTagType::Compound)
+        .with_required("pos", list_of(NbtSchema::of(NbtTagType::Double)).with_length(3, 3))
+        .with_required("blockPos", int_xyz.clone())
+        .with_required("nbt", NbtSchema::of(NbtTagType::Compound));
+
+    NbtSchema::of(NbtTagType::Compound)
+        .with_required("DataVersion", NbtSchema::of(NbtTagType::Int))
+        .with_required("size", int_xyz)
+        .with_optional("palette", list_of(block_state()))
+        .with_optional("palettes", list_of(list_of(block_state())))
+        .with_required("blocks", list_of(block))
+        .with_required("entities", list_of(entity))
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+
+use super::*;
+use crate::nbt;
+
+fn item_schema() -> NbtSchema {
+    NbtSchema::of(NbtTagType::Compound)
+        .with_required("id", NbtSchema::of(NbtTagType::String).with_length(1, 64))
+        .with_optional("Count", NbtSchema::of(NbtTagType::Byte).with_range(1.0, 64.0))
+        .with_optional("Lore", NbtSchema::of(NbtTagType::List).with_elements(NbtSchema::of(NbtTagType::String)))
+}
+
+#[test]
+fn test_valid_tree() {
+    let item = nbt!{ "id": "minecraft:stone", "Count": 3b, "Lore": ["a", "b"], "extra": 1 };
+
+    assert!(item.validate(&item_schema()).is_empty());
+    assert!(nbt!{ "id": "minecraft:stone" }.validate(&item_schema()).is_empty());
+    assert!(NbtTag::Compound(item).validate(&NbtSchema::any()).is_empty());
+}
+
+#[test]
+fn test_every_violation_is_reported() {
+    let item = nbt!{ "Count": 65b, "Lore": ["a", ""] };
+    let lore_schema = NbtSchema::of(NbtTagType::List).with_elements(NbtSchema::of(NbtTagType::Int));
+
+    let violations = item.validate(&item_schema().with_optional("Lore", lore_schema));
+
+    assert_eq!(violations, vec![
+        SchemaViolation::MissingKey { path: "id".to_string() },
+        SchemaViolation::OutOfRange { path: "Count".to_string(), value: 65.0, min: 1.0, max: 64.0 },
+        SchemaViolation::WrongType { path: "Lore[0]".to_string(), expected: NbtTagType::Int, found: NbtTagType::String },
+        SchemaViolation::WrongType { path: "Lore[1]".to_string(), expected: NbtTagType::Int, found: NbtTagType::String },
+    ]);
+    assert_eq!(violations[0].to_string(), "id: missing");
+    assert_eq!(violations[1].to_string(), "Count: 65 is not in 1..=64");
+    assert_eq!(violations[2].to_string(), "Lore[0]: expected Int, found String");
+}
+
+#[test]
+fn test_wrong_type_stops_at_the_tag() {
+    let item = nbt!{ "id": 5, "Count": "x" };
+
+    let lines: Vec<_> = item.validate(&item_schema()).iter().map(ToString::to_string).collect();
+
+    assert_eq!(lines, vec!["id: expected String, found Int", "Count: expected Byte, found String"]);
+    assert_eq!(NbtSchema::of(NbtTagType::List).validate_compound(&item)[0].to_string(), "<root>: expected List, found Compound");
+}
+
+#[test]
+fn test_values_and_lengths() {
+    let schema = NbtSchema::of(NbtTagType::Compound).with_values(NbtSchema::of(NbtTagType::IntArray).with_length(4, 4));
+    let uuids = nbt!{ "owner": [I; 1, 2, 3, 4], "target": [I; 1, 2] };
+
+    assert_eq!(uuids.validate(&schema), vec![SchemaViolation::WrongLength { path: "target".to_string(), length: 2, min: 4, max: 4 }]);
+}
+
+#[test]
+fn test_builtin_section() {
+    let tag = nbt!{
+        "Y": 300i16,
+        "block_states": { "palette": [{ "Name": "minecraft:stone", "Properties": { "lit": 1b } }], "data": [L; 0L] },
+        "SkyLight": [B; 0i8],
+    };
+
+    let lines: Vec<_> = tag.validate(&section()).iter().map(ToString::to_string).collect();
+
+    assert_eq!(lines, vec![
+        "Y: expected Byte, found Short",
+        "block_states.palette[0].Properties.lit: expected String, found Byte",
+        "SkyLight: length 1 is not in 2048..=2048",
+    ]);
+    assert!(NbtSchema::from_name("section").is_some());
+    assert!(NbtSchema::from_name("level").is_none());
+}
+
+#[test]
+fn test_builtin_structure() {
+    let tag = nbt!{
+        "DataVersion": 3465,
+        "size": [1, 1, 1],
+        "palette": [{ "Name": "minecraft:stone" }],
+        "blocks": [{ "state": 0, "pos": [0, 0, 0] }],
+        "entities": [],
+    };
+
+    assert!(tag.validate(&structure()).is_empty());
+
+    let mut broken = tag.clone();
+    broken.set_at("blocks[0].pos", NbtTag::from_snbt("[0,0]").unwrap()).unwrap();
+    broken.remove("entities");
+    let lines: Vec<_> = broken.validate(&structure()).iter().map(ToString::to_string).collect();
+    assert_eq!(lines, vec!["blocks[0].pos: length 2 is not in 3..=3", "entities: missing"]);
+}
@@ -0,0 +1,164 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.1
+//
+// ## Changelog
+// - 1.0.0: Initial version, tree walk with pruning and predicate search [mrmarkolinus:2026-10-16]
+// - 1.0.1: Key globs match the key in the parent compound [mrmarkolinus:2026-10-16]
+
+//! Walks over every tag of a tree and searches tags by name, type, value or regex.
+//!
+//! `NbtTag::walk` calls a visitor on each tag in tree order, parents before children, with
+//! the NBT path of the tag. The visitor returns a `Walk` to go on, to skip the children of
+//! the tag or to stop. Compounds and lists are walked at any depth, including lists of
+//! lists; the elements of arrays are values, not tags, and are not visited.
+//!
+//! `search` collects the tags matching a `TagPredicate`:
+//!
+//! ```ignore
+//! // Every String containing "diamond"
+//! let found = tag.search(&TagPredicate::new().with_type(NbtTagType::String).with_regex("diamond")?);
+//! // Every Long named LastUpdate
+//! let found = tag.search(&TagPredicate::new().with_key("LastUpdate").with_type(NbtTagType::Long));
+//! ```
+
+use crate::error::{NbtError, NbtErrorKind};
+use crate::nbt_path::{self, child_path, element_path, NbtMatch};
+use crate::nbt_tag::*;
+
+use regex::Regex;
+use std::borrow::Cow;
+
+#[cfg(test)]
+mod tests;
+
+/// What the walk does after a visitor call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Walk {
+    /// Visits the children of the tag, then its siblings.
+    Continue,
+    /// Does not visit the children of the tag.
+    Skip,
+    /// Ends the walk.
+    Stop,
+}
+
+/// Conditions a tag must meet to be found by `search`. Conditions that are not set match
+/// every tag.
+#[derive(Clone, Debug, Default)]
+pub struct TagPredicate {
+    key: Option<String>,
+    ty: Option<NbtTagType>,
+    value: Option<NbtTag>,
+    regex: Option<Regex>,
+}
+
+impl TagPredicate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The name of the tag, which is its key in a compound, matches a glob where `*` stands
+    /// for any run of characters and `?` for one character. List elements have no name.
+    pub fn with_key(mut self, glob: &str) -> Self {
+        self.key = Some(glob.to_string());
+        self
+    }
+
+    pub fn with_type(mut self, ty: NbtTagType) -> Self {
+        self.ty = Some(ty);
+        self
+    }
+
+    /// The tag contains `value` as in the filters of NBT paths: scalars are equal, compounds
+    /// hold at least the keys of `value`, see `nbt_path::contains`.
+    pub fn with_value(mut self, value: NbtTag) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// The tag is a `String` with a match of the regex, e.g. `diamond` or `^minecraft:.*_ore$`.
+    pub fn with_regex(mut self, pattern: &str) -> Result<Self, NbtError> {
+        let regex = Regex::new(pattern).map_err(|e| NbtError::new(NbtErrorKind::InvalidRegex(e.to_string()), 0))?;
+        self.regex = Some(regex);
+        Ok(self)
+    }
+
+    /// Tests a tag found under `key` in its compound, `""` for list elements and roots.
+    pub fn matches(&self, key: &str, tag: &NbtTag) -> bool {
+        self.key.as_deref().is_none_or(|glob| glob_matches(glob, key))
+            && self.ty.is_none_or(|ty| tag.ty() == ty)
+            && self.value.as_ref().is_none_or(|value| nbt_path::contains(tag, value))
+            && self.regex.as_ref().is_none_or(|regex| tag.as_str().is_some_and(|text| regex.is_match(text)))
+    }
+}
+
+/// Visits `tag`, at `path` under `key`, then its children. Returns true when the walk was
+/// stopped. The visitor gets the path, the key and the tag; tags do not know their keys.
+pub(crate) fn walk_tag<'a>(path: &str, key: &str, tag: &'a NbtTag, visitor: &mut dyn FnMut(&str, &str, &'a NbtTag) -> Walk) -> bool {
+    match visitor(path, key, tag) {
+        Walk::Continue => {}
+        Walk::Skip => return false,
+        Walk::Stop => return true,
+    }
+
+    match tag {
+        NbtTag::Compound(compound) => walk_compound(path, compound, visitor),
+        NbtTag::List(list) => list.values.iter().enumerate().any(|(index, value)| walk_tag(&element_path(path, index), "", value, visitor)),
+        _ => false,
+    }
+}
+
+/// Walks the children of a compound found at `path`.
+pub(crate) fn walk_compound<'a>(path: &str, compound: &'a NbtTagCompound, visitor: &mut dyn FnMut(&str, &str, &'a NbtTag) -> Walk) -> bool {
+    compound.values.iter().any(|(key, value)| walk_tag(&child_path(path, key), key, value, visitor))
+}
+
+/// Collects the tags matching `predicate` in a walk.
+pub(crate) fn search_with<'a>(predicate: &TagPredicate, walk: impl FnOnce(&mut dyn FnMut(&str, &str, &'a NbtTag) -> Walk)) -> Vec<NbtMatch<'a>> {
+    let mut found = Vec::new();
+    walk(&mut |path, key, tag| {
+        if predicate.matches(key, tag) {
+            found.push(NbtMatch { path: path.to_string(), tag: Cow::Borrowed(tag) });
+        }
+        Walk::Continue
+    });
+    found
+}
+
+/// Matches `text` against a glob with `*` and `?`.
+fn glob_matches(glob: &str, text: &str) -> bool {
+    let (glob, text): (Vec<char>, Vec<char>) = (glob.chars().collect(), text.chars().collect());
+    // Position after the last `*` in the glob, and where its run ends in the text
+    let mut star: Option<(usize, usize)> = None;
+    let (mut g, mut t) = (0, 0);
+
+    while t < text.len() {
+        match glob.get(g) {
+            Some('*') => {
+                star = Some((g + 1, t));
+                g += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                g += 1;
+                t += 1;
+            }
+            _ => match star {
+                // Let the last star take one more character
+                Some((after, run_end)) => {
+                    star = Some((after, run_end + 1));
+                    g = after;
+                    t = run_end + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    glob[g..].iter().all(|c| *c == '*')
+}
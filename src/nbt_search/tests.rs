@@ -0,0 +1,82 @@
+#[cfg(test)]
+
+use super::*;
+use crate::nbt;
+use crate::nbt_path::tests::match_paths;
+
+#[test]
+fn test_walk_order_and_paths() {
+    let tag = NbtTag::Compound(nbt!{ "a": { "b": 1 }, "l": [[2], []], "arr": [I; 1, 2] });
+    let mut visited = Vec::new();
+
+    tag.walk(&mut |path, tag| {
+        visited.push(format!("{} {:?}", path, tag.ty()));
+        Walk::Continue
+    });
+
+    assert_eq!(visited, vec![" Compound", "a Compound", "a.b Int", "l List", "l[0] List", "l[0][0] Int", "l[1] List", "arr IntArray"]);
+}
+
+#[test]
+fn test_walk_skip_and_stop() {
+    let tag = NbtTag::Compound(nbt!{
+        "id": "minecraft:chest",
+        "Items": [{ "Slot": 0i8, "Count": 1b }, { "Slot": 1b, "Count": 64b }],
+        "Grid": [[]],
+    });
+
+    let mut visited = Vec::new();
+    tag.walk(&mut |path, tag| {
+        visited.push(path.to_string());
+        if tag.ty() == NbtTagType::List { Walk::Skip } else { Walk::Continue }
+    });
+    assert_eq!(visited, vec!["", "id", "Items", "Grid"]);
+
+    let mut first_count = None;
+    tag.walk(&mut |path, _| match path.ends_with("Count") {
+        true => {
+            first_count = Some(path.to_string());
+            Walk::Stop
+        }
+        false => Walk::Continue,
+    });
+    assert_eq!(first_count.as_deref(), Some("Items[0].Count"));
+}
+
+#[test]
+fn test_search_by_regex_and_type() {
+    let tag = NbtTag::Compound(nbt!{
+        "id": "minecraft:chest",
+        "Items": [{ "id": "minecraft:diamond_sword" }, { "id": "minecraft:stone", "tag": { "Lore": ["a diamond inside"] } }],
+        "Grid": [[{ "id": "minecraft:diamond" }], []],
+    });
+    let predicate = TagPredicate::new().with_type(NbtTagType::String).with_regex("diamond").unwrap();
+
+    assert_eq!(match_paths(&tag.search(&predicate)), vec!["Items[0].id", "Items[1].tag.Lore[0]", "Grid[0][0].id"]);
+    assert!(matches!(TagPredicate::new().with_regex("(").unwrap_err().kind, NbtErrorKind::InvalidRegex(_)));
+}
+
+#[test]
+fn test_search_by_key_and_value() {
+    let tag = NbtTag::Compound(nbt!{
+        "LastUpdate": 120L,
+        "Items": [{ "Slot": 0i8, "Count": 1b }, { "Slot": 1b, "Count": 64b, "tag": {} }],
+        "Grid": [[{ "id": "minecraft:diamond" }]],
+    });
+
+    assert_eq!(match_paths(&tag.search(&TagPredicate::new().with_key("LastUpdate").with_type(NbtTagType::Long))), vec!["LastUpdate"]);
+    assert_eq!(match_paths(&tag.search(&TagPredicate::new().with_key("?lot"))), vec!["Items[0].Slot", "Items[1].Slot"]);
+    assert_eq!(match_paths(&tag.search(&TagPredicate::new().with_key("*Up*"))), vec!["LastUpdate"]);
+    assert_eq!(match_paths(&tag.search(&TagPredicate::new().with_value(NbtTag::from_snbt("{Count:64b}").unwrap()))), vec!["Items[1]"]);
+    assert_eq!(match_paths(&tag.as_compound().unwrap().search(&TagPredicate::new().with_type(NbtTagType::Compound))), vec!["Items[0]", "Items[1]", "Items[1].tag", "Grid[0][0]"]);
+}
+
+#[test]
+fn test_glob() {
+    assert!(glob_matches("*", ""));
+    assert!(glob_matches("a*b*c", "axxbyyc"));
+    assert!(glob_matches("*ab", "aab"));
+    assert!(glob_matches("x?z", "xyz"));
+    assert!(!glob_matches("x?z", "xz"));
+    assert!(!glob_matches("a*b", "acbd"));
+}
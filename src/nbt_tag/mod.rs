@@ -5,29 +5,61 @@
 // - 2023-12-17
 //
 // ## File Version
-// - 1.0.2
+// - 1.0.18
 //
 // ## Changelog
 // - 1.0.0: Initial version [caelunshun:2019-07-09]
 // - 1.0.1: Splitted the file_parser logic from the nbt_tag logic [mrmarkolinus:2023-12-17]
 // - 1.0.2: Added support for json-nbt bidirectional conversion [mrmarkolinus:2023-12-17]
-
-use byteorder::{BigEndian, WriteBytesExt};
-use std::collections::HashMap;
-use std::io::Write;
+// - 1.0.3: Encode names and strings as Java Modified UTF-8 [mrmarkolinus:2026-10-16]
+// - 1.0.4: SNBT conversion [mrmarkolinus:2026-10-16]
+// - 1.0.5: Writer supports the little-endian Bedrock flavor [mrmarkolinus:2026-10-16]
+// - 1.0.6: Writer supports the network flavors [mrmarkolinus:2026-10-16]
+// - 1.0.7: write delegates to NbtWriter, fixed ty() of LongArray, tags are PartialEq [mrmarkolinus:2026-10-16]
+// - 1.0.8: NbtTagCompound::save [mrmarkolinus:2026-10-16]
+// - 1.0.9: Compounds keep the insertion order of their keys [mrmarkolinus:2026-10-16]
+// - 1.0.10: Typed and plain JSON schemas, from_json detects the schema [mrmarkolinus:2026-10-16]
+// - 1.0.11: NbtTag::query with NBT paths [mrmarkolinus:2026-10-16]
+// - 1.0.12: Mutation API, borrowed and mutable accessors, set and remove by path [mrmarkolinus:2026-10-16]
+// - 1.0.13: nbt_tag::diff [mrmarkolinus:2026-10-16]
+// - 1.0.14: NbtTagCompound::merge with /data merge semantics [mrmarkolinus:2026-10-16]
+// - 1.0.15: validate against an nbt_schema [mrmarkolinus:2026-10-16]
+// - 1.0.16: walk, search and NbtTagType::from_name [mrmarkolinus:2026-10-16]
+// - 1.0.17: Tags no longer store their name, compound keys are the only copy [mrmarkolinus:2026-10-16]
+// - 1.0.18: as_*, as_*_mut and into_* for every variant, scalar getters [mrmarkolinus:2026-10-16]
+
+pub use indexmap::IndexMap;
+use indexmap::map::Entry;
 use serde::{Serialize, Deserialize};
 use std::fs;
-use std::io::{self, BufWriter, BufReader};
+use std::io::{self, BufWriter, BufReader, Write};
 use derive_new::new;
+use crate::snbt;
+use crate::nbt_path::{NbtMatch, NbtPath};
+use crate::nbt_diff::{self, NbtChange};
+use crate::nbt_schema::{NbtSchema, SchemaViolation};
+use crate::nbt_search::{self, TagPredicate, Walk};
+use crate::error::NbtError;
+use crate::nbt_writer::NbtWriter;
+use crate::generic_bin::{self, CompressionType};
+use crate::json::{self, JsonFormat};
 
 #[cfg(test)]
 mod tests;
 
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+/// A compound tag. Keys keep the order in which they were inserted, which for a parsed
+/// file is the order of the file, so writing it back reproduces the original bytes and
+/// JSON exports are stable. Removing a key with `shift_remove` keeps the others in order.
+///
+/// Tags have no name of their own: the name of a tag is its key in the parent compound,
+/// stored once. `name` is the name of a root compound, as written in a file header; it is
+/// empty for nested compounds. The `Serialize` and `Deserialize` impls keep the JSON shape
+/// of the `serde` format, see the `json` module.
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct NbtTagCompound {
     pub name: String,
-    pub values: HashMap<String, NbtTag>,
+    pub values: IndexMap<String, NbtTag>,
 }
 
 
@@ -35,25 +67,119 @@ impl NbtTagCompound {
     pub fn new(name: &str) -> Self {
         Self {
             name: name.to_string(),
-            values: HashMap::new(),
+            values: IndexMap::new(),
         }
     }
 
-/*     pub fn get(&self, name: &str) -> Option<NbtTag> {
-        self.values.get(name).cloned()
+    pub fn get(&self, name: &str) -> Option<&NbtTag> {
+        self.values.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut NbtTag> {
+        self.values.get_mut(name)
+    }
+
+    /// Inserts `value` under `name` and returns the tag it replaces. A replaced key keeps its
+    /// position.
+    pub fn insert(&mut self, name: &str, value: NbtTag) -> Option<NbtTag> {
+        self.values.insert(name.to_string(), value)
     }
 
+    /// Like `insert`, discarding the replaced tag.
     pub fn set(&mut self, name: &str, value: NbtTag) {
-        self.values.insert(name.to_string(), value);
-    } */
+        self.insert(name, value);
+    }
+
+    /// Removes the tag under `name`. The other keys keep their order.
+    pub fn remove(&mut self, name: &str) -> Option<NbtTag> {
+        self.values.shift_remove(name)
+    }
+
+    /// Entry for in-place insertion or update, e.g.
+    /// `compound.entry("Count").or_insert(NbtTag::Byte(NbtTagByte::new(1)))`.
+    pub fn entry(&mut self, name: &str) -> NbtEntry<'_> {
+        NbtEntry { entry: self.values.entry(name.to_string()) }
+    }
+
+    /// Value of the int tag under `name`, `None` if it is missing or of another type.
+    pub fn get_i32(&self, name: &str) -> Option<i32> {
+        self.get(name).and_then(NbtTag::as_i32)
+    }
+
+    /// Value of the string tag under `name`, `None` if it is missing or of another type.
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(NbtTag::as_str)
+    }
+
+    /// Merges `other` into the compound like `/data merge`: compounds present on both sides
+    /// are merged recursively, every other value of `other` is added or replaces the current
+    /// one. Lists are replaced, not concatenated.
+    pub fn merge(&mut self, other: &NbtTagCompound) {
+        for (key, value) in other.values.iter() {
+            match (self.values.get_mut(key), value) {
+                (Some(NbtTag::Compound(current)), NbtTag::Compound(value)) => current.merge(value),
+                _ => self.set(key, value.clone()),
+            }
+        }
+    }
+
+    /// Sets every tag selected by an NBT path, creating missing compounds along the way, and
+    /// returns how many were set, see `NbtPath::set`. E.g.
+    /// `item.set_at("display.Name", NbtTag::from_snbt("'\"Sword\"'")?)`.
+    pub fn set_at(&mut self, path: &str, tag: NbtTag) -> Result<usize, NbtError> {
+        NbtPath::parse(path)?.set_compound(self, tag)
+    }
+
+    /// Removes every tag selected by an NBT path and returns how many were removed.
+    pub fn remove_at(&mut self, path: &str) -> Result<usize, NbtError> {
+        NbtPath::parse(path)?.remove_compound(self)
+    }
+
+    /// Calls `visitor` on every tag below the compound, with its path, see `NbtTag::walk`.
+    /// The compound itself is not visited.
+    pub fn walk<'a>(&'a self, visitor: &mut dyn FnMut(&str, &'a NbtTag) -> Walk) {
+        nbt_search::walk_compound("", self, &mut |path, _, tag| visitor(path, tag));
+    }
+
+    /// Returns the tags below the compound that match `predicate`, in tree order.
+    pub fn search(&self, predicate: &TagPredicate) -> Vec<NbtMatch<'_>> {
+        nbt_search::search_with(predicate, |visitor| {
+            nbt_search::walk_compound("", self, visitor);
+        })
+    }
+
+    /// Checks the compound against a schema and returns every violation, see `nbt_schema`.
+    pub fn validate(&self, schema: &NbtSchema) -> Vec<SchemaViolation> {
+        schema.validate_compound(self)
+    }
+
+    /// Writes the compound as compact SNBT, e.g. `{Count:1b,id:"minecraft:stone"}`.
+    pub fn to_snbt(&self) -> String {
+        snbt::to_snbt(&NbtTag::Compound(self.clone()), false)
+    }
+
+    /// Saves the compound as a Java NBT file, e.g. `save("out.nbt", CompressionType::Gzip, 6)`.
+    /// `level` goes from 0 (fastest) to 9 (smallest) and is ignored without compression.
+    /// The file is replaced atomically, a failed save leaves the previous content in place.
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P, compression: CompressionType, level: u32) -> io::Result<()> {
+        let mut data = Vec::new();
+        write(&mut data, self)?;
+        generic_bin::write_atomic(path, |writer| generic_bin::compress_into(writer, &data, compression, level))
+    }
 
     pub fn to_json<P: AsRef<std::path::Path>>(&self, path: P) -> io::Result<()> {
+        self.to_json_with_format(path, JsonFormat::Serde)
+    }
+
+    /// Writes the compound as JSON in the given schema, see the `json` module.
+    pub fn to_json_with_format<P: AsRef<std::path::Path>>(&self, path: P, format: JsonFormat) -> io::Result<()> {
         // Open a file for writing.
         let file = fs::File::create(path)?;
-        let writer = BufWriter::new(file); // Using a BufWriter for more efficient writes.
+        let mut writer = BufWriter::new(file); // Using a BufWriter for more efficient writes.
 
         // Write the pretty-printed JSON to the file.
-        serde_json::to_writer_pretty(writer, &self)?;
+        json::to_writer(&mut writer, self, format)?;
+        writer.flush()?;
         
         Ok(())
     }
@@ -70,16 +196,24 @@ impl NbtTagCompound {
     }
  */
 
+    /// Reads a JSON file in any of the schemas of the `json` module, detected from the
+    /// shape of the root.
     pub fn from_json<P: AsRef<std::path::Path>>(path: P) -> Result<Self, io::Error> {
+        let value = Self::read_json_value(path)?;
+        let format = JsonFormat::detect(&value);
+        Ok(json::from_value(value, format)?)
+    }
 
+    pub fn from_json_with_format<P: AsRef<std::path::Path>>(path: P, format: JsonFormat) -> Result<Self, io::Error> {
+        let value = Self::read_json_value(path)?;
+        Ok(json::from_value(value, format)?)
+    }
+
+    fn read_json_value<P: AsRef<std::path::Path>>(path: P) -> io::Result<serde_json::Value> {
         let file = fs::File::open(path)?;
         let reader = BufReader::new(file); // Wrap the file in a BufReader, since very large file are expected.
 
-        // Deserialize the JSON data directly from the stream.
-        let deserialized_nbt = serde_json::from_reader(reader)?;
-        
-        Ok(deserialized_nbt)
-
+        Ok(serde_json::from_reader(reader)?)
     }
 
     /* pub fn from_json(&self, path: String) -> PyResult<Self> {
@@ -94,6 +228,37 @@ impl NbtTagCompound {
     } */
 }
 
+/// A key of a compound, vacant or occupied, see `NbtTagCompound::entry`.
+pub struct NbtEntry<'a> {
+    entry: Entry<'a, String, NbtTag>,
+}
+
+impl<'a> NbtEntry<'a> {
+    pub fn key(&self) -> &str {
+        self.entry.key()
+    }
+
+    /// Returns the tag under the key, inserting `default` first if there is none.
+    pub fn or_insert(self, default: NbtTag) -> &'a mut NbtTag {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> NbtTag>(self, default: F) -> &'a mut NbtTag {
+        match self.entry {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Runs `f` on the tag under the key, if any.
+    pub fn and_modify<F: FnOnce(&mut NbtTag)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self.entry {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
 /// Represents the type of an NBT (Named Binary Tag) tag.
 ///
 /// NBT is a tag-based binary format used to store structured data.
@@ -117,6 +282,29 @@ pub enum NbtTagType {
     LongArray,
 }
 
+impl NbtTagType {
+    /// Parses the names of the variants, e.g. `Long` or `IntArray`, as used by the Python API.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let ty = match name {
+            "End" => NbtTagType::End,
+            "Byte" => NbtTagType::Byte,
+            "Short" => NbtTagType::Short,
+            "Int" => NbtTagType::Int,
+            "Long" => NbtTagType::Long,
+            "Float" => NbtTagType::Float,
+            "Double" => NbtTagType::Double,
+            "ByteArray" => NbtTagType::ByteArray,
+            "String" => NbtTagType::String,
+            "List" => NbtTagType::List,
+            "Compound" => NbtTagType::Compound,
+            "IntArray" => NbtTagType::IntArray,
+            "LongArray" => NbtTagType::LongArray,
+            _ => return None,
+        };
+        Some(ty)
+    }
+}
+
 impl Default for NbtTagType {
     fn default() -> Self {
         NbtTagType::End
@@ -124,7 +312,7 @@ impl Default for NbtTagType {
 }
 
 impl NbtTagType {
-    fn id(&self) -> u8 {
+    pub fn id(&self) -> u8 {
         match self {
             NbtTagType::End => 0,
             NbtTagType::Byte => 1,
@@ -166,7 +354,7 @@ impl NbtTagType {
 ///
 /// This enum encapsulates all possible NBT tags, each variant holding
 /// data corresponding to its type.
-#[derive(Clone, new, Debug, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, PartialEq)]
 pub enum NbtTag {
     End,
     Byte(NbtTagByte),
@@ -191,6 +379,48 @@ impl Default for NbtTag {
 
 impl NbtTag {
 
+    /// Writes the tag as SNBT on a single line, as accepted by commands like `/give`.
+    pub fn to_snbt(&self) -> String {
+        snbt::to_snbt(self, false)
+    }
+
+    /// Writes the tag as SNBT indented over several lines, for reading.
+    pub fn to_snbt_pretty(&self) -> String {
+        snbt::to_snbt(self, true)
+    }
+
+    /// Parses SNBT text such as `{Count:1b,id:"minecraft:stone"}`.
+    pub fn from_snbt(text: &str) -> Result<NbtTag, NbtError> {
+        snbt::from_snbt(text)
+    }
+
+    /// Returns the tags selected by an NBT path such as `sections[].block_states.palette[0]`,
+    /// with their concrete paths. Parse the path once with `NbtPath::parse` to run it on
+    /// many trees.
+    pub fn query(&self, path: &str) -> Result<Vec<NbtMatch<'_>>, NbtError> {
+        Ok(NbtPath::parse(path)?.query(self))
+    }
+
+    /// Calls `visitor` on the tag and everything below it, in tree order, with the NBT path
+    /// of each tag. The root has an empty path. Return `Walk::Skip` to leave out the children
+    /// of a tag and `Walk::Stop` to end the walk.
+    pub fn walk<'a>(&'a self, visitor: &mut dyn FnMut(&str, &'a NbtTag) -> Walk) {
+        nbt_search::walk_tag("", "", self, &mut |path, _, tag| visitor(path, tag));
+    }
+
+    /// Returns the tags matching `predicate`, the tag itself included, in tree order. The
+    /// tag itself has no key.
+    pub fn search(&self, predicate: &TagPredicate) -> Vec<NbtMatch<'_>> {
+        nbt_search::search_with(predicate, |visitor| {
+            nbt_search::walk_tag("", "", self, visitor);
+        })
+    }
+
+    /// Checks the tag against a schema and returns every violation, see `nbt_schema`.
+    pub fn validate(&self, schema: &NbtSchema) -> Vec<SchemaViolation> {
+        schema.validate(self)
+    }
+
     pub fn ty(&self) -> NbtTagType {
         match &self {
             NbtTag::End => NbtTagType::End,
@@ -205,340 +435,185 @@ impl NbtTag {
             NbtTag::List(_) => NbtTagType::List,
             NbtTag::Compound(_) => NbtTagType::Compound,
             NbtTag::IntArray(_) => NbtTagType::IntArray,
-            NbtTag::LongArray(_) => NbtTagType::End,
+            NbtTag::LongArray(_) => NbtTagType::LongArray,
         }
     } 
 
-    pub fn byte(&self) -> Option<NbtTagByte> {
-        if let NbtTag::Byte(x) = self {
-            Some(x.clone())
-        } else {
-            None
-        }
-    }
-
-    pub fn short(&self) -> Option<NbtTagShort> {
-        if let NbtTag::Short(x) = self {
-            Some(x.clone())
-        } else {
-            None
-        }
-    }
-
-    pub fn int(&self) -> Option<NbtTagInt> {
-        if let NbtTag::Int(x) = self {
-            Some(x.clone())
-        } else {
-            None
-        }
-    }
-
-    pub fn long(&self) -> Option<NbtTagLong> {
-        if let NbtTag::Long(x) = self {
-            Some(x.clone())
-        } else {
-            None
-        }
-    }
-
-    pub fn float(&self) -> Option<NbtTagFloat> {
-        if let NbtTag::Float(x) = self {
-            Some(x.clone())
-        } else {
-            None
-        }
-    }
-
-    pub fn double(&self) -> Option<NbtTagDouble> {
-        if let NbtTag::Double(x) = self {
-            Some(x.clone())
-        } else {
-            None
-        }
-    }
-
-    pub fn byte_array(&self) -> Option<NbtTagByteArray> {
-        if let NbtTag::ByteArray(x) = self {
-            Some(x.clone())
-        } else {
-            None
-        }
-    }
-
-    pub fn string(&self) -> Option<NbtTagString> {
-        if let NbtTag::String(x) = self {
-            Some(x.clone())
-        } else {
-            None
-        }
+    /// Same as `as_list`.
+    pub fn list_as_ref(&self) -> Option<&NbtTagList> {
+        self.as_list()
     }
 
-    pub fn list(&self) -> Option<NbtTagList> {
-        if let NbtTag::List(x) = self {
-            Some(x.clone())
-        } else {
-            None
-        }
+    /// Same as `as_compound`.
+    pub fn compound_as_ref(&self) -> Option<&NbtTagCompound> {
+        self.as_compound()
     }
 
-    pub fn list_as_ref(&self) -> Option<&NbtTagList> {
-        if let NbtTag::List(x) = self {
-            Some(x)
-        } else {
-            None
-        }
+    /// Same as `as_long_array`.
+    pub fn long_array_as_ref(&self) -> Option<&NbtTagLongArray> {
+        self.as_long_array()
     }
 
-    pub fn compound(&self) -> Option<NbtTagCompound> {
-        if let NbtTag::Compound(x) = self {
-            Some(x.clone())
-        } else {
-            None
-        }
+    pub fn as_str(&self) -> Option<&str> {
+        self.as_string().map(|x| x.value.as_str())
     }
 
-    pub fn compound_as_ref(&self) -> Option<&NbtTagCompound> {
-        if let NbtTag::Compound(x) = self {
-            Some(x)
-        } else {
-            None
-        }
-    }
+}
 
-    pub fn int_array(&self) -> Option<NbtTagIntArray> {
-        if let NbtTag::IntArray(x) = self {
-            Some(x.clone())
-        } else {
-            None
+/// Accessors for each variant but `End`: `as_*` borrows the tag, `as_*_mut` borrows it
+/// mutably and `into_*` consumes it. The older `byte()`, `list()`, … return a copy.
+macro_rules! variant_accessors {
+    ($($variant:ident($tag:ident): $copy:ident, $as_ref:ident, $as_mut:ident, $into:ident;)*) => {
+        impl NbtTag {
+            $(
+                #[doc = concat!("A copy of the tag, including everything below it. `", stringify!($as_ref), "` and `", stringify!($into), "` do not copy.")]
+                pub fn $copy(&self) -> Option<$tag> {
+                    self.$as_ref().cloned()
+                }
+
+                pub fn $as_ref(&self) -> Option<&$tag> {
+                    match self {
+                        NbtTag::$variant(x) => Some(x),
+                        _ => None,
+                    }
+                }
+
+                pub fn $as_mut(&mut self) -> Option<&mut $tag> {
+                    match self {
+                        NbtTag::$variant(x) => Some(x),
+                        _ => None,
+                    }
+                }
+
+                pub fn $into(self) -> Option<$tag> {
+                    match self {
+                        NbtTag::$variant(x) => Some(x),
+                        _ => None,
+                    }
+                }
+            )*
         }
-    }
+    };
+}
 
-    pub fn long_array(&self) -> Option<NbtTagLongArray> {
-        if let NbtTag::LongArray(x) = self {
-            Some(x.clone())
-        } else {
-            None
-        }
-    }
+variant_accessors! {
+    Byte(NbtTagByte): byte, as_byte, as_byte_mut, into_byte;
+    Short(NbtTagShort): short, as_short, as_short_mut, into_short;
+    Int(NbtTagInt): int, as_int, as_int_mut, into_int;
+    Long(NbtTagLong): long, as_long, as_long_mut, into_long;
+    Float(NbtTagFloat): float, as_float, as_float_mut, into_float;
+    Double(NbtTagDouble): double, as_double, as_double_mut, into_double;
+    ByteArray(NbtTagByteArray): byte_array, as_byte_array, as_byte_array_mut, into_byte_array;
+    String(NbtTagString): string, as_string, as_string_mut, into_string;
+    List(NbtTagList): list, as_list, as_list_mut, into_list;
+    Compound(NbtTagCompound): compound, as_compound, as_compound_mut, into_compound;
+    IntArray(NbtTagIntArray): int_array, as_int_array, as_int_array_mut, into_int_array;
+    LongArray(NbtTagLongArray): long_array, as_long_array, as_long_array_mut, into_long_array;
+}
 
-    pub fn long_array_as_ref(&self) -> Option<&NbtTagLongArray> {
-        if let NbtTag::LongArray(x) = self {
-            Some(x)
-        } else {
-            None
+/// Values of numeric tags. Each getter only reads its own type: `as_i32` is `None` for a
+/// `Byte` or a `Long`.
+macro_rules! scalar_getters {
+    ($($getter:ident: $variant:ident => $ty:ty;)*) => {
+        impl NbtTag {
+            $(
+                pub fn $getter(&self) -> Option<$ty> {
+                    match self {
+                        NbtTag::$variant(x) => Some(x.value),
+                        _ => None,
+                    }
+                }
+            )*
         }
-    }
+    };
+}
 
+scalar_getters! {
+    as_i8: Byte => i8;
+    as_i16: Short => i16;
+    as_i32: Int => i32;
+    as_i64: Long => i64;
+    as_f32: Float => f32;
+    as_f64: Double => f64;
 }
 
 
 
-#[derive(Clone, new, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, Default, PartialEq)]
 pub struct NbtTagByte {
-    pub name: String,
     pub value: i8,
 }
 
 
-#[derive(Clone, new, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, Default, PartialEq)]
 pub struct NbtTagShort {
-    pub name: String,
     pub value: i16,
 }
 
 
-#[derive(Clone, new, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, Default, PartialEq)]
 pub struct NbtTagInt {
-    pub name: String,
     pub value: i32,
 }
 
 
-#[derive(Clone, new, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, Default, PartialEq)]
 pub struct NbtTagLong {
-    pub name: String,
     pub value: i64,
 }
 
 
-#[derive(Clone, new, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, Default, PartialEq)]
 pub struct NbtTagFloat {
-    pub name: String,
     pub value: f32,
 }
 
 
-#[derive(Clone, new, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, Default, PartialEq)]
 pub struct NbtTagDouble {
-    pub name: String,
     pub value: f64,
 }
 
 
-#[derive(Clone, new, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, Default, PartialEq)]
 pub struct NbtTagByteArray {
-    pub name: String,
     pub values: Vec<i8>,
 }
 
 
-#[derive(Clone, new, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, Default, PartialEq)]
 pub struct NbtTagString {
-    pub name: String,
     pub value: String,
 }
 
 
-#[derive(Clone, new, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, Default, PartialEq)]
 pub struct NbtTagList {
-    pub name: String,
     pub ty: NbtTagType,
     pub values: Vec<NbtTag>,
 }
 
 
-#[derive(Clone, new, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, Default, PartialEq)]
 pub struct NbtTagIntArray {
-    pub name: String,
     pub values: Vec<i32>,
 }
 
 
-#[derive(Clone, new, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, new, Debug, Default, PartialEq)]
 pub struct NbtTagLongArray {
-    pub name: String,
     pub values: Vec<i64>,
 }
 
 
-pub fn write(buf: &mut Vec<u8>, compound: &NbtTagCompound) {
-    write_tag_type(buf, NbtTagType::Compound);
-    write_tag_name(buf, &compound.name);
-    write_compound(buf, compound);
-}
-
-fn write_compound(buf: &mut Vec<u8>, compound: &NbtTagCompound) {
-    for val in compound.values.values() {
-        write_value(buf, val, true);
-    }
-}
-
-fn write_value(buf: &mut Vec<u8>, value: &NbtTag, write_name: bool) {
-    let ty = value.ty();
-    write_tag_type(buf, ty);
-
-    match value {
-        NbtTag::End => (),
-        NbtTag::Byte(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-            buf.write_i8(val.value).unwrap();
-        }
-        NbtTag::Short(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-            buf.write_i16::<BigEndian>(val.value).unwrap();
-        }
-        NbtTag::Int(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-            buf.write_i32::<BigEndian>(val.value).unwrap();
-        }
-        NbtTag::Long(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-            buf.write_i64::<BigEndian>(val.value).unwrap();
-        }
-        NbtTag::Float(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-            buf.write_f32::<BigEndian>(val.value).unwrap();
-        }
-        NbtTag::Double(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-            buf.write_f64::<BigEndian>(val.value).unwrap();
-        }
-        NbtTag::ByteArray(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-
-            buf.write_i16::<BigEndian>(val.values.len() as i16).unwrap();
-            buf.reserve(val.values.len());
-
-            for x in &val.values {
-                buf.write_i8(*x).unwrap();
-            }
-        }
-        NbtTag::String(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-
-            buf.write_u16::<BigEndian>(val.value.len() as u16).unwrap();
-            buf.write(val.value.as_bytes()).unwrap();
-        }
-        NbtTag::List(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-
-            write_tag_type(buf, val.ty);
-            buf.write_i32::<BigEndian>(val.values.len() as i32).unwrap();
-
-            for val in &val.values {
-                // Finally, an actual application of recursion
-                write_value(buf, val, false);
-            }
-        }
-        NbtTag::Compound(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-
-            write_compound(buf, val);
-        }
-        NbtTag::IntArray(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-
-            buf.write_i32::<BigEndian>(val.values.len() as i32).unwrap();
-
-            buf.reserve(val.values.len());
-
-            for x in &val.values {
-                buf.write_i32::<BigEndian>(*x).unwrap();
-            }
-        }
-        NbtTag::LongArray(val) => {
-            if write_name {
-                write_tag_name(buf, &val.name);
-            }
-
-            buf.write_i32::<BigEndian>(val.values.len() as i32).unwrap();
-
-            buf.reserve(val.values.len());
-
-            for x in &val.values {
-                buf.write_i64::<BigEndian>(*x).unwrap();
-            }
-        }
-    }
-}
-
-fn write_tag_name(buf: &mut Vec<u8>, s: &str) {
-    buf.write_i16::<BigEndian>(s.len() as i16).unwrap();
-    buf.write(s.as_bytes()).unwrap();
+/// Compares two trees by value and returns what changed from `old` to `new`. See
+/// `nbt_diff::diff_with_options` for a float tolerance and matching lists by key.
+pub fn diff(old: &NbtTag, new: &NbtTag) -> Vec<NbtChange> {
+    nbt_diff::diff(old, new)
 }
 
-fn write_tag_type(buf: &mut Vec<u8>, ty: NbtTagType) {
-    buf.write_u8(ty.id()).unwrap();
+/// Appends the compound to `buf` as Java binary NBT. Use `NbtWriter` for other writers
+/// and flavors.
+pub fn write(buf: &mut Vec<u8>, compound: &NbtTagCompound) -> io::Result<()> {
+    NbtWriter::new(buf).write_compound(compound)
 }
@@ -37,3 +37,110 @@ fn test_nbt_tag_type_from_id() {
     assert_eq!(NbtTagType::from_id(255), None); // Test an invalid ID
 }
 
+fn item() -> NbtTagCompound {
+    NbtTag::from_snbt("{id:\"minecraft:stone\",Count:1b,tag:{Damage:3,Tags:[\"a\",\"b\"]}}").unwrap().compound().unwrap()
+}
+
+#[test]
+fn test_compound_accessors() {
+    let compound = item();
+
+    assert_eq!(compound.get_str("id"), Some("minecraft:stone"));
+    assert_eq!(compound.get_str("Count"), None);
+    assert_eq!(compound.get("tag").and_then(NbtTag::as_compound).and_then(|tag| tag.get_i32("Damage")), Some(3));
+    assert_eq!(compound.get_i32("missing"), None);
+}
+
+#[test]
+fn test_insert_keeps_order() {
+    let mut compound = item();
+
+    let previous = compound.insert("Count", NbtTag::Byte(NbtTagByte::new(5)));
+    assert_eq!(previous.unwrap().byte().unwrap().value, 1);
+    assert_eq!(compound.get("Count"), Some(&NbtTag::Byte(NbtTagByte::new(5))));
+    assert_eq!(compound.values.keys().collect::<Vec<_>>(), vec!["id", "Count", "tag"]);
+
+    compound.set("new", NbtTag::Int(NbtTagInt::new(7)));
+    assert_eq!(compound.get_i32("new"), Some(7));
+
+    assert!(compound.remove("Count").is_some());
+    assert_eq!(compound.values.keys().collect::<Vec<_>>(), vec!["id", "tag", "new"]);
+}
+
+#[test]
+fn test_mutable_accessors() {
+    let mut compound = item();
+
+    let tags = compound.get_mut("tag").and_then(NbtTag::as_compound_mut).unwrap().get_mut("Tags").and_then(NbtTag::as_list_mut).unwrap();
+    tags.values.push(NbtTag::String(NbtTagString::new("c".to_string())));
+
+    assert_eq!(compound.to_snbt(), "{id:\"minecraft:stone\",Count:1b,tag:{Damage:3,Tags:[\"a\",\"b\",\"c\"]}}");
+}
+
+#[test]
+fn test_entry() {
+    let mut compound = item();
+
+    let count = compound.entry("Count").or_insert(NbtTag::Byte(NbtTagByte::new(9)));
+    assert_eq!(count.byte().unwrap().value, 1);
+
+    let slot = compound.entry("Slot").or_insert(NbtTag::Byte(NbtTagByte::new(2)));
+    assert_eq!(slot.byte().unwrap().value, 2);
+
+    compound.entry("Count").and_modify(|tag| *tag = NbtTag::Byte(NbtTagByte::new(4)));
+    assert_eq!(compound.get("Count"), Some(&NbtTag::Byte(NbtTagByte::new(4))));
+}
+
+#[test]
+fn test_set_and_remove_at() {
+    let mut compound = item();
+
+    assert_eq!(compound.set_at("tag.display.Name", NbtTag::from_snbt("\"Rock\"").unwrap()).unwrap(), 1);
+    assert_eq!(compound.remove_at("tag.Tags[0]").unwrap(), 1);
+    assert_eq!(compound.to_snbt(), "{id:\"minecraft:stone\",Count:1b,tag:{Damage:3,Tags:[\"b\"],display:{Name:\"Rock\"}}}");
+
+    let display = compound.get("tag").and_then(NbtTag::as_compound).unwrap().get("display").unwrap();
+    assert_eq!(display.as_compound().unwrap().name, "");
+    assert_eq!(display.as_compound().unwrap().get("Name"), Some(&NbtTag::from("Rock")));
+}
+
+#[test]
+fn test_merge() {
+    let mut compound = item();
+
+    compound.merge(&NbtTag::from_snbt("{Count:2b,tag:{Damage:4,Unbreakable:1b,Tags:[\"z\"]}}").unwrap().compound().unwrap());
+
+    assert_eq!(compound.to_snbt(), "{id:\"minecraft:stone\",Count:2b,tag:{Damage:4,Tags:[\"z\"],Unbreakable:1b}}");
+    assert_eq!(compound.get("tag").and_then(NbtTag::as_compound).unwrap().get("Unbreakable"), Some(&NbtTag::from(true)));
+}
+
+#[test]
+fn test_borrowing_and_consuming_accessors() {
+    let mut tag = NbtTag::Compound(item());
+
+    let tags = tag.as_compound().and_then(|c| c.get("tag")).and_then(NbtTag::as_compound).and_then(|c| c.get("Tags")).and_then(NbtTag::as_list).unwrap();
+    assert_eq!(tags.values[0].as_string().map(|s| s.value.as_str()), Some("a"));
+    assert_eq!(tags.values[0].as_str(), Some("a"));
+    assert_eq!(tags.values[0].as_int(), None);
+
+    let count = tag.as_compound_mut().and_then(|c| c.get_mut("Count")).and_then(NbtTag::as_byte_mut).unwrap();
+    count.value = 7;
+    assert_eq!(tag.as_compound().and_then(|c| c.get("Count")).and_then(NbtTag::as_i8), Some(7));
+
+    let compound = tag.into_compound().unwrap();
+    assert_eq!(compound.get_str("id"), Some("minecraft:stone"));
+    assert_eq!(NbtTag::from(vec![1i64, 2]).into_long_array().map(|a| a.values), Some(vec![1, 2]));
+    assert_eq!(NbtTag::from(5).into_string(), None);
+}
+
+#[test]
+fn test_scalar_getters_read_one_type() {
+    assert_eq!(NbtTag::from(1i8).as_i8(), Some(1));
+    assert_eq!(NbtTag::from(2i16).as_i16(), Some(2));
+    assert_eq!(NbtTag::from(3).as_i32(), Some(3));
+    assert_eq!(NbtTag::from(4i64).as_i64(), Some(4));
+    assert_eq!(NbtTag::from(0.5f32).as_f32(), Some(0.5));
+    assert_eq!(NbtTag::from(0.25).as_f64(), Some(0.25));
+    assert_eq!(NbtTag::from(1i8).as_i32(), None);
+    assert_eq!(NbtTag::from(3).as_i64(), None);
+}
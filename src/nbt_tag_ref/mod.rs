@@ -0,0 +1,542 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.1
+//
+// ## Changelog
+// - 1.0.0: Initial version, zero-copy borrowed NBT tags [mrmarkolinus:2026-10-16]
+// - 1.0.1: Owned copies no longer carry a name per tag [mrmarkolinus:2026-10-16]
+
+//! Read-only NBT tags borrowing from the (uncompressed) input buffer.
+//!
+//! `parse` checks the structure of the whole document once, without allocating, and returns
+//! the root compound. Names and strings are kept as raw Modified UTF-8 and decoded on demand,
+//! arrays are big-endian slices decoded element by element on access, and compounds are
+//! searched by scanning their entries. Nothing is copied until asked for, which makes this
+//! the cheapest way to pick a few values out of many chunks:
+//!
+//! ```ignore
+//! let bytes = region.read_and_decompress_chunk(index)?;
+//! let chunk = nbt_tag_ref::parse(&bytes)?;
+//! if let Some(NbtTagRef::List(sections)) = chunk.get("sections") {
+//!     for section in sections.iter() { /* ... */ }
+//! }
+//! ```
+
+use crate::error::{NbtError, NbtErrorKind};
+use crate::file_parser::ParseLimits;
+use crate::mutf8;
+use crate::nbt_tag::*;
+
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+
+#[cfg(test)]
+mod tests;
+
+/// Parses a document with the default limits and returns its root compound.
+pub fn parse(bytes: &[u8]) -> Result<NbtCompoundRef<'_>, NbtError> {
+    parse_named(bytes, &ParseLimits::default()).map(|(_, root)| root)
+}
+
+/// Parses a document and returns the name of the root tag along with the root compound.
+/// Only nesting depth and lengths are checked against `limits`, nothing is allocated.
+pub fn parse_named<'a>(bytes: &'a [u8], limits: &ParseLimits) -> Result<(NbtStrRef<'a>, NbtCompoundRef<'a>), NbtError> {
+    let mut scanner = Scanner::new(bytes, limits);
+
+    let ty = scanner.read_tag_type()?;
+    if ty != NbtTagType::Compound {
+        return Err(NbtError::new(NbtErrorKind::InvalidRoot(ty), 0));
+    }
+    let name = scanner.read_str()?;
+
+    let start = scanner.pos;
+    scanner.skip_payload(NbtTagType::Compound)?;
+
+    Ok((name, NbtCompoundRef { data: &bytes[start..scanner.pos] }))
+}
+
+/// A tag borrowing its content from the input buffer. Names are not part of the tag: they
+/// are returned by the compound that holds it.
+#[derive(Clone, Copy, Debug)]
+pub enum NbtTagRef<'a> {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(NbtArrayRef<'a, i8>),
+    String(NbtStrRef<'a>),
+    List(NbtListRef<'a>),
+    Compound(NbtCompoundRef<'a>),
+    IntArray(NbtArrayRef<'a, i32>),
+    LongArray(NbtArrayRef<'a, i64>),
+}
+
+impl<'a> NbtTagRef<'a> {
+    pub fn ty(&self) -> NbtTagType {
+        match self {
+            NbtTagRef::Byte(_) => NbtTagType::Byte,
+            NbtTagRef::Short(_) => NbtTagType::Short,
+            NbtTagRef::Int(_) => NbtTagType::Int,
+            NbtTagRef::Long(_) => NbtTagType::Long,
+            NbtTagRef::Float(_) => NbtTagType::Float,
+            NbtTagRef::Double(_) => NbtTagType::Double,
+            NbtTagRef::ByteArray(_) => NbtTagType::ByteArray,
+            NbtTagRef::String(_) => NbtTagType::String,
+            NbtTagRef::List(_) => NbtTagType::List,
+            NbtTagRef::Compound(_) => NbtTagType::Compound,
+            NbtTagRef::IntArray(_) => NbtTagType::IntArray,
+            NbtTagRef::LongArray(_) => NbtTagType::LongArray,
+        }
+    }
+
+    pub fn as_compound(&self) -> Option<NbtCompoundRef<'a>> {
+        match self {
+            NbtTagRef::Compound(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<NbtListRef<'a>> {
+        match self {
+            NbtTagRef::List(l) => Some(*l),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<NbtStrRef<'a>> {
+        match self {
+            NbtTagRef::String(s) => Some(*s),
+            _ => None,
+        }
+    }
+
+    pub fn as_long_array(&self) -> Option<NbtArrayRef<'a, i64>> {
+        match self {
+            NbtTagRef::LongArray(a) => Some(*a),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of any integer tag (byte, short, int or long) widened to `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            NbtTagRef::Byte(v) => Some(*v as i64),
+            NbtTagRef::Short(v) => Some(*v as i64),
+            NbtTagRef::Int(v) => Some(*v as i64),
+            NbtTagRef::Long(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Copies the tag into an owned `NbtTag`.
+    pub fn to_owned_tag(&self) -> NbtTag {
+        match self {
+            NbtTagRef::Byte(v) => NbtTag::Byte(NbtTagByte::new(*v)),
+            NbtTagRef::Short(v) => NbtTag::Short(NbtTagShort::new(*v)),
+            NbtTagRef::Int(v) => NbtTag::Int(NbtTagInt::new(*v)),
+            NbtTagRef::Long(v) => NbtTag::Long(NbtTagLong::new(*v)),
+            NbtTagRef::Float(v) => NbtTag::Float(NbtTagFloat::new(*v)),
+            NbtTagRef::Double(v) => NbtTag::Double(NbtTagDouble::new(*v)),
+            NbtTagRef::ByteArray(a) => NbtTag::ByteArray(NbtTagByteArray::new(a.to_vec())),
+            NbtTagRef::String(s) => NbtTag::String(NbtTagString::new(s.to_str().into_owned())),
+            NbtTagRef::List(l) => NbtTag::List(NbtTagList::new(l.element_type(), l.iter().map(|v| v.to_owned_tag()).collect())),
+            NbtTagRef::Compound(c) => NbtTag::Compound(c.to_owned_compound("")),
+            NbtTagRef::IntArray(a) => NbtTag::IntArray(NbtTagIntArray::new(a.to_vec())),
+            NbtTagRef::LongArray(a) => NbtTag::LongArray(NbtTagLongArray::new(a.to_vec())),
+        }
+    }
+}
+
+/// A name or string value, kept as raw Modified UTF-8.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NbtStrRef<'a>(&'a [u8]);
+
+impl<'a> NbtStrRef<'a> {
+    /// Decodes the string. Plain ASCII and most UTF-8 text is returned without copying.
+    pub fn to_str(&self) -> Cow<'a, str> {
+        mutf8::decode(self.0)
+    }
+
+    /// The raw Modified UTF-8 bytes.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+}
+
+impl PartialEq<str> for NbtStrRef<'_> {
+    fn eq(&self, other: &str) -> bool {
+        mutf8::encode(other).as_ref() == self.0
+    }
+}
+
+impl PartialEq<&str> for NbtStrRef<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl fmt::Debug for NbtStrRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_str(), f)
+    }
+}
+
+/// Element types of NBT arrays, decoded from big-endian bytes.
+pub trait BigEndianElement: Copy + 'static {
+    const SIZE: usize;
+    fn from_be_slice(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_big_endian_element {
+    ($($t:ty),*) => {
+        $(
+            impl BigEndianElement for $t {
+                const SIZE: usize = std::mem::size_of::<$t>();
+                fn from_be_slice(bytes: &[u8]) -> Self {
+                    <$t>::from_be_bytes(bytes.try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+impl_big_endian_element!(i8, i32, i64);
+
+/// A byte, int or long array borrowed from the input. Elements are decoded on access.
+#[derive(Clone, Copy)]
+pub struct NbtArrayRef<'a, T> {
+    data: &'a [u8],
+    marker: PhantomData<T>,
+}
+
+impl<'a, T: BigEndianElement> NbtArrayRef<'a, T> {
+    fn new(data: &'a [u8]) -> Self {
+        NbtArrayRef { data, marker: PhantomData }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len() / T::SIZE
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.data.get(index * T::SIZE..(index + 1) * T::SIZE).map(T::from_be_slice)
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = T> + 'a {
+        self.data.chunks_exact(T::SIZE).map(T::from_be_slice)
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+
+    /// The raw big-endian bytes.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+impl<T: BigEndianElement + fmt::Debug> fmt::Debug for NbtArrayRef<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// A list borrowed from the input.
+#[derive(Clone, Copy, Debug)]
+pub struct NbtListRef<'a> {
+    ty: NbtTagType,
+    len: usize,
+    data: &'a [u8],
+}
+
+impl<'a> NbtListRef<'a> {
+    pub fn element_type(&self) -> NbtTagType {
+        self.ty
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the element at `index`. Direct for numeric lists, a scan for the others.
+    pub fn get(&self, index: usize) -> Option<NbtTagRef<'a>> {
+        if index >= self.len {
+            return None;
+        }
+
+        match fixed_size(self.ty) {
+            Some(size) => Some(read_validated(&self.data[index * size..], self.ty).0),
+            None => self.iter().nth(index),
+        }
+    }
+
+    /// The raw bytes of the elements, without the list header.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = NbtTagRef<'a>> + 'a {
+        let (ty, mut data) = (self.ty, self.data);
+        (0..self.len).map(move |_| {
+            let (value, size) = read_validated(data, ty);
+            data = &data[size..];
+            value
+        })
+    }
+}
+
+/// A compound borrowed from the input. Lookups scan the entries, so when several keys are
+/// needed, a single pass with `iter` is cheaper than several `get`.
+#[derive(Clone, Copy, Debug)]
+pub struct NbtCompoundRef<'a> {
+    // Entries followed by the End tag
+    data: &'a [u8],
+}
+
+impl<'a> NbtCompoundRef<'a> {
+    pub fn get(&self, key: &str) -> Option<NbtTagRef<'a>> {
+        let key = mutf8::encode(key);
+        self.iter().find(|(name, _)| name.as_bytes() == key.as_ref()).map(|(_, value)| value)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Number of entries. Requires a scan.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data[0] == 0
+    }
+
+    /// The raw bytes of the entries, including the closing End tag.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Iterates over the entries in file order.
+    pub fn iter(&self) -> impl Iterator<Item = (NbtStrRef<'a>, NbtTagRef<'a>)> + 'a {
+        let mut data = self.data;
+        std::iter::from_fn(move || {
+            let ty = NbtTagType::from_id(data[0]).expect(VALIDATED);
+            if ty == NbtTagType::End {
+                return None;
+            }
+
+            let name_len = u16::from_be_bytes([data[1], data[2]]) as usize;
+            let name = NbtStrRef(&data[3..3 + name_len]);
+            let (value, size) = read_validated(&data[3 + name_len..], ty);
+            data = &data[3 + name_len + size..];
+
+            Some((name, value))
+        })
+    }
+
+    /// Copies the compound into an owned `NbtTagCompound` with the given root name.
+    pub fn to_owned_compound(&self, name: &str) -> NbtTagCompound {
+        let mut compound = NbtTagCompound::new(name);
+        for (key, value) in self.iter() {
+            let key = key.to_str().into_owned();
+            compound.values.insert(key, value.to_owned_tag());
+        }
+        compound
+    }
+}
+
+const VALIDATED: &str = "borrowed tags only exist for input validated by parse";
+
+fn fixed_size(ty: NbtTagType) -> Option<usize> {
+    match ty {
+        NbtTagType::Byte => Some(1),
+        NbtTagType::Short => Some(2),
+        NbtTagType::Int | NbtTagType::Float => Some(4),
+        NbtTagType::Long | NbtTagType::Double => Some(8),
+        _ => None,
+    }
+}
+
+/// Reads the payload of a tag of type `ty` at the start of `data`, returning the tag and
+/// the number of bytes it spans. `data` must come from a document validated by `parse`.
+fn read_validated(data: &[u8], ty: NbtTagType) -> (NbtTagRef<'_>, usize) {
+    let mut scanner = Scanner::new(data, &ParseLimits::unlimited());
+    let value = scanner.read_payload(ty).expect(VALIDATED);
+    (value, scanner.pos)
+}
+
+/// Cursor over the input that checks bounds, lengths and nesting.
+struct Scanner<'a> {
+    data: &'a [u8],
+    pos: usize,
+    limits: ParseLimits,
+    depth: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(data: &'a [u8], limits: &ParseLimits) -> Self {
+        Scanner { data, pos: 0, limits: *limits, depth: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], NbtError> {
+        let bytes = self.data.get(self.pos..self.pos.saturating_add(len))
+            .ok_or_else(|| NbtError::new(NbtErrorKind::UnexpectedEof, self.pos as u64))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn read_tag_type(&mut self) -> Result<NbtTagType, NbtError> {
+        let offset = self.pos as u64;
+        let id = self.take(1)?[0];
+        NbtTagType::from_id(id).ok_or_else(|| NbtError::new(NbtErrorKind::UnknownTagId(id), offset))
+    }
+
+    fn read_str(&mut self) -> Result<NbtStrRef<'a>, NbtError> {
+        let offset = self.pos as u64;
+        let bytes = self.take(2)?;
+        let len = u16::from_be_bytes([bytes[0], bytes[1]]) as usize;
+        if len > self.limits.max_string_len {
+            return Err(NbtError::new(NbtErrorKind::LimitExceeded { limit: "string length", value: len as u64, max: self.limits.max_string_len as u64 }, offset));
+        }
+
+        Ok(NbtStrRef(self.take(len)?))
+    }
+
+    fn read_length(&mut self, limit: &'static str) -> Result<usize, NbtError> {
+        let offset = self.pos as u64;
+        let bytes = self.take(4)?;
+        let len = i32::from_be_bytes(bytes.try_into().unwrap());
+        if len < 0 {
+            return Err(NbtError::new(NbtErrorKind::NegativeLength(len), offset));
+        }
+        if len as usize > self.limits.max_array_len {
+            return Err(NbtError::new(NbtErrorKind::LimitExceeded { limit, value: len as u64, max: self.limits.max_array_len as u64 }, offset));
+        }
+
+        Ok(len as usize)
+    }
+
+    fn enter(&mut self) -> Result<(), NbtError> {
+        self.depth += 1;
+        if self.depth > self.limits.max_depth {
+            let (value, max) = (self.depth as u64, self.limits.max_depth as u64);
+            return Err(NbtError::new(NbtErrorKind::LimitExceeded { limit: "nesting depth", value, max }, self.pos as u64));
+        }
+
+        Ok(())
+    }
+
+    fn read_array<T: BigEndianElement>(&mut self, limit: &'static str) -> Result<NbtArrayRef<'a, T>, NbtError> {
+        let len = self.read_length(limit)?;
+        Ok(NbtArrayRef::new(self.take(len.saturating_mul(T::SIZE))?))
+    }
+
+    fn read_list_header(&mut self) -> Result<(NbtTagType, usize), NbtError> {
+        let ty = self.read_tag_type()?;
+        let offset = self.pos as u64;
+        let len = self.read_length("list length")?;
+        if ty == NbtTagType::End && len > 0 {
+            return Err(NbtError::new(NbtErrorKind::InvalidListType(ty), offset));
+        }
+
+        Ok((ty, len))
+    }
+
+    /// Reads one payload. Compounds and lists are skipped over and returned as views.
+    fn read_payload(&mut self, ty: NbtTagType) -> Result<NbtTagRef<'a>, NbtError> {
+        let start = self.pos;
+        Ok(match ty {
+            NbtTagType::List => {
+                let (elem_ty, len) = self.read_list_header()?;
+                let start = self.pos;
+                self.skip_elements(elem_ty, len)?;
+                NbtTagRef::List(NbtListRef { ty: elem_ty, len, data: &self.data[start..self.pos] })
+            }
+            NbtTagType::Compound => {
+                self.skip_entries()?;
+                NbtTagRef::Compound(NbtCompoundRef { data: &self.data[start..self.pos] })
+            }
+            _ => self.read_leaf(ty)?,
+        })
+    }
+
+    fn read_leaf(&mut self, ty: NbtTagType) -> Result<NbtTagRef<'a>, NbtError> {
+        Ok(match ty {
+            NbtTagType::Byte => NbtTagRef::Byte(self.take(1)?[0] as i8),
+            NbtTagType::Short => NbtTagRef::Short(i16::from_be_bytes(self.take(2)?.try_into().unwrap())),
+            NbtTagType::Int => NbtTagRef::Int(i32::from_be_bytes(self.take(4)?.try_into().unwrap())),
+            NbtTagType::Long => NbtTagRef::Long(i64::from_be_bytes(self.take(8)?.try_into().unwrap())),
+            NbtTagType::Float => NbtTagRef::Float(f32::from_be_bytes(self.take(4)?.try_into().unwrap())),
+            NbtTagType::Double => NbtTagRef::Double(f64::from_be_bytes(self.take(8)?.try_into().unwrap())),
+            NbtTagType::ByteArray => NbtTagRef::ByteArray(self.read_array("byte array length")?),
+            NbtTagType::String => NbtTagRef::String(self.read_str()?),
+            NbtTagType::IntArray => NbtTagRef::IntArray(self.read_array("int array length")?),
+            NbtTagType::LongArray => NbtTagRef::LongArray(self.read_array("long array length")?),
+            NbtTagType::End | NbtTagType::List | NbtTagType::Compound => {
+                return Err(NbtError::new(NbtErrorKind::InvalidListType(ty), self.pos as u64));
+            }
+        })
+    }
+
+    /// Moves past one payload. Only lists and compounds recurse, keeping the frames small
+    /// for deeply nested input.
+    fn skip_payload(&mut self, ty: NbtTagType) -> Result<(), NbtError> {
+        match ty {
+            NbtTagType::List => {
+                let (elem_ty, len) = self.read_list_header()?;
+                self.skip_elements(elem_ty, len)
+            }
+            NbtTagType::Compound => self.skip_entries(),
+            _ => self.read_leaf(ty).map(|_| ()),
+        }
+    }
+
+    fn skip_entries(&mut self) -> Result<(), NbtError> {
+        self.enter()?;
+        loop {
+            let ty = self.read_tag_type()?;
+            if ty == NbtTagType::End {
+                break;
+            }
+
+            let name = self.read_str()?;
+            self.skip_payload(ty).map_err(|e| e.in_key(&name.to_str()))?;
+        }
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn skip_elements(&mut self, ty: NbtTagType, len: usize) -> Result<(), NbtError> {
+        self.enter()?;
+        match fixed_size(ty) {
+            Some(size) => {
+                self.take(len.saturating_mul(size))?;
+            }
+            None => {
+                for index in 0..len {
+                    self.skip_payload(ty).map_err(|e| e.in_index(index))?;
+                }
+            }
+        }
+        self.depth -= 1;
+        Ok(())
+    }
+}
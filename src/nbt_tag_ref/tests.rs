@@ -0,0 +1,89 @@
+#[cfg(test)]
+
+use super::*;
+use crate::file_parser;
+
+/// `{n: "x", l: [{v: 1}, {}], s: [1s, 2s], a: [L; 3, -4]}`
+fn sample_bytes() -> Vec<u8> {
+    let mut bytes = vec![
+        10, 0, 4, b'r', b'o', b'o', b't',
+            8, 0, 1, b'n', 0, 1, b'x',
+            9, 0, 1, b'l', 10, 0, 0, 0, 2,
+                3, 0, 1, b'v', 0, 0, 0, 1, 0,
+                0,
+            9, 0, 1, b's', 2, 0, 0, 0, 2, 0, 1, 0, 2,
+            12, 0, 1, b'a', 0, 0, 0, 2,
+    ];
+    bytes.extend_from_slice(&3i64.to_be_bytes());
+    bytes.extend_from_slice(&(-4i64).to_be_bytes());
+    bytes.push(0);
+    bytes
+}
+
+#[test]
+fn test_lookup() {
+    let bytes = sample_bytes();
+    let (name, root) = parse_named(&bytes, &ParseLimits::default()).unwrap();
+
+    assert_eq!(name, "root");
+    assert_eq!(root.len(), 4);
+    assert_eq!(root.get("n").unwrap().as_str().unwrap(), "x");
+    assert!(root.get("missing").is_none());
+
+    let list = root.get("l").unwrap().as_list().unwrap();
+    assert_eq!(list.element_type(), NbtTagType::Compound);
+    assert_eq!(list.get(0).unwrap().as_compound().unwrap().get("v").unwrap().as_i64(), Some(1));
+    assert!(list.get(1).unwrap().as_compound().unwrap().is_empty());
+    assert!(list.get(2).is_none());
+
+    let shorts = root.get("s").unwrap().as_list().unwrap();
+    assert_eq!(shorts.get(1).unwrap().as_i64(), Some(2));
+
+    let array = root.get("a").unwrap().as_long_array().unwrap();
+    assert_eq!(array.len(), 2);
+    assert_eq!(array.get(1), Some(-4));
+    assert_eq!(array.to_vec(), vec![3, -4]);
+}
+
+#[test]
+fn test_matches_owned_parser() {
+    let bytes = sample_bytes();
+    let borrowed = parse(&bytes).unwrap().to_owned_compound("root");
+    let owned = file_parser::parse_bytes(&bytes).unwrap();
+
+    assert_eq!(serde_json::to_value(NbtTag::Compound(borrowed)).unwrap(), serde_json::to_value(owned).unwrap());
+}
+
+#[test]
+fn test_errors() {
+    let truncated = [
+        10, 0, 1, b'r',
+            9, 0, 1, b'l', 10, 0, 0, 0, 2,
+                3, 0, 1, b'v', 0, 0, 0, 1, 0,
+                3, 0, 1, b'v', 0, 0,
+    ];
+    let error = parse(&truncated).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::UnexpectedEof));
+    assert_eq!(error.offset, 26);
+    assert_eq!(error.path(), "l[1].v");
+
+    let error = parse(&[8, 0, 0, 0, 0]).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::InvalidRoot(NbtTagType::String)));
+
+    // Array longer than the input
+    let error = parse(&[10, 0, 0, 12, 0, 1, b'a', 0, 1, 0, 0, 0]).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::UnexpectedEof));
+    assert_eq!(error.path(), "a");
+}
+
+#[test]
+fn test_default_depth_limit() {
+    let mut bytes = vec![10, 0, 0, 9, 0, 1, b'l'];
+    for _ in 1..100_000 {
+        bytes.extend_from_slice(&[9, 0, 0, 0, 1]);
+    }
+    bytes.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+
+    let error = parse(&bytes).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::LimitExceeded { limit: "nesting depth", .. }));
+}
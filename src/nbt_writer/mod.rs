@@ -0,0 +1,267 @@
+// ## Author
+// - caelunshun, mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.0
+//
+// ## Changelog
+// - 1.0.0: NbtWriter over any io::Write, replaces the Vec based nbt_tag::write [mrmarkolinus:2026-10-16]
+
+//! Encodes `NbtTag` trees to binary NBT.
+//!
+//! Compound entries are written under their key in `NbtTagCompound::values`; only the
+//! `NbtTagCompound::name` of the root is written as a name. Lists must only hold elements of their
+//! declared type, and strings must fit in the 16-bit length prefix; anything else is an
+//! error rather than a file the game cannot load.
+
+use crate::error::{NbtError, NbtErrorKind};
+use crate::file_parser::ARRAY_BLOCK_SIZE;
+use crate::flavor::{Endianness, NbtFlavor};
+use crate::mutf8;
+use crate::nbt_tag::*;
+
+use std::io::{self, Write};
+
+#[cfg(test)]
+mod tests;
+
+/// Writes NBT to any writer. Each value is written with a few small writes, so pass a
+/// buffered writer (or a `Vec<u8>`).
+///
+/// ```ignore
+/// let mut writer = NbtWriter::new(BufWriter::new(File::create("level.dat")?));
+/// writer.write_compound(&root)?;
+/// ```
+pub struct NbtWriter<W: Write> {
+    writer: W,
+    flavor: NbtFlavor,
+    offset: u64,
+}
+
+impl<W: Write> NbtWriter<W> {
+    pub fn new(writer: W) -> Self {
+        NbtWriter { writer, flavor: NbtFlavor::Java, offset: 0 }
+    }
+
+    /// Selects the binary flavor, Java by default.
+    pub fn with_flavor(mut self, flavor: NbtFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    pub fn get_flavor(&self) -> NbtFlavor {
+        self.flavor
+    }
+
+    /// Number of bytes written so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes a root compound named after `compound.name`.
+    pub fn write_compound(&mut self, compound: &NbtTagCompound) -> io::Result<()> {
+        self.write_root_header(NbtTagType::Compound, &compound.name)
+            .and_then(|_| self.write_compound_values(compound))
+            .map_err(into_io_error)
+    }
+
+    /// Writes any tag as the root. The name is ignored by flavors with a nameless root.
+    pub fn write_tag(&mut self, name: &str, tag: &NbtTag) -> io::Result<()> {
+        if let NbtTag::End = tag {
+            return Err(into_io_error(NbtError::new(NbtErrorKind::EndTagValue, self.offset)));
+        }
+
+        self.write_root_header(tag.ty(), name)
+            .and_then(|_| self.write_payload(tag))
+            .map_err(into_io_error)
+    }
+
+    fn write_root_header(&mut self, ty: NbtTagType, name: &str) -> Result<(), NbtError> {
+        self.write_tag_type(ty)?;
+        if self.flavor.named_root() {
+            self.write_string(name)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_compound_values(&mut self, compound: &NbtTagCompound) -> Result<(), NbtError> {
+        for (key, value) in compound.values.iter() {
+            if let NbtTag::End = value {
+                return Err(NbtError::new(NbtErrorKind::EndTagValue, self.offset).in_key(key));
+            }
+
+            self.write_tag_type(value.ty()).map_err(|e| e.in_key(key))?;
+            self.write_string(key).map_err(|e| e.in_key(key))?;
+            self.write_payload(value).map_err(|e| e.in_key(key))?;
+        }
+
+        self.write_tag_type(NbtTagType::End)
+    }
+
+    fn write_list(&mut self, list: &NbtTagList) -> Result<(), NbtError> {
+        self.write_tag_type(list.ty)?;
+        self.write_length(list.values.len())?;
+
+        for (index, value) in list.values.iter().enumerate() {
+            let found = value.ty();
+            if found != list.ty || found == NbtTagType::End {
+                return Err(NbtError::new(NbtErrorKind::ListTypeMismatch { expected: list.ty, found }, self.offset).in_index(index));
+            }
+
+            self.write_payload(value).map_err(|e| e.in_index(index))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the value of a tag, without type and name.
+    fn write_payload(&mut self, tag: &NbtTag) -> Result<(), NbtError> {
+        // Only lists and compounds recurse, see Parser::parse_value
+        match tag {
+            NbtTag::List(list) => self.write_list(list),
+            NbtTag::Compound(compound) => self.write_compound_values(compound),
+            _ => self.write_leaf(tag),
+        }
+    }
+
+    fn write_leaf(&mut self, tag: &NbtTag) -> Result<(), NbtError> {
+        match tag {
+            NbtTag::End | NbtTag::List(_) | NbtTag::Compound(_) => unreachable!(), // Should already be covered
+            NbtTag::Byte(val) => self.write_all(&val.value.to_be_bytes()),
+            NbtTag::Short(val) => self.write_number(val.value.to_be_bytes(), val.value.to_le_bytes()),
+            NbtTag::Int(val) => self.write_i32(val.value),
+            NbtTag::Long(val) => self.write_i64(val.value),
+            NbtTag::Float(val) => self.write_number(val.value.to_be_bytes(), val.value.to_le_bytes()),
+            NbtTag::Double(val) => self.write_number(val.value.to_be_bytes(), val.value.to_le_bytes()),
+            NbtTag::ByteArray(val) => {
+                self.write_length(val.values.len())?;
+                self.write_array(&val.values, i8::to_be_bytes, i8::to_le_bytes)
+            }
+            NbtTag::String(val) => self.write_string(&val.value),
+            NbtTag::IntArray(val) if self.flavor.varint() => {
+                self.write_length(val.values.len())?;
+                val.values.iter().try_for_each(|x| self.write_i32(*x))
+            }
+            NbtTag::IntArray(val) => {
+                self.write_length(val.values.len())?;
+                self.write_array(&val.values, i32::to_be_bytes, i32::to_le_bytes)
+            }
+            NbtTag::LongArray(val) if self.flavor.varint() => {
+                self.write_length(val.values.len())?;
+                val.values.iter().try_for_each(|x| self.write_i64(*x))
+            }
+            NbtTag::LongArray(val) => {
+                self.write_length(val.values.len())?;
+                self.write_array(&val.values, i64::to_be_bytes, i64::to_le_bytes)
+            }
+        }
+    }
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), NbtError> {
+        let offset = self.offset;
+        self.writer.write_all(bytes).map_err(|e| NbtError::new(NbtErrorKind::Io(e), offset))?;
+        self.offset += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Writes fixed-size elements in blocks rather than one by one.
+    fn write_array<T: Copy, const N: usize>(&mut self, values: &[T], big: fn(T) -> [u8; N], little: fn(T) -> [u8; N]) -> Result<(), NbtError> {
+        let convert = match self.flavor.endianness() {
+            Endianness::Big => big,
+            Endianness::Little => little,
+        };
+
+        let mut block = Vec::with_capacity((values.len() * N).min(ARRAY_BLOCK_SIZE));
+        for chunk in values.chunks(ARRAY_BLOCK_SIZE / N) {
+            block.clear();
+            block.extend(chunk.iter().flat_map(|x| convert(*x)));
+            self.write_all(&block)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the big or little-endian representation, according to the flavor.
+    fn write_number<const N: usize>(&mut self, big: [u8; N], little: [u8; N]) -> Result<(), NbtError> {
+        match self.flavor.endianness() {
+            Endianness::Big => self.write_all(&big),
+            Endianness::Little => self.write_all(&little),
+        }
+    }
+
+    fn write_i32(&mut self, value: i32) -> Result<(), NbtError> {
+        if self.flavor.varint() {
+            return self.write_varint(((value << 1) ^ (value >> 31)) as u32 as u64);
+        }
+        self.write_number(value.to_be_bytes(), value.to_le_bytes())
+    }
+
+    fn write_i64(&mut self, value: i64) -> Result<(), NbtError> {
+        if self.flavor.varint() {
+            return self.write_varint(((value << 1) ^ (value >> 63)) as u64);
+        }
+        self.write_number(value.to_be_bytes(), value.to_le_bytes())
+    }
+
+    /// Writes an unsigned LEB128 varint, as used by network Bedrock NBT.
+    fn write_varint(&mut self, mut value: u64) -> Result<(), NbtError> {
+        let mut buf = [0u8; 10];
+        let mut len = 0;
+        while value >= 0x80 {
+            buf[len] = value as u8 | 0x80;
+            value >>= 7;
+            len += 1;
+        }
+        buf[len] = value as u8;
+
+        self.write_all(&buf[..=len])
+    }
+
+    /// Writes the `i32` length prefix of a list or array.
+    fn write_length(&mut self, len: usize) -> Result<(), NbtError> {
+        let len = i32::try_from(len).map_err(|_| {
+            NbtError::new(NbtErrorKind::LimitExceeded { limit: "length", value: len as u64, max: i32::MAX as u64 }, self.offset)
+        })?;
+        self.write_i32(len)
+    }
+
+    fn write_string(&mut self, s: &str) -> Result<(), NbtError> {
+        let bytes = match self.flavor.modified_utf8() {
+            true => mutf8::encode(s),
+            false => s.as_bytes().into(),
+        };
+
+        if self.flavor.varint() {
+            self.write_varint(bytes.len() as u64)?;
+        }
+        else {
+            let len = u16::try_from(bytes.len()).map_err(|_| {
+                NbtError::new(NbtErrorKind::LimitExceeded { limit: "string length", value: bytes.len() as u64, max: u16::MAX as u64 }, self.offset)
+            })?;
+            self.write_number(len.to_be_bytes(), len.to_le_bytes())?;
+        }
+
+        self.write_all(&bytes)
+    }
+
+    fn write_tag_type(&mut self, ty: NbtTagType) -> Result<(), NbtError> {
+        self.write_all(&[ty.id()])
+    }
+}
+
+/// Failures of the underlying writer are returned unchanged, invalid trees as an
+/// `InvalidData` error wrapping the `NbtError`.
+fn into_io_error(error: NbtError) -> io::Error {
+    match error.kind {
+        NbtErrorKind::Io(e) => e,
+        _ => error.into(),
+    }
+}
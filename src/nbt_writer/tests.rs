@@ -0,0 +1,189 @@
+#[cfg(test)]
+
+use super::*;
+use crate::error::NbtError;
+use crate::file_parser::{parse_bytes, parse_bytes_with_flavor, ParseLimits};
+
+fn compound_with(key: &str, value: NbtTag) -> NbtTagCompound {
+    let mut compound = NbtTagCompound::new("");
+    compound.values.insert(key.to_string(), value);
+    compound
+}
+
+fn write_to_vec(compound: &NbtTagCompound, flavor: NbtFlavor) -> io::Result<Vec<u8>> {
+    let mut writer = NbtWriter::new(Vec::new()).with_flavor(flavor);
+    writer.write_compound(compound)?;
+    Ok(writer.into_inner())
+}
+
+fn nbt_error(error: &io::Error) -> &NbtError {
+    error.get_ref().unwrap().downcast_ref::<NbtError>().unwrap()
+}
+
+#[test]
+fn test_write_compound_ends_with_end_tag() {
+    let nested = compound_with("v", NbtTag::Byte(NbtTagByte::new(1)));
+    let compound = compound_with("c", NbtTag::Compound(nested));
+
+    let bytes = write_to_vec(&compound, NbtFlavor::Java).unwrap();
+
+    assert_eq!(bytes, [10, 0, 0, 10, 0, 1, b'c', 1, 0, 1, b'v', 1, 0, 0]);
+}
+
+#[test]
+fn test_write_byte_array_length_is_i32() {
+    let compound = compound_with("a", NbtTag::ByteArray(NbtTagByteArray::new(vec![1, -1])));
+
+    let bytes = write_to_vec(&compound, NbtFlavor::Java).unwrap();
+
+    assert_eq!(bytes, [10, 0, 0, 7, 0, 1, b'a', 0, 0, 0, 2, 1, 0xFF, 0]);
+}
+
+#[test]
+fn test_write_long_array_type_id() {
+    let compound = compound_with("a", NbtTag::LongArray(NbtTagLongArray::new(vec![1])));
+
+    let bytes = write_to_vec(&compound, NbtFlavor::Java).unwrap();
+
+    assert_eq!(bytes, [10, 0, 0, 12, 0, 1, b'a', 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0]);
+    assert_eq!(parse_bytes(&bytes).unwrap(), NbtTag::Compound(compound));
+}
+
+#[test]
+fn test_write_uses_compound_keys() {
+    // The tag name disagrees with its key, the key wins
+    let compound = compound_with("key", NbtTag::Int(NbtTagInt::new(1)));
+
+    let bytes = write_to_vec(&compound, NbtFlavor::Java).unwrap();
+    let root = parse_bytes(&bytes).unwrap();
+
+    assert_eq!(root.compound_as_ref().unwrap().values.get("key").unwrap().int().unwrap().value, 1);
+}
+
+#[test]
+fn test_write_list_type_mismatch() {
+    let values = vec![
+        NbtTag::Int(NbtTagInt::new(1)),
+        NbtTag::Short(NbtTagShort::new(2)),
+    ];
+    let compound = compound_with("l", NbtTag::List(NbtTagList::new(NbtTagType::Int, values)));
+
+    let error = write_to_vec(&compound, NbtFlavor::Java).unwrap_err();
+    let error = nbt_error(&error);
+
+    assert!(matches!(error.kind, NbtErrorKind::ListTypeMismatch { expected: NbtTagType::Int, found: NbtTagType::Short }));
+    assert_eq!(error.path(), "l[1]");
+}
+
+#[test]
+fn test_write_end_typed_list_with_elements() {
+    let values = vec![NbtTag::Int(NbtTagInt::new(1))];
+    let compound = compound_with("l", NbtTag::List(NbtTagList::new(NbtTagType::End, values)));
+
+    let error = write_to_vec(&compound, NbtFlavor::Java).unwrap_err();
+
+    assert!(matches!(nbt_error(&error).kind, NbtErrorKind::ListTypeMismatch { expected: NbtTagType::End, .. }));
+}
+
+#[test]
+fn test_write_empty_list() {
+    let compound = compound_with("l", NbtTag::List(NbtTagList::new(NbtTagType::End, Vec::new())));
+
+    let bytes = write_to_vec(&compound, NbtFlavor::Java).unwrap();
+
+    assert_eq!(bytes, [10, 0, 0, 9, 0, 1, b'l', 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_write_string_too_long() {
+    let compound = compound_with("s", NbtTag::String(NbtTagString::new("x".repeat(65536))));
+
+    let error = write_to_vec(&compound, NbtFlavor::Java).unwrap_err();
+
+    assert!(matches!(nbt_error(&error).kind, NbtErrorKind::LimitExceeded { limit: "string length", .. }));
+    assert_eq!(nbt_error(&error).path(), "s");
+}
+
+#[test]
+fn test_write_end_value() {
+    let error = write_to_vec(&compound_with("e", NbtTag::End), NbtFlavor::Java).unwrap_err();
+
+    assert!(matches!(nbt_error(&error).kind, NbtErrorKind::EndTagValue));
+}
+
+#[test]
+fn test_write_io_error_is_returned_unchanged() {
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let error = NbtWriter::new(FailingWriter).write_compound(&NbtTagCompound::new("")).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn test_write_bedrock_flavor_is_little_endian() {
+    let compound = compound_with("v", NbtTag::Int(NbtTagInt::new(0x01020304)));
+
+    let java = write_to_vec(&compound, NbtFlavor::Java).unwrap();
+    let bedrock = write_to_vec(&compound, NbtFlavor::Bedrock).unwrap();
+
+    assert_eq!(java, [10, 0, 0, 3, 0, 1, b'v', 1, 2, 3, 4, 0]);
+    assert_eq!(bedrock, [10, 0, 0, 3, 1, 0, b'v', 4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn test_write_java_network_nameless_root() {
+    let tag = NbtTag::String(NbtTagString::new("hello".to_string()));
+
+    let mut writer = NbtWriter::new(Vec::new()).with_flavor(NbtFlavor::JavaNetwork);
+    writer.write_tag("ignored", &tag).unwrap();
+
+    assert_eq!(writer.into_inner(), [8, 0, 5, b'h', b'e', b'l', b'l', b'o']);
+}
+
+#[test]
+fn test_write_bedrock_network_varints() {
+    let compound = compound_with("l", NbtTag::Long(NbtTagLong::new(-150)));
+
+    let bytes = write_to_vec(&compound, NbtFlavor::BedrockNetwork).unwrap();
+
+    // -150 zigzags to 299
+    assert_eq!(bytes, [10, 0, 4, 1, b'l', 0xAB, 0x02, 0]);
+}
+
+#[test]
+fn test_write_parse_round_trip_all_flavors() {
+    let mut compound = NbtTagCompound::new("root");
+    compound.values.insert("b".to_string(), NbtTag::Byte(NbtTagByte::new(-1)));
+    compound.values.insert("s".to_string(), NbtTag::Short(NbtTagShort::new(-300)));
+    compound.values.insert("i".to_string(), NbtTag::Int(NbtTagInt::new(i32::MIN)));
+    compound.values.insert("l".to_string(), NbtTag::Long(NbtTagLong::new(i64::MAX)));
+    compound.values.insert("f".to_string(), NbtTag::Float(NbtTagFloat::new(0.5)));
+    compound.values.insert("d".to_string(), NbtTag::Double(NbtTagDouble::new(-2.25)));
+    compound.values.insert("t".to_string(), NbtTag::String(NbtTagString::new("a\0😀".to_string())));
+    compound.values.insert("ba".to_string(), NbtTag::ByteArray(NbtTagByteArray::new(vec![1, 2, -3])));
+    compound.values.insert("ia".to_string(), NbtTag::IntArray(NbtTagIntArray::new(vec![-1, 1 << 20])));
+    compound.values.insert("la".to_string(), NbtTag::LongArray(NbtTagLongArray::new(vec![i64::MIN, 7])));
+    let list = vec![NbtTag::Compound(NbtTagCompound::new("")), NbtTag::Compound(compound_with("x", NbtTag::Int(NbtTagInt::new(3))))];
+    compound.values.insert("l2".to_string(), NbtTag::List(NbtTagList::new(NbtTagType::Compound, list)));
+
+    for flavor in [NbtFlavor::Java, NbtFlavor::Bedrock, NbtFlavor::JavaNetwork, NbtFlavor::BedrockNetwork] {
+        let bytes = write_to_vec(&compound, flavor).unwrap();
+        let mut parsed = parse_bytes_with_flavor(&bytes, flavor, &ParseLimits::default()).unwrap().compound().unwrap();
+        if !flavor.named_root() {
+            parsed.name = compound.name.clone();
+        }
+
+        assert_eq!(parsed, compound, "{:?}", flavor);
+    }
+}
@@ -5,16 +5,24 @@
 // - 2023-12-17
 //
 // ## File Version
-// - 1.0.0
+// - 1.0.5
 //
 // ## Changelog
 // - 1.0.0: Initial version
+// - 1.0.1: Propagate NbtError instead of a generic parse error [mrmarkolinus:2026-10-16]
+// - 1.0.2: Configurable ParseLimits [mrmarkolinus:2026-10-16]
+// - 1.0.3: Chunks are decompressed and parsed as a stream [mrmarkolinus:2026-10-16]
+// - 1.0.4: Event reader over single chunks [mrmarkolinus:2026-10-16]
+// - 1.0.5: Chunks are moved out of their tags instead of copied [mrmarkolinus:2026-10-16]
 
+use crate::event_reader::NbtEventReader;
 use crate::file_parser;
+use crate::file_parser::ParseLimits;
 use crate::nbt_tag::*;
 use crate::generic_bin::*;
 
 use std::io;
+use std::io::{BufReader, Read};
 use std::path::PathBuf;
 
 const HEADER_LENGTH: usize = 4096;
@@ -51,11 +59,29 @@ impl RegionFile {
         Ok(region_file)
     }
 
+    /// Replaces the default limits used when the chunks are parsed.
+    pub fn with_limits(mut self, limits: ParseLimits) -> Self {
+        self.bin_content = self.bin_content.with_limits(limits);
+        self
+    }
+
     /// Returns the number of chunks in the region file.
     pub fn get_chunks_num(&self) -> usize {
         self.num_chunks
     }
 
+    /// Returns true if the chunk at `index` has been generated and is stored in the file.
+    pub fn has_chunk(&self, index: usize) -> bool {
+        self.chunk_offsets.get(index).is_some_and(|(offset, _)| *offset != 0)
+    }
+
+    /// Returns an event reader over the chunk at `index`, decompressed on the fly.
+    /// Much cheaper than `to_compounds_list` when only a few fields of each chunk are needed.
+    pub fn chunk_events(&self, index: usize) -> io::Result<NbtEventReader<BufReader<Box<dyn Read + '_>>>> {
+        let chunk_reader = BufReader::new(self.chunk_reader(index)?);
+        Ok(NbtEventReader::new(chunk_reader, self.bin_content.get_limits()))
+    }
+
     pub fn to_compounds_list(&self) -> std::io::Result<Vec<NbtTagCompound>> {
         let chunks_as_nbt = self.process_all_chunks()?;
         Ok(chunks_as_nbt)
@@ -88,17 +114,15 @@ impl RegionFile {
         let mut processed_chunks_list = Vec::new();
 
         for index in 0..self.num_chunks {
-            let (offset, _) = self.chunk_offsets[index];    
-            if offset == 0 { 
+            if !self.has_chunk(index) {
                 continue; // Skip if the chunk is not present
             }
             
-            let chunk_data = self.read_and_decompress_chunk(index)?;
-            let chunk_nbt = file_parser::parse_bytes(&chunk_data)
-                .map_err(|_| io::Error::new(io::ErrorKind::Other, "Parse error"))?;
+            let chunk_reader = BufReader::new(self.chunk_reader(index)?);
+            let chunk_nbt = file_parser::parse_reader(chunk_reader, self.bin_content.get_limits())?;
 
             //TODO: remove unwrap and handle errors
-            processed_chunks_list.push(chunk_nbt.compound().unwrap());
+            processed_chunks_list.push(chunk_nbt.into_compound().unwrap());
         }
 
         Ok(processed_chunks_list)
@@ -113,7 +137,19 @@ impl RegionFile {
     /// The fifth byte is the compression method (usually zlib)
     /// The rest x bytes (where x is the u32 of the first 4 bytes) are the actual chunk data, which is compressed.
     /// 
-    fn read_and_decompress_chunk(&self, index: usize) -> io::Result<Vec<u8>> {
+    pub fn read_and_decompress_chunk(&self, index: usize) -> io::Result<Vec<u8>> {
+        let (chunk_payload, chunk_compression_method) = self.chunk_payload(index)?;
+        self.bin_content.decode_binary_data(chunk_payload, chunk_compression_method)
+    }
+
+    /// Returns a reader that decompresses the chunk at `index` on the fly.
+    pub fn chunk_reader(&self, index: usize) -> io::Result<Box<dyn Read + '_>> {
+        let (chunk_payload, chunk_compression_method) = self.chunk_payload(index)?;
+        self.bin_content.decoder(chunk_payload, chunk_compression_method)
+    }
+
+    /// Returns the compressed payload of a chunk and its compression method byte.
+    fn chunk_payload(&self, index: usize) -> io::Result<(&[u8], &[u8])> {
         if index < self.chunk_offsets.len() {
             let (offset, size) = self.chunk_offsets[index];
             let raw_data = self.bin_content.get_raw_data();
@@ -121,17 +157,16 @@ impl RegionFile {
             if (offset as usize) < raw_data.len() && (offset as usize) + (size as usize) <= raw_data.len() {
                 let chunk_data = &raw_data[offset as usize..(offset as usize) + (size as usize)];
 
-                let real_chunk_len_slice = &chunk_data[..CHUNK_HEADER_LENGTH];
-
-                if real_chunk_len_slice.len() == 4 {
-                    let bytes = [real_chunk_len_slice[0], real_chunk_len_slice[1], real_chunk_len_slice[2], real_chunk_len_slice[3]];
+                if chunk_data.len() >= CHUNK_HEADER_COMPRESSION {
+                    let bytes = [chunk_data[0], chunk_data[1], chunk_data[2], chunk_data[3]];
                     
+                    // The length includes the compression method byte
                     let real_chunk_len = u32::from_be_bytes(bytes) as usize;
                     let chunk_compression_method = &chunk_data[CHUNK_HEADER_LENGTH..CHUNK_HEADER_COMPRESSION];
-                    let chunk_payload = &chunk_data[CHUNK_HEADER_COMPRESSION..CHUNK_HEADER_COMPRESSION + real_chunk_len];
+                    let chunk_payload = chunk_data.get(CHUNK_HEADER_COMPRESSION..CHUNK_HEADER_LENGTH + real_chunk_len)
+                        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Chunk length out of bounds"))?;
 
-                    //Self::decode_binary_data(chunk_payload, chunk_compression_method)
-                    self.bin_content.decode_binary_data(chunk_payload, chunk_compression_method)
+                    Ok((chunk_payload, chunk_compression_method))
                 }
                 else {
                     Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid or Unsupported chunk header length"))
@@ -146,4 +181,3 @@ impl RegionFile {
     }
     
 }
-
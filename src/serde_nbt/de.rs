@@ -0,0 +1,307 @@
+//! Deserializer working on the borrowed tags of `nbt_tag_ref`: the input is validated once,
+//! then values are decoded straight from the buffer as the target type asks for them.
+
+use super::{serde_error, BYTE_ARRAY_TOKEN, INT_ARRAY_TOKEN, LONG_ARRAY_TOKEN, TAG_TOKEN};
+use crate::error::{NbtError, NbtErrorKind};
+use crate::file_parser::ParseLimits;
+use crate::nbt_tag::NbtTagType;
+use crate::nbt_tag_ref::{self, BigEndianElement, NbtArrayRef, NbtCompoundRef, NbtStrRef, NbtTagRef};
+
+use serde::de::value::{BorrowedStrDeserializer, StringDeserializer};
+use serde::de::{self, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::forward_to_deserialize_any;
+use std::borrow::Cow;
+
+pub(super) fn from_bytes<'de, T: de::Deserialize<'de>>(bytes: &'de [u8], limits: &ParseLimits) -> Result<T, NbtError> {
+    let (_, root) = nbt_tag_ref::parse_named(bytes, limits)?;
+    T::deserialize(TagDeserializer { tag: NbtTagRef::Compound(root), input: bytes })
+}
+
+/// Errors raised by the target type carry no position. They get the offset of the compound
+/// or list holding the offending tag, before the path is added while unwinding.
+fn locate(mut error: NbtError, input: &[u8], data: &[u8]) -> NbtError {
+    if matches!(error.kind, NbtErrorKind::Serde(_)) && error.offset == 0 && error.path().is_empty() {
+        error.offset = (data.as_ptr() as usize).saturating_sub(input.as_ptr() as usize) as u64;
+    }
+    error
+}
+
+fn visit_str<'de, V: Visitor<'de>>(s: NbtStrRef<'de>, visitor: V) -> Result<V::Value, NbtError> {
+    match s.to_str() {
+        Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+        Cow::Owned(s) => visitor.visit_string(s),
+    }
+}
+
+struct TagDeserializer<'de> {
+    tag: NbtTagRef<'de>,
+    input: &'de [u8],
+}
+
+impl<'de> TagDeserializer<'de> {
+    fn invalid_type(&self, expected: &str) -> NbtError {
+        serde_error(format!("invalid type: {:?} tag, expected {}", self.tag.ty(), expected))
+    }
+
+    fn visit_array<V: Visitor<'de>, T>(&self, array: NbtArrayRef<'de, T>, visitor: V) -> Result<V::Value, NbtError>
+    where
+        T: BigEndianElement + IntoDeserializer<'de, NbtError>,
+    {
+        visitor.visit_seq(de::value::SeqDeserializer::new(array.iter()))
+    }
+}
+
+impl<'de> Deserializer<'de> for TagDeserializer<'de> {
+    type Error = NbtError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        match self.tag {
+            NbtTagRef::Byte(v) => visitor.visit_i8(v),
+            NbtTagRef::Short(v) => visitor.visit_i16(v),
+            NbtTagRef::Int(v) => visitor.visit_i32(v),
+            NbtTagRef::Long(v) => visitor.visit_i64(v),
+            NbtTagRef::Float(v) => visitor.visit_f32(v),
+            NbtTagRef::Double(v) => visitor.visit_f64(v),
+            NbtTagRef::String(s) => visit_str(s, visitor),
+            NbtTagRef::ByteArray(a) => self.visit_array(a, visitor),
+            NbtTagRef::IntArray(a) => self.visit_array(a, visitor),
+            NbtTagRef::LongArray(a) => self.visit_array(a, visitor),
+            NbtTagRef::List(list) => {
+                let input = self.input;
+                let seq = ListAccess { elements: Box::new(list.iter()), index: 0, len: list.len(), input, data: list.as_bytes() };
+                visitor.visit_seq(seq).map_err(|e| locate(e, input, list.as_bytes()))
+            }
+            NbtTagRef::Compound(compound) => {
+                let input = self.input;
+                let map = CompoundAccess::new(compound, input);
+                visitor.visit_map(map).map_err(|e| locate(e, input, compound.as_bytes()))
+            }
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        match self.tag {
+            NbtTagRef::Byte(v) => visitor.visit_bool(v != 0),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    // Unsigned integers are stored in the signed tag of the same width
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        match self.tag {
+            NbtTagRef::Byte(v) => visitor.visit_u8(v as u8),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        match self.tag {
+            NbtTagRef::Short(v) => visitor.visit_u16(v as u16),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        match self.tag {
+            NbtTagRef::Int(v) => visitor.visit_u32(v as u32),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        match self.tag {
+            NbtTagRef::Long(v) => visitor.visit_u64(v as u64),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        match self.tag {
+            NbtTagRef::String(s) => {
+                let s = s.to_str();
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => visitor.visit_char(c),
+                    _ => Err(serde_error(format!("invalid value: string {:?}, expected a single character", s))),
+                }
+            }
+            _ => Err(self.invalid_type("a character")),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        match self.tag {
+            NbtTagRef::ByteArray(a) => visitor.visit_borrowed_bytes(a.as_bytes()),
+            NbtTagRef::String(s) => visitor.visit_borrowed_bytes(s.as_bytes()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        // Absent tags are handled by serde as missing fields, a present tag is always Some
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, NbtError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V) -> Result<V::Value, NbtError> {
+        let expected = match name {
+            BYTE_ARRAY_TOKEN => NbtTagType::ByteArray,
+            INT_ARRAY_TOKEN => NbtTagType::IntArray,
+            LONG_ARRAY_TOKEN => NbtTagType::LongArray,
+            // `NbtTag` and `NbtTagCompound` read the tag back from network NBT
+            TAG_TOKEN => {
+                let bytes = super::tag_to_bytes(&self.tag.to_owned_tag()).map_err(|e| serde_error(e.to_string()))?;
+                return visitor.visit_byte_buf(bytes);
+            }
+            _ => return visitor.visit_newtype_struct(self),
+        };
+
+        if self.tag.ty() != expected {
+            return Err(self.invalid_type(&format!("a {:?} tag", expected)));
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, NbtError> {
+        match self.tag {
+            NbtTagRef::String(s) => {
+                let variant: Cow<'de, str> = s.to_str();
+                visitor.visit_enum(variant.into_owned().into_deserializer())
+            }
+            NbtTagRef::Compound(compound) => {
+                let mut entries = compound.iter();
+                match (entries.next(), entries.next()) {
+                    (Some((name, value)), None) => {
+                        let input = self.input;
+                        let access = EnumEntry { name, value: TagDeserializer { tag: value, input } };
+                        visitor.visit_enum(access).map_err(|e| locate(e, input, compound.as_bytes()))
+                    }
+                    _ => Err(serde_error("invalid value: enum compounds must have exactly one key".to_string())),
+                }
+            }
+            _ => Err(self.invalid_type("a string or a compound")),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        // The content was validated by parse, nothing to decode
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u128 f32 f64 str string seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct ListAccess<'de> {
+    elements: Box<dyn Iterator<Item = NbtTagRef<'de>> + 'de>,
+    index: usize,
+    len: usize,
+    input: &'de [u8],
+    data: &'de [u8],
+}
+
+impl<'de> SeqAccess<'de> for ListAccess<'de> {
+    type Error = NbtError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, NbtError> {
+        let Some(tag) = self.elements.next() else {
+            return Ok(None);
+        };
+
+        let index = self.index;
+        self.index += 1;
+        seed.deserialize(TagDeserializer { tag, input: self.input })
+            .map(Some)
+            .map_err(|e| locate(e, self.input, self.data).in_index(index))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+struct CompoundAccess<'de> {
+    entries: Box<dyn Iterator<Item = (NbtStrRef<'de>, NbtTagRef<'de>)> + 'de>,
+    value: Option<(NbtStrRef<'de>, NbtTagRef<'de>)>,
+    input: &'de [u8],
+    data: &'de [u8],
+}
+
+impl<'de> CompoundAccess<'de> {
+    fn new(compound: NbtCompoundRef<'de>, input: &'de [u8]) -> Self {
+        CompoundAccess { entries: Box::new(compound.iter()), value: None, input, data: compound.as_bytes() }
+    }
+}
+
+impl<'de> MapAccess<'de> for CompoundAccess<'de> {
+    type Error = NbtError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, NbtError> {
+        let Some((name, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+
+        self.value = Some((name, value));
+        let key = match name.to_str() {
+            Cow::Borrowed(s) => seed.deserialize(BorrowedStrDeserializer::new(s)),
+            Cow::Owned(s) => seed.deserialize(StringDeserializer::new(s)),
+        };
+        key.map(Some).map_err(|e| locate(e, self.input, self.data).in_key(&name.to_str()))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, NbtError> {
+        let (name, tag) = self.value.take().ok_or_else(|| serde_error("value requested before key".to_string()))?;
+        seed.deserialize(TagDeserializer { tag, input: self.input })
+            .map_err(|e| locate(e, self.input, self.data).in_key(&name.to_str()))
+    }
+}
+
+struct EnumEntry<'de> {
+    name: NbtStrRef<'de>,
+    value: TagDeserializer<'de>,
+}
+
+impl<'de> EnumAccess<'de> for EnumEntry<'de> {
+    type Error = NbtError;
+    type Variant = TagDeserializer<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant), NbtError> {
+        let variant = match self.name.to_str() {
+            Cow::Borrowed(s) => seed.deserialize(BorrowedStrDeserializer::<NbtError>::new(s))?,
+            Cow::Owned(s) => seed.deserialize(StringDeserializer::<NbtError>::new(s))?,
+        };
+        Ok((variant, self.value))
+    }
+}
+
+impl<'de> VariantAccess<'de> for TagDeserializer<'de> {
+    type Error = NbtError;
+
+    fn unit_variant(self) -> Result<(), NbtError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, NbtError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, NbtError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, NbtError> {
+        self.deserialize_any(visitor)
+    }
+}
@@ -0,0 +1,223 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.1
+//
+// ## Changelog
+// - 1.0.0: Initial version, serde support for binary NBT [mrmarkolinus:2026-10-16]
+// - 1.0.1: NbtTag and NbtTagCompound fields are written and read as plain NBT [mrmarkolinus:2026-10-16]
+
+//! Maps Rust types to and from binary NBT with serde.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Section {
+//!     #[serde(rename = "Y")]
+//!     y: i8,
+//!     block_states: Option<BlockStates>,
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct BlockStates {
+//!     palette: Vec<HashMap<String, fastnbt::nbt_tag::NbtTag>>,
+//!     data: Option<fastnbt::LongArray>,
+//! }
+//! ```
+//!
+//! Structs and maps are compounds, sequences are lists and `Option::None` fields are left
+//! out. `bool` is a byte, `char` a string, and unsigned integers are stored in the signed
+//! tag of the same width (`u8` as a byte, `u32` as an int...), keeping their bit pattern.
+//! Unit enum variants are strings, the other variants a compound with the variant name as
+//! its only key.
+//!
+//! A `Vec<i64>` is written as a list of longs. To get a byte, int or long array use the
+//! `ByteArray`, `IntArray` and `LongArray` wrappers, or annotate the field with
+//! `#[serde(with = "fastnbt::serde_nbt::long_array")]` (and `byte_array`, `int_array`).
+//! When reading, arrays and lists of numbers are accepted interchangeably by `Vec`s.
+//!
+//! `NbtTag` and `NbtTagCompound` fields hold any tag as is, e.g. the `Properties` of a
+//! block state: `{Name: "minecraft:stone"}` reads into a `HashMap<String, NbtTag>` with a
+//! string tag. Other data formats get the serde shape of the `json` module.
+
+use crate::error::{NbtError, NbtErrorKind};
+use crate::file_parser::{self, ParseLimits};
+use crate::flavor::NbtFlavor;
+use crate::generic_bin;
+use crate::nbt_tag::{NbtTag, NbtTagCompound};
+use crate::nbt_writer::NbtWriter;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::io::{BufReader, Read};
+use std::ops::{Deref, DerefMut};
+
+mod de;
+mod ser;
+
+#[cfg(test)]
+mod tests;
+
+/// Newtype names used by the array wrappers to tell the NBT serializer and deserializer
+/// which array type they stand for. Other data formats see a plain newtype.
+pub(crate) const BYTE_ARRAY_TOKEN: &str = "__fastnbt_byte_array";
+pub(crate) const INT_ARRAY_TOKEN: &str = "__fastnbt_int_array";
+pub(crate) const LONG_ARRAY_TOKEN: &str = "__fastnbt_long_array";
+/// Newtype name of `NbtTag` and `NbtTagCompound`. Under it, formats that are not human
+/// readable get the tag as bytes, see `tag_to_bytes`, which binary NBT copies as is.
+pub(crate) const TAG_TOKEN: &str = "__fastnbt_tag";
+
+/// Deserializes a value from uncompressed binary NBT. The root must be a compound.
+/// Strings and byte arrays can be borrowed from `bytes`.
+pub fn from_bytes<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, NbtError> {
+    from_bytes_with_limits(bytes, &ParseLimits::default())
+}
+
+pub fn from_bytes_with_limits<'de, T: Deserialize<'de>>(bytes: &'de [u8], limits: &ParseLimits) -> Result<T, NbtError> {
+    de::from_bytes(bytes, limits)
+}
+
+/// Deserializes a value from a reader, decompressing gzip or zlib data on the fly.
+pub fn from_reader<R: Read, T: DeserializeOwned>(reader: R) -> Result<T, NbtError> {
+    let mut bytes = Vec::new();
+    generic_bin::decompress_reader(BufReader::new(reader))
+        .and_then(|mut decompressed| decompressed.read_to_end(&mut bytes))
+        .map_err(|e| NbtError::from_io(e, bytes.len() as u64))?;
+
+    from_bytes(&bytes)
+}
+
+/// Serializes a value to uncompressed binary NBT. The value must serialize as a struct or
+/// a map, which becomes the root compound (with an empty name).
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, NbtError> {
+    let mut buf = Vec::new();
+    value.serialize(ser::ValueSerializer::root(&mut buf))?;
+    Ok(buf)
+}
+
+fn serde_error(msg: String) -> NbtError {
+    NbtError::new(NbtErrorKind::Serde(msg), 0)
+}
+
+/// A tag in network NBT: its type id and payload, without a name.
+pub(crate) fn tag_to_bytes(tag: &NbtTag) -> std::io::Result<Vec<u8>> {
+    let mut writer = NbtWriter::new(Vec::new()).with_flavor(NbtFlavor::JavaNetwork);
+    writer.write_tag("", tag)?;
+    Ok(writer.into_inner())
+}
+
+/// Same as `tag_to_bytes`, for a compound. Its name is not written.
+pub(crate) fn compound_to_bytes(compound: &NbtTagCompound) -> std::io::Result<Vec<u8>> {
+    let mut writer = NbtWriter::new(Vec::new()).with_flavor(NbtFlavor::JavaNetwork);
+    writer.write_compound(compound)?;
+    Ok(writer.into_inner())
+}
+
+/// Reads back the output of `tag_to_bytes`.
+pub(crate) fn tag_from_bytes(bytes: &[u8]) -> Result<NbtTag, NbtError> {
+    file_parser::parse_bytes_with_flavor(bytes, NbtFlavor::JavaNetwork, &ParseLimits::default())
+}
+
+macro_rules! array_wrapper {
+    ($(#[$doc:meta])* $name:ident, $module:ident, $elem:ty, $token:expr) => {
+        $(#[$doc])*
+        #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct $name(pub Vec<$elem>);
+
+        impl $name {
+            pub fn new(values: Vec<$elem>) -> Self {
+                $name(values)
+            }
+
+            pub fn into_inner(self) -> Vec<$elem> {
+                self.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = Vec<$elem>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl From<Vec<$elem>> for $name {
+            fn from(values: Vec<$elem>) -> Self {
+                $name(values)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                $module::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                $module::deserialize(deserializer).map($name)
+            }
+        }
+
+        /// Field attribute module: `#[serde(with = "...")]` on a `Vec` field stores it as
+        /// an NBT array instead of a list.
+        pub mod $module {
+            use serde::de::{SeqAccess, Visitor};
+            use serde::{Deserialize, Deserializer, Serializer};
+            use std::fmt;
+
+            pub fn serialize<S: Serializer>(values: &[$elem], serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_newtype_struct($token, values)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<$elem>, D::Error> {
+                struct ArrayVisitor;
+
+                impl<'de> Visitor<'de> for ArrayVisitor {
+                    type Value = Vec<$elem>;
+
+                    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        write!(f, "a sequence of {}", stringify!($elem))
+                    }
+
+                    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+                        Vec::deserialize(deserializer)
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(1 << 16));
+                        while let Some(value) = seq.next_element()? {
+                            values.push(value);
+                        }
+                        Ok(values)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct($token, ArrayVisitor)
+            }
+        }
+    };
+}
+
+array_wrapper!(
+    /// A `Vec<i8>` stored as an NBT byte array.
+    ByteArray, byte_array, i8, super::BYTE_ARRAY_TOKEN
+);
+array_wrapper!(
+    /// A `Vec<i32>` stored as an NBT int array.
+    IntArray, int_array, i32, super::INT_ARRAY_TOKEN
+);
+array_wrapper!(
+    /// A `Vec<i64>` stored as an NBT long array, e.g. block state and heightmap data.
+    LongArray, long_array, i64, super::LONG_ARRAY_TOKEN
+);
@@ -0,0 +1,525 @@
+//! Serializer writing binary NBT directly, without building an `NbtTag` tree.
+//!
+//! The tag type of a value is only known when serde calls the matching `serialize_*` method,
+//! so the header (type and name) of each tag is written at that point. Lists reserve room
+//! for their element type and length, which are filled in once the elements are written.
+
+use super::{serde_error, BYTE_ARRAY_TOKEN, INT_ARRAY_TOKEN, LONG_ARRAY_TOKEN, TAG_TOKEN};
+use crate::error::NbtError;
+use crate::mutf8;
+use crate::nbt_tag::NbtTagType;
+
+use serde::ser::{self, Impossible, Serialize, Serializer};
+
+/// Where the value being serialized goes, which decides how its header is written.
+enum Slot<'a> {
+    /// The document root: a compound with an empty name.
+    Root,
+    /// An entry of a compound.
+    Named(&'a str),
+    /// An element of a list. All elements must have the same type.
+    Element(&'a mut Option<NbtTagType>),
+    /// An element of a byte, int or long array.
+    ArrayElement(NbtTagType),
+}
+
+pub(super) struct ValueSerializer<'a> {
+    out: &'a mut Vec<u8>,
+    slot: Slot<'a>,
+    /// Set by the array wrappers: the next sequence is written as this array type.
+    array: Option<NbtTagType>,
+    /// Set by `NbtTag` and `NbtTagCompound`: the next bytes are a whole tag in network NBT.
+    tag: bool,
+}
+
+impl<'a> ValueSerializer<'a> {
+    pub(super) fn root(out: &'a mut Vec<u8>) -> Self {
+        ValueSerializer { out, slot: Slot::Root, array: None, tag: false }
+    }
+
+    fn new(out: &'a mut Vec<u8>, slot: Slot<'a>) -> Self {
+        ValueSerializer { out, slot, array: None, tag: false }
+    }
+
+    /// Writes what precedes the payload of a tag of type `ty` in the current slot.
+    fn header(&mut self, ty: NbtTagType) -> Result<(), NbtError> {
+        match &mut self.slot {
+            Slot::Root => {
+                if ty != NbtTagType::Compound {
+                    return Err(serde_error(format!("the root must be a compound, not {:?}", ty)));
+                }
+                self.out.extend_from_slice(&[ty.id(), 0, 0]);
+            }
+            Slot::Named(name) => {
+                self.out.push(ty.id());
+                write_string(self.out, name)?;
+            }
+            Slot::Element(elem_ty) => match elem_ty {
+                None => **elem_ty = Some(ty),
+                Some(expected) if *expected == ty => {}
+                Some(expected) => return Err(serde_error(format!("a list of {:?} cannot hold a {:?} element", expected, ty))),
+            },
+            Slot::ArrayElement(array_ty) => {
+                let expected = match array_ty {
+                    NbtTagType::ByteArray => NbtTagType::Byte,
+                    NbtTagType::IntArray => NbtTagType::Int,
+                    _ => NbtTagType::Long,
+                };
+                if ty != expected {
+                    return Err(serde_error(format!("a {:?} cannot hold a {:?} element", array_ty, ty)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn unsupported(what: &str) -> NbtError {
+        serde_error(format!("{} cannot be represented in NBT", what))
+    }
+
+    fn list(mut self, extra_end: bool) -> Result<ListSerializer<'a>, NbtError> {
+        match self.array {
+            Some(ty) => self.header(ty)?,
+            None => {
+                self.header(NbtTagType::List)?;
+                self.out.push(NbtTagType::End.id());
+            }
+        }
+
+        let len_pos = self.out.len();
+        self.out.extend_from_slice(&[0; 4]);
+        Ok(ListSerializer { out: self.out, array: self.array, elem_ty: None, len_pos, len: 0, extra_end })
+    }
+
+    fn compound(mut self) -> Result<CompoundSerializer<'a>, NbtError> {
+        self.header(NbtTagType::Compound)?;
+        Ok(CompoundSerializer { out: self.out, key: None, extra_end: false })
+    }
+
+    /// Opens the compound `{variant: ...}` used for enum variants with data.
+    fn variant(self, variant: &'static str) -> Result<ValueSerializer<'a>, NbtError> {
+        let out = self.compound()?.out;
+        Ok(ValueSerializer::new(out, Slot::Named(variant)))
+    }
+}
+
+fn write_string(out: &mut Vec<u8>, s: &str) -> Result<(), NbtError> {
+    let bytes = mutf8::encode(s);
+    let len = u16::try_from(bytes.len()).map_err(|_| serde_error(format!("a string of {} bytes is too long for NBT", bytes.len())))?;
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(&bytes);
+    Ok(())
+}
+
+fn length(len: usize) -> Result<i32, NbtError> {
+    i32::try_from(len).map_err(|_| serde_error(format!("a sequence of {} elements is too long for NBT", len)))
+}
+
+impl<'a> Serializer for ValueSerializer<'a> {
+    type Ok = ();
+    type Error = NbtError;
+    type SerializeSeq = ListSerializer<'a>;
+    type SerializeTuple = ListSerializer<'a>;
+    type SerializeTupleStruct = ListSerializer<'a>;
+    type SerializeTupleVariant = ListSerializer<'a>;
+    type SerializeMap = CompoundSerializer<'a>;
+    type SerializeStruct = CompoundSerializer<'a>;
+    type SerializeStructVariant = CompoundSerializer<'a>;
+
+    /// Asks `NbtTag` and `NbtTagCompound` for their bytes instead of their serde shape.
+    fn is_human_readable(&self) -> bool {
+        !self.tag
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), NbtError> {
+        self.serialize_i8(v as i8)
+    }
+
+    fn serialize_i8(mut self, v: i8) -> Result<(), NbtError> {
+        self.header(NbtTagType::Byte)?;
+        self.out.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    fn serialize_i16(mut self, v: i16) -> Result<(), NbtError> {
+        self.header(NbtTagType::Short)?;
+        self.out.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    fn serialize_i32(mut self, v: i32) -> Result<(), NbtError> {
+        self.header(NbtTagType::Int)?;
+        self.out.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    fn serialize_i64(mut self, v: i64) -> Result<(), NbtError> {
+        self.header(NbtTagType::Long)?;
+        self.out.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    // Unsigned integers keep their bit pattern in the signed tag of the same width
+    fn serialize_u8(self, v: u8) -> Result<(), NbtError> {
+        self.serialize_i8(v as i8)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), NbtError> {
+        self.serialize_i16(v as i16)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), NbtError> {
+        self.serialize_i32(v as i32)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), NbtError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_f32(mut self, v: f32) -> Result<(), NbtError> {
+        self.header(NbtTagType::Float)?;
+        self.out.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(mut self, v: f64) -> Result<(), NbtError> {
+        self.header(NbtTagType::Double)?;
+        self.out.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), NbtError> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(mut self, v: &str) -> Result<(), NbtError> {
+        self.header(NbtTagType::String)?;
+        write_string(self.out, v)
+    }
+
+    fn serialize_bytes(mut self, v: &[u8]) -> Result<(), NbtError> {
+        if self.tag {
+            // The payload is already binary NBT, only the header depends on the slot
+            let ty = v.first().and_then(|id| NbtTagType::from_id(*id)).ok_or_else(|| serde_error("invalid tag bytes".to_string()))?;
+            self.header(ty)?;
+            self.out.extend_from_slice(&v[1..]);
+            return Ok(());
+        }
+
+        self.header(NbtTagType::ByteArray)?;
+        self.out.extend_from_slice(&length(v.len())?.to_be_bytes());
+        self.out.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), NbtError> {
+        match self.slot {
+            // The entry is simply left out of its compound
+            Slot::Named(_) => Ok(()),
+            _ => Err(Self::unsupported("None outside of a compound")),
+        }
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), NbtError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), NbtError> {
+        Err(Self::unsupported("()"))
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), NbtError> {
+        Err(Self::unsupported(name))
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<(), NbtError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(mut self, name: &'static str, value: &T) -> Result<(), NbtError> {
+        self.array = match name {
+            BYTE_ARRAY_TOKEN => Some(NbtTagType::ByteArray),
+            INT_ARRAY_TOKEN => Some(NbtTagType::IntArray),
+            LONG_ARRAY_TOKEN => Some(NbtTagType::LongArray),
+            _ => None,
+        };
+        self.tag = name == TAG_TOKEN;
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, variant: &'static str, value: &T) -> Result<(), NbtError> {
+        let mut compound = self.compound()?;
+        compound.entry(variant, value)?;
+        compound.finish()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<ListSerializer<'a>, NbtError> {
+        self.list(false)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<ListSerializer<'a>, NbtError> {
+        self.list(false)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<ListSerializer<'a>, NbtError> {
+        self.list(false)
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _index: u32, variant: &'static str, _len: usize) -> Result<ListSerializer<'a>, NbtError> {
+        self.variant(variant)?.list(true)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<CompoundSerializer<'a>, NbtError> {
+        self.compound()
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<CompoundSerializer<'a>, NbtError> {
+        self.compound()
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _index: u32, variant: &'static str, _len: usize) -> Result<CompoundSerializer<'a>, NbtError> {
+        let mut inner = self.variant(variant)?.compound()?;
+        inner.extra_end = true;
+        Ok(inner)
+    }
+}
+
+pub(super) struct ListSerializer<'a> {
+    out: &'a mut Vec<u8>,
+    array: Option<NbtTagType>,
+    elem_ty: Option<NbtTagType>,
+    len_pos: usize,
+    len: usize,
+    /// Also closes the compound wrapping an enum variant.
+    extra_end: bool,
+}
+
+impl ListSerializer<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+        let slot = match self.array {
+            Some(ty) => Slot::ArrayElement(ty),
+            None => Slot::Element(&mut self.elem_ty),
+        };
+        value.serialize(ValueSerializer::new(self.out, slot)).map_err(|e| e.in_index(self.len))?;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), NbtError> {
+        if self.array.is_none() {
+            self.out[self.len_pos - 1] = self.elem_ty.unwrap_or(NbtTagType::End).id();
+        }
+        self.out[self.len_pos..self.len_pos + 4].copy_from_slice(&length(self.len)?.to_be_bytes());
+        if self.extra_end {
+            self.out.push(NbtTagType::End.id());
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for ListSerializer<'_> {
+    type Ok = ();
+    type Error = NbtError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), NbtError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for ListSerializer<'_> {
+    type Ok = ();
+    type Error = NbtError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), NbtError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for ListSerializer<'_> {
+    type Ok = ();
+    type Error = NbtError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), NbtError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for ListSerializer<'_> {
+    type Ok = ();
+    type Error = NbtError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), NbtError> {
+        self.finish()
+    }
+}
+
+pub(super) struct CompoundSerializer<'a> {
+    out: &'a mut Vec<u8>,
+    key: Option<String>,
+    /// Also closes the compound wrapping an enum variant.
+    extra_end: bool,
+}
+
+impl CompoundSerializer<'_> {
+    fn entry<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), NbtError> {
+        value.serialize(ValueSerializer::new(self.out, Slot::Named(key))).map_err(|e| e.in_key(key))
+    }
+
+    fn finish(self) -> Result<(), NbtError> {
+        self.out.push(NbtTagType::End.id());
+        if self.extra_end {
+            self.out.push(NbtTagType::End.id());
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for CompoundSerializer<'_> {
+    type Ok = ();
+    type Error = NbtError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), NbtError> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+        let key = self.key.take().ok_or_else(|| serde_error("value serialized before its key".to_string()))?;
+        self.entry(&key, value)
+    }
+
+    fn end(self) -> Result<(), NbtError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for CompoundSerializer<'_> {
+    type Ok = ();
+    type Error = NbtError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), NbtError> {
+        self.entry(key, value)
+    }
+
+    fn end(self) -> Result<(), NbtError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for CompoundSerializer<'_> {
+    type Ok = ();
+    type Error = NbtError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), NbtError> {
+        self.entry(key, value)
+    }
+
+    fn end(self) -> Result<(), NbtError> {
+        self.finish()
+    }
+}
+
+/// Compound keys must be strings (or characters).
+struct KeySerializer;
+
+fn key_error() -> NbtError {
+    serde_error("compound keys must be strings".to_string())
+}
+
+macro_rules! reject_keys {
+    ($($method:ident($($ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method(self $(, _: $ty)*) -> Result<String, NbtError> {
+                Err(key_error())
+            }
+        )*
+    };
+}
+
+impl Serializer for KeySerializer {
+    type Ok = String;
+    type Error = NbtError;
+    type SerializeSeq = Impossible<String, NbtError>;
+    type SerializeTuple = Impossible<String, NbtError>;
+    type SerializeTupleStruct = Impossible<String, NbtError>;
+    type SerializeTupleVariant = Impossible<String, NbtError>;
+    type SerializeMap = Impossible<String, NbtError>;
+    type SerializeStruct = Impossible<String, NbtError>;
+    type SerializeStructVariant = Impossible<String, NbtError>;
+
+    fn serialize_str(self, v: &str) -> Result<String, NbtError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, NbtError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<String, NbtError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<String, NbtError> {
+        value.serialize(self)
+    }
+
+    reject_keys! {
+        serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32), serialize_i64(i64),
+        serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64),
+        serialize_f32(f32), serialize_f64(f64), serialize_bytes(&[u8]), serialize_none(),
+        serialize_unit(), serialize_unit_struct(&'static str),
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<String, NbtError> {
+        Err(key_error())
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, _variant: &'static str, _value: &T) -> Result<String, NbtError> {
+        Err(key_error())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, NbtError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, NbtError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, NbtError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, NbtError> {
+        Err(key_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, NbtError> {
+        Err(key_error())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, NbtError> {
+        Err(key_error())
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, NbtError> {
+        Err(key_error())
+    }
+}
@@ -0,0 +1,237 @@
+#[cfg(test)]
+
+use super::*;
+use crate::error::NbtErrorKind;
+use crate::file_parser;
+use crate::nbt_tag::{NbtTag, NbtTagCompound, NbtTagString, NbtTagType};
+use crate::nbt_writer::NbtWriter;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Radius(f64),
+    Point(i32, i32),
+    Rect { w: i16, h: i16 },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Item {
+    id: String,
+    #[serde(rename = "Count")]
+    count: i8,
+    tag: Option<HashMap<String, i32>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Everything {
+    byte: i8,
+    short: i16,
+    int: i32,
+    long: i64,
+    float: f32,
+    double: f64,
+    flag: bool,
+    unsigned: u8,
+    letter: char,
+    text: String,
+    missing: Option<i32>,
+    present: Option<i32>,
+    items: Vec<Item>,
+    nested: Vec<Vec<i32>>,
+    empty: Vec<String>,
+    bytes: ByteArray,
+    ints: IntArray,
+    longs: LongArray,
+    #[serde(with = "long_array")]
+    plain_longs: Vec<i64>,
+    shapes: Vec<Shape>,
+}
+
+fn everything() -> Everything {
+    Everything {
+        byte: -1,
+        short: 300,
+        int: 70_000,
+        long: i64::MIN,
+        float: 0.5,
+        double: -2.25,
+        flag: true,
+        unsigned: 255,
+        letter: 'é',
+        text: "Grüße\0".to_string(),
+        missing: None,
+        present: Some(7),
+        items: vec![
+            Item { id: "minecraft:stone".to_string(), count: 64, tag: None },
+            Item { id: "minecraft:chest".to_string(), count: 1, tag: Some(HashMap::from([("Damage".to_string(), 3)])) },
+        ],
+        nested: vec![vec![1, 2], vec![], vec![3]],
+        empty: vec![],
+        bytes: ByteArray::new(vec![1, -2, 3]),
+        ints: IntArray::new(vec![i32::MAX, 0]),
+        longs: LongArray::new(vec![1, 2, 3]),
+        plain_longs: vec![-5],
+        shapes: vec![Shape::Radius(1.5), Shape::Point(1, 2), Shape::Rect { w: 3, h: 4 }],
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let value = everything();
+    let bytes = to_bytes(&value).unwrap();
+
+    assert_eq!(from_bytes::<Everything>(&bytes).unwrap(), value);
+}
+
+#[test]
+fn test_written_tag_types() {
+    let bytes = to_bytes(&everything()).unwrap();
+    let root = file_parser::parse_bytes(&bytes).unwrap();
+    let values = &root.compound_as_ref().unwrap().values;
+
+    let types: HashMap<&str, NbtTagType> = values.iter().map(|(k, v)| (k.as_str(), v.ty())).collect();
+    assert_eq!(types["flag"], NbtTagType::Byte);
+    assert_eq!(types["letter"], NbtTagType::String);
+    assert_eq!(types["bytes"], NbtTagType::ByteArray);
+    assert_eq!(types["ints"], NbtTagType::IntArray);
+    assert!(values["plain_longs"].long_array_as_ref().is_some());
+    assert_eq!(types["nested"], NbtTagType::List);
+    assert!(!types.contains_key("missing"));
+
+    let items = values["items"].list_as_ref().unwrap();
+    assert_eq!(items.ty, NbtTagType::Compound);
+    assert_eq!(items.values.len(), 2);
+    assert_eq!(values["empty"].list_as_ref().unwrap().ty, NbtTagType::End);
+    assert_eq!(values["unsigned"].byte().unwrap().value, -1);
+}
+
+#[test]
+fn test_unit_variant_is_a_string() {
+    #[derive(Serialize)]
+    struct Wrapper {
+        shape: Shape,
+    }
+
+    let bytes = to_bytes(&Wrapper { shape: Shape::Empty }).unwrap();
+    let root = file_parser::parse_bytes(&bytes).unwrap();
+    assert_eq!(root.compound_as_ref().unwrap().values["shape"].string().unwrap().value, "Empty");
+}
+
+#[test]
+fn test_borrowed_strings() {
+    #[derive(Deserialize)]
+    struct Borrowed<'a> {
+        id: &'a str,
+    }
+
+    let bytes = to_bytes(&HashMap::from([("id", "minecraft:stone")])).unwrap();
+    assert_eq!(from_bytes::<Borrowed>(&bytes).unwrap().id, "minecraft:stone");
+}
+
+#[test]
+fn test_array_and_list_are_distinguished() {
+    #[derive(Serialize)]
+    struct Plain {
+        longs: Vec<i64>,
+    }
+    #[derive(Debug, Deserialize)]
+    struct Wrapped {
+        #[allow(dead_code)]
+        longs: LongArray,
+    }
+
+    // A list of longs can be read into a Vec, but not into a LongArray
+    let bytes = to_bytes(&Plain { longs: vec![1, 2] }).unwrap();
+    let error = from_bytes::<Wrapped>(&bytes).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::Serde(_)));
+    assert_eq!(error.path(), "longs");
+}
+
+#[test]
+fn test_error_path() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct WrongCount {
+        items: Vec<HashMap<String, String>>,
+    }
+
+    let bytes = to_bytes(&everything()).unwrap();
+    let error = from_bytes::<WrongCount>(&bytes).unwrap_err();
+
+    assert_eq!(error.path(), "items[0].Count");
+    assert!(error.offset > 0);
+}
+
+#[test]
+fn test_root_must_be_a_compound() {
+    assert!(to_bytes(&5).is_err());
+    assert!(to_bytes(&vec![1, 2]).is_err());
+}
+
+#[test]
+fn test_from_reader_decompresses() {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    let bytes = to_bytes(&everything()).unwrap();
+    let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+    gzip.write_all(&bytes).unwrap();
+    let compressed = gzip.finish().unwrap();
+
+    assert_eq!(from_reader::<_, Everything>(compressed.as_slice()).unwrap(), everything());
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct BlockStates {
+    palette: Vec<HashMap<String, NbtTag>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct BlockState {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Properties")]
+    properties: Option<NbtTagCompound>,
+}
+
+fn snbt_bytes(snbt: &str) -> Vec<u8> {
+    let root = NbtTag::from_snbt(snbt).unwrap();
+    let mut writer = NbtWriter::new(Vec::new());
+    writer.write_compound(root.compound_as_ref().unwrap()).unwrap();
+    writer.into_inner()
+}
+
+#[test]
+fn test_tag_field_round_trip() {
+    let bytes = snbt_bytes(r#"{palette: [{Name: "minecraft:stone"}, {Name: "minecraft:oak_log", Properties: {axis: "y"}, data: [L; 1L, 2L]}]}"#);
+    let states = from_bytes::<BlockStates>(&bytes).unwrap();
+
+    assert_eq!(states.palette[0]["Name"], NbtTag::String(NbtTagString::new("minecraft:stone".to_string())));
+    assert_eq!(states.palette[1]["data"].ty(), NbtTagType::LongArray);
+    // Map entries come back in any order, compare the trees
+    let written = to_bytes(&states).unwrap();
+    assert_eq!(file_parser::parse_bytes(&written).unwrap(), file_parser::parse_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_compound_field_round_trip() {
+    let bytes = snbt_bytes(r#"{Name: "minecraft:oak_stairs", Properties: {facing: "north", half: "top", waterlogged: "false"}}"#);
+    let state = from_bytes::<BlockState>(&bytes).unwrap();
+
+    let properties = state.properties.as_ref().unwrap();
+    assert_eq!(properties.values.keys().collect::<Vec<_>>(), ["facing", "half", "waterlogged"]);
+    assert_eq!(to_bytes(&state).unwrap(), bytes);
+    assert_eq!(from_bytes::<NbtTagCompound>(&bytes).unwrap().values["Name"].string().unwrap().value, "minecraft:oak_stairs");
+}
+
+#[test]
+fn test_tag_is_written_as_plain_nbt() {
+    let bytes = to_bytes(&HashMap::from([("x", NbtTag::from(5i32))])).unwrap();
+    assert_eq!(bytes, snbt_bytes("{x: 5}"));
+
+    // Tags still have to fit where they are written
+    let error = to_bytes(&HashMap::from([("list", vec![NbtTag::from(1i32), NbtTag::from(1i64)])])).unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::Serde(_)));
+}
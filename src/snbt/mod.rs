@@ -0,0 +1,517 @@
+// ## Author
+// - mrmarkolinus
+//
+// ## Date
+// - 2026-10-16
+//
+// ## File Version
+// - 1.0.4
+//
+// ## Changelog
+// - 1.0.0: Initial version, SNBT writer and parser [mrmarkolinus:2026-10-16]
+// - 1.0.1: Prefix parsing of compounds and quoted strings for NBT paths [mrmarkolinus:2026-10-16]
+// - 1.0.2: Parsed tags carry no name [mrmarkolinus:2026-10-16]
+// - 1.0.3: Array elements are read with the scalar getters [mrmarkolinus:2026-10-16]
+// - 1.0.4: Compound keys are written in insertion order [mrmarkolinus:2026-10-16]
+
+//! Stringified NBT, the text syntax used by Minecraft commands such as `/give` and
+//! `/data merge`, e.g. `{Count:1b,id:"minecraft:stone",tag:{Damage:3,Tags:["a","b"]}}`.
+//!
+//! Numbers carry a type suffix (`1b`, `2s`, `3`, `4L`, `1.5f`, `2.5d`), arrays are written
+//! `[B;…]`, `[I;…]` and `[L;…]`, strings are quoted when needed with `\` as escape character,
+//! and keys are left unquoted when they only contain `A-Z a-z 0-9 _ - . +`.
+//!
+//! Compound keys are written in the order of the compound, which is the order of the file
+//! they were read from.
+
+use crate::error::{NbtError, NbtErrorKind};
+use crate::file_parser::ParseLimits;
+use crate::nbt_tag::*;
+
+use std::fmt::Write;
+
+#[cfg(test)]
+mod tests;
+
+const INDENT: &str = "    ";
+
+/// Writes a tag as SNBT. In pretty mode compounds and lists of non-numeric values are
+/// spread over several indented lines, otherwise everything is on one line without spaces.
+pub fn to_snbt(tag: &NbtTag, pretty: bool) -> String {
+    let mut out = String::new();
+    write_tag(&mut out, tag, pretty.then_some(0));
+    out
+}
+
+/// Parses SNBT text. The root can be any tag; a root compound has an empty name.
+/// Errors report the byte offset in `text` and the path of the tag being parsed.
+pub fn from_snbt(text: &str) -> Result<NbtTag, NbtError> {
+    let mut parser = SnbtParser { text, pos: 0, depth: 0, max_depth: ParseLimits::default().max_depth };
+    let tag = parser.parse_value()?;
+
+    parser.skip_whitespace();
+    if parser.pos < text.len() {
+        return Err(parser.error("trailing characters after the value"));
+    }
+    Ok(tag)
+}
+
+/// Parses the SNBT compound at the start of `text` and returns it with the number of bytes
+/// it spans. Errors report byte offsets in `text`.
+pub(crate) fn parse_compound_prefix(text: &str) -> Result<(NbtTagCompound, usize), NbtError> {
+    let mut parser = SnbtParser { text, pos: 0, depth: 0, max_depth: ParseLimits::default().max_depth };
+    let compound = parser.parse_compound()?;
+    Ok((compound, parser.pos))
+}
+
+/// Parses the quoted string at the start of `text`, with the escapes of SNBT, and returns
+/// it with the number of bytes it spans.
+pub(crate) fn parse_quoted_prefix(text: &str) -> Result<(String, usize), NbtError> {
+    let mut parser = SnbtParser { text, pos: 0, depth: 0, max_depth: 0 };
+    let value = parser.parse_quoted()?;
+    Ok((value, parser.pos))
+}
+
+fn is_unquoted_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+fn write_key(out: &mut String, key: &str) {
+    if !key.is_empty() && key.chars().all(is_unquoted_char) {
+        out.push_str(key);
+    }
+    else {
+        write_quoted(out, key);
+    }
+}
+
+/// Quotes with `"` unless the string contains `"` but no `'`, like the game does.
+fn write_quoted(out: &mut String, s: &str) {
+    let quote = if s.contains('"') && !s.contains('\'') { '\'' } else { '"' };
+
+    out.push(quote);
+    for c in s.chars() {
+        if c == quote || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push(quote);
+}
+
+/// `repr` is the `Debug` output of the float, which always keeps a decimal point or an
+/// exponent (`1.0`, `1e-7`), so the value reads back as a float.
+fn write_float(out: &mut String, repr: String, suffix: char) {
+    // Infinity and NaN have no SNBT syntax; they are written like Java does, for readability
+    let repr = match repr.as_str() {
+        "inf" => "Infinity",
+        "-inf" => "-Infinity",
+        other => other,
+    };
+    out.push_str(repr);
+    out.push(suffix);
+}
+
+fn write_newline(out: &mut String, indent: usize) {
+    out.push('\n');
+    for _ in 0..indent {
+        out.push_str(INDENT);
+    }
+}
+
+fn write_array<T: std::fmt::Display>(out: &mut String, prefix: char, suffix: &str, values: &[T], pretty: bool) {
+    let _ = write!(out, "[{};", prefix);
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if pretty {
+            out.push(' ');
+        }
+        let _ = write!(out, "{}{}", value, suffix);
+    }
+    out.push(']');
+}
+
+/// `indent` is `None` in compact mode, the current nesting level in pretty mode.
+fn write_tag(out: &mut String, tag: &NbtTag, indent: Option<usize>) {
+    match tag {
+        NbtTag::End => {}
+        NbtTag::Byte(v) => { let _ = write!(out, "{}b", v.value); }
+        NbtTag::Short(v) => { let _ = write!(out, "{}s", v.value); }
+        NbtTag::Int(v) => { let _ = write!(out, "{}", v.value); }
+        NbtTag::Long(v) => { let _ = write!(out, "{}L", v.value); }
+        NbtTag::Float(v) => write_float(out, format!("{:?}", v.value), 'f'),
+        NbtTag::Double(v) => write_float(out, format!("{:?}", v.value), 'd'),
+        NbtTag::String(v) => write_quoted(out, &v.value),
+        NbtTag::ByteArray(v) => write_array(out, 'B', "b", &v.values, indent.is_some()),
+        NbtTag::IntArray(v) => write_array(out, 'I', "", &v.values, indent.is_some()),
+        NbtTag::LongArray(v) => write_array(out, 'L', "L", &v.values, indent.is_some()),
+        NbtTag::List(list) => {
+            // Numbers stay on one line even in pretty mode
+            let numeric = matches!(list.ty, NbtTagType::Byte | NbtTagType::Short | NbtTagType::Int | NbtTagType::Long | NbtTagType::Float | NbtTagType::Double);
+            let multiline = indent.filter(|_| !numeric && !list.values.is_empty());
+
+            out.push('[');
+            for (i, value) in list.values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                    if indent.is_some() && multiline.is_none() {
+                        out.push(' ');
+                    }
+                }
+                if let Some(level) = multiline {
+                    write_newline(out, level + 1);
+                }
+                write_tag(out, value, indent.map(|level| level + 1));
+            }
+            if let Some(level) = multiline {
+                write_newline(out, level);
+            }
+            out.push(']');
+        }
+        NbtTag::Compound(compound) => write_compound(out, compound, indent),
+    }
+}
+
+fn write_compound(out: &mut String, compound: &NbtTagCompound, indent: Option<usize>) {
+    out.push('{');
+    for (i, (key, value)) in compound.values.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if let Some(level) = indent {
+            write_newline(out, level + 1);
+        }
+        write_key(out, key);
+        out.push(':');
+        if indent.is_some() {
+            out.push(' ');
+        }
+        write_tag(out, value, indent.map(|level| level + 1));
+    }
+    if let (Some(level), false) = (indent, compound.values.is_empty()) {
+        write_newline(out, level);
+    }
+    out.push('}');
+}
+
+struct SnbtParser<'t> {
+    text: &'t str,
+    pos: usize,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<'t> SnbtParser<'t> {
+    fn error(&self, message: &str) -> NbtError {
+        NbtError::new(NbtErrorKind::InvalidSnbt(message.to_string()), self.pos as u64)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips whitespace and consumes `c` if it comes next.
+    fn accept(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        }
+        else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), NbtError> {
+        if self.accept(c) {
+            Ok(())
+        }
+        else {
+            Err(self.error(&format!("expected '{}'", c)))
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), NbtError> {
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            let (value, max) = (self.depth as u64, self.max_depth as u64);
+            return Err(NbtError::new(NbtErrorKind::LimitExceeded { limit: "nesting depth", value, max }, self.pos as u64));
+        }
+        Ok(())
+    }
+
+    /// Only compounds and lists recurse. Everything else is parsed in separate functions to
+    /// keep the recursive frames small, which matters for deeply nested (or hostile) input.
+    fn parse_value(&mut self) -> Result<NbtTag, NbtError> {
+        self.skip_whitespace();
+        let rest = &self.text.as_bytes()[self.pos..];
+        match rest {
+            [b'{', ..] => Ok(NbtTag::Compound(self.parse_compound()?)),
+            [b'[', b'B' | b'I' | b'L', b';', ..] => self.parse_array(),
+            [b'[', ..] => Ok(NbtTag::List(self.parse_list()?)),
+            _ => self.parse_leaf(),
+        }
+    }
+
+    #[inline(never)]
+    fn parse_leaf(&mut self) -> Result<NbtTag, NbtError> {
+        match self.peek() {
+            Some('"') | Some('\'') => Ok(NbtTag::String(NbtTagString::new(self.parse_quoted()?))),
+            Some(_) => self.parse_unquoted(),
+            None => Err(self.error("expected a value")),
+        }
+    }
+
+    fn parse_compound(&mut self) -> Result<NbtTagCompound, NbtError> {
+        self.enter()?;
+        self.expect('{')?;
+        let mut compound = NbtTagCompound::new("");
+
+        if !self.accept('}') {
+            loop {
+                let key = self.parse_key()?;
+                self.expect(':').map_err(|e| e.in_key(&key))?;
+                let value = self.parse_value().map_err(|e| e.in_key(&key))?;
+                compound.values.insert(key, value);
+
+                if self.accept('}') {
+                    break;
+                }
+                self.expect(',')?;
+            }
+        }
+
+        self.depth -= 1;
+        Ok(compound)
+    }
+
+    fn parse_key(&mut self) -> Result<String, NbtError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"') | Some('\'') => self.parse_quoted(),
+            _ => {
+                let key = self.read_unquoted();
+                if key.is_empty() {
+                    return Err(self.error("expected a key"));
+                }
+                Ok(key.to_string())
+            }
+        }
+    }
+
+    fn read_unquoted(&mut self) -> &'t str {
+        let rest = &self.text[self.pos..];
+        let len = rest.find(|c: char| !is_unquoted_char(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn parse_quoted(&mut self) -> Result<String, NbtError> {
+        let quote = self.peek().unwrap();
+        self.pos += 1;
+        let mut value = String::new();
+
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += c.len_utf8();
+
+            if c == quote {
+                return Ok(value);
+            }
+            if c != '\\' {
+                value.push(c);
+                continue;
+            }
+
+            let escape_pos = self.pos - 1;
+            let escaped = match self.peek() {
+                Some(c @ ('\\' | '"' | '\'')) => c,
+                Some('n') => '\n',
+                Some('t') => '\t',
+                Some('r') => '\r',
+                Some('b') => '\u{8}',
+                Some('f') => '\u{c}',
+                Some('s') => ' ',
+                Some(e @ ('x' | 'u' | 'U')) => {
+                    let digits = match e { 'x' => 2, 'u' => 4, _ => 8 };
+                    let hex = self.text.get(self.pos + 1..self.pos + 1 + digits).unwrap_or("");
+                    let c = u32::from_str_radix(hex, 16).ok().filter(|_| hex.len() == digits).and_then(char::from_u32);
+                    match c {
+                        Some(c) => {
+                            self.pos += digits;
+                            c
+                        }
+                        None => {
+                            self.pos = escape_pos;
+                            return Err(self.error("invalid unicode escape"));
+                        }
+                    }
+                }
+                _ => {
+                    self.pos = escape_pos;
+                    return Err(self.error("invalid escape sequence"));
+                }
+            };
+            self.pos += 1;
+            value.push(escaped);
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<NbtTag, NbtError> {
+        self.expect('[')?;
+        let prefix = self.text.as_bytes()[self.pos];
+        self.pos += 2;
+
+        Ok(match prefix {
+            b'B' => NbtTag::ByteArray(NbtTagByteArray::new(self.parse_array_values(NbtTagType::Byte, NbtTag::as_i8)?)),
+            b'I' => NbtTag::IntArray(NbtTagIntArray::new(self.parse_array_values(NbtTagType::Int, NbtTag::as_i32)?)),
+            _ => NbtTag::LongArray(NbtTagLongArray::new(self.parse_array_values(NbtTagType::Long, NbtTag::as_i64)?)),
+        })
+    }
+
+    fn parse_array_values<T>(&mut self, ty: NbtTagType, extract: fn(&NbtTag) -> Option<T>) -> Result<Vec<T>, NbtError> {
+        let mut values = Vec::new();
+        if self.accept(']') {
+            return Ok(values);
+        }
+
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            let value = self.parse_unquoted().map_err(|e| e.in_index(values.len()))?;
+            match extract(&value) {
+                Some(v) => values.push(v),
+                None => {
+                    self.pos = start;
+                    return Err(self.error(&format!("expected a {:?} array element", ty)).in_index(values.len()));
+                }
+            }
+
+            if self.accept(']') {
+                return Ok(values);
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<NbtTagList, NbtError> {
+        self.enter()?;
+        self.expect('[')?;
+        let mut values: Vec<NbtTag> = Vec::new();
+        let mut ty = NbtTagType::End;
+
+        if !self.accept(']') {
+            loop {
+                self.skip_whitespace();
+                let start = self.pos;
+                let index = values.len();
+                let value = self.parse_value().map_err(|e| e.in_index(index))?;
+
+                if index == 0 {
+                    ty = value.ty();
+                }
+                else if value.ty() != ty {
+                    return Err(self.mixed_list_error(start, ty, value.ty(), index));
+                }
+                values.push(value);
+
+                if self.accept(']') {
+                    break;
+                }
+                self.expect(',')?;
+            }
+        }
+
+        self.depth -= 1;
+        Ok(NbtTagList::new(ty, values))
+    }
+
+    #[cold]
+    fn mixed_list_error(&mut self, start: usize, ty: NbtTagType, found: NbtTagType, index: usize) -> NbtError {
+        self.pos = start;
+        self.error(&format!("list of {:?} cannot hold a {:?} element", ty, found)).in_index(index)
+    }
+
+    /// Parses a number, `true`/`false` or an unquoted string.
+    fn parse_unquoted(&mut self) -> Result<NbtTag, NbtError> {
+        self.skip_whitespace();
+        let token = self.read_unquoted();
+        if token.is_empty() {
+            return Err(self.error("expected a value"));
+        }
+
+        Ok(match parse_scalar(token) {
+            Some(Scalar::Byte(v)) => NbtTag::Byte(NbtTagByte::new(v)),
+            Some(Scalar::Short(v)) => NbtTag::Short(NbtTagShort::new(v)),
+            Some(Scalar::Int(v)) => NbtTag::Int(NbtTagInt::new(v)),
+            Some(Scalar::Long(v)) => NbtTag::Long(NbtTagLong::new(v)),
+            Some(Scalar::Float(v)) => NbtTag::Float(NbtTagFloat::new(v)),
+            Some(Scalar::Double(v)) => NbtTag::Double(NbtTagDouble::new(v)),
+            None => NbtTag::String(NbtTagString::new(token.to_string())),
+        })
+    }
+}
+
+enum Scalar {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+}
+
+fn is_integer(s: &str) -> bool {
+    let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Digits with an optional sign, decimal point and exponent, e.g. `1`, `-.5`, `2.`, `1e-3`.
+fn is_decimal(s: &str) -> bool {
+    let s = s.strip_prefix(['-', '+']).unwrap_or(s);
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(i) => (&s[..i], Some(&s[i + 1..])),
+        None => (s, None),
+    };
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    let mantissa_ok = (!int_part.is_empty() || !frac_part.is_empty())
+        && int_part.bytes().all(|b| b.is_ascii_digit())
+        && frac_part.bytes().all(|b| b.is_ascii_digit());
+    mantissa_ok && exponent.is_none_or(is_integer)
+}
+
+/// Infers the type of an unquoted token the way the game does. Tokens that look like
+/// numbers but are out of range for their type are strings, as in Minecraft.
+fn parse_scalar(token: &str) -> Option<Scalar> {
+    match token {
+        "true" => return Some(Scalar::Byte(1)),
+        "false" => return Some(Scalar::Byte(0)),
+        _ => {}
+    }
+
+    let (body, suffix) = match token.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&token[..i], Some(c.to_ascii_lowercase())),
+        _ => (token, None),
+    };
+
+    match suffix {
+        Some('b') if is_integer(body) => body.parse().ok().map(Scalar::Byte),
+        Some('s') if is_integer(body) => body.parse().ok().map(Scalar::Short),
+        Some('l') if is_integer(body) => body.parse().ok().map(Scalar::Long),
+        Some('f') if is_decimal(body) => body.parse().ok().map(Scalar::Float),
+        Some('d') if is_decimal(body) => body.parse().ok().map(Scalar::Double),
+        None if is_integer(body) => body.parse().ok().map(Scalar::Int),
+        None if is_decimal(body) && body.contains('.') => body.parse().ok().map(Scalar::Double),
+        _ => None,
+    }
+}
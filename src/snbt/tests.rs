@@ -0,0 +1,143 @@
+#[cfg(test)]
+
+use super::*;
+use crate::file_parser::{FileParser, ReadMode};
+use crate::generic_bin::FileType;
+use std::path::PathBuf;
+
+fn same(a: &NbtTag, b: &NbtTag) -> bool {
+    serde_json::to_value(a).unwrap() == serde_json::to_value(b).unwrap()
+}
+
+fn item() -> NbtTag {
+    NbtTag::from_snbt(r#"{Count:1b,id:"minecraft:diamond_sword",tag:{Damage:3,Enchantments:[{id:"minecraft:sharpness",lvl:5s}],display:{Name:'{"text":"Hi"}'}}}"#).unwrap()
+}
+
+#[test]
+fn test_parse_command_syntax() {
+    let tag = item();
+    let root = tag.compound_as_ref().unwrap();
+
+    assert_eq!(root.values["Count"].byte().unwrap().value, 1);
+    assert_eq!(root.values["id"].string().unwrap().value, "minecraft:diamond_sword");
+
+    let inner = root.values["tag"].compound_as_ref().unwrap();
+    assert_eq!(inner.values["Damage"].int().unwrap().value, 3);
+    assert_eq!(inner.values["display"].compound_as_ref().unwrap().values["Name"].string().unwrap().value, r#"{"text":"Hi"}"#);
+
+    let enchantments = inner.values["Enchantments"].list_as_ref().unwrap();
+    assert_eq!(enchantments.ty, NbtTagType::Compound);
+    assert_eq!(enchantments.values[0].compound_as_ref().unwrap().values["lvl"].short().unwrap().value, 5);
+}
+
+#[test]
+fn test_compact_output() {
+    assert_eq!(
+        item().to_snbt(),
+        r#"{Count:1b,id:"minecraft:diamond_sword",tag:{Damage:3,Enchantments:[{id:"minecraft:sharpness",lvl:5s}],display:{Name:'{"text":"Hi"}'}}}"#
+    );
+}
+
+#[test]
+fn test_pretty_output() {
+    let tag = NbtTag::from_snbt(r#"{a:[1,2],b:[{}],c:{},"d e":[L;1L,2L]}"#).unwrap();
+    let expected = "{\n    a: [1, 2],\n    b: [\n        {}\n    ],\n    c: {},\n    \"d e\": [L; 1L, 2L]\n}";
+
+    assert_eq!(tag.to_snbt_pretty(), expected);
+    assert!(same(&NbtTag::from_snbt(expected).unwrap(), &tag));
+}
+
+#[test]
+fn test_type_inference() {
+    let cases: [(&str, NbtTagType); 16] = [
+        ("1b", NbtTagType::Byte),
+        ("-1B", NbtTagType::Byte),
+        ("true", NbtTagType::Byte),
+        ("2s", NbtTagType::Short),
+        ("3", NbtTagType::Int),
+        ("3L", NbtTagType::Long),
+        ("1.5f", NbtTagType::Float),
+        ("1f", NbtTagType::Float),
+        ("1.5", NbtTagType::Double),
+        (".5", NbtTagType::Double),
+        ("1e3d", NbtTagType::Double),
+        // Out of range or not a number in the game's syntax: strings
+        ("300b", NbtTagType::String),
+        ("2147483648", NbtTagType::String),
+        ("1e3", NbtTagType::String),
+        ("minecraft:stone", NbtTagType::String),
+        ("1.2.3", NbtTagType::String),
+    ];
+
+    for (text, ty) in cases {
+        // `:` is not allowed in unquoted strings, so that one must be quoted
+        let text = if text.contains(':') { format!("\"{}\"", text) } else { text.to_string() };
+        assert_eq!(NbtTag::from_snbt(&text).unwrap().ty(), ty, "{}", text);
+    }
+}
+
+#[test]
+fn test_arrays() {
+    let tag = NbtTag::from_snbt("{b:[B;1b,-2b],i:[I; 1, 2 ,3],l:[L;]}").unwrap();
+    let root = tag.compound_as_ref().unwrap();
+
+    assert_eq!(root.values["b"].byte_array().unwrap().values, vec![1, -2]);
+    assert_eq!(root.values["i"].int_array().unwrap().values, vec![1, 2, 3]);
+    assert!(root.values["l"].long_array().unwrap().values.is_empty());
+    assert_eq!(tag.to_snbt(), "{b:[B;1b,-2b],i:[I;1,2,3],l:[L;]}");
+}
+
+#[test]
+fn test_string_escapes() {
+    let tag = NbtTag::from_snbt(r#"['a\'b', "c\"d", "e\\f", 'g"h', "é\n"]"#).unwrap();
+    let values: Vec<String> = tag.list_as_ref().unwrap().values.iter().map(|v| v.string().unwrap().value).collect();
+
+    assert_eq!(values, vec!["a'b", "c\"d", "e\\f", "g\"h", "é\n"]);
+    assert_eq!(tag.to_snbt(), "[\"a'b\",'c\"d',\"e\\\\f\",'g\"h',\"é\n\"]");
+}
+
+#[test]
+fn test_errors() {
+    let error = NbtTag::from_snbt("{a:{b:[1,2b]}}").unwrap_err();
+    assert!(matches!(error.kind, NbtErrorKind::InvalidSnbt(_)));
+    assert_eq!(error.path(), "a.b[1]");
+    assert_eq!(error.offset, 9);
+
+    let error = NbtTag::from_snbt("{a:1,}").unwrap_err();
+    assert_eq!(error.offset, 5);
+
+    let error = NbtTag::from_snbt("{a:1} x").unwrap_err();
+    assert_eq!(error.offset, 6);
+
+    assert!(NbtTag::from_snbt("[I;1L]").is_err());
+    assert!(NbtTag::from_snbt("\"open").is_err());
+    assert!(NbtTag::from_snbt("").is_err());
+}
+
+#[test]
+fn test_depth_limit() {
+    let text = "[".repeat(100_000);
+    let error = NbtTag::from_snbt(&text).unwrap_err();
+
+    assert!(matches!(error.kind, NbtErrorKind::LimitExceeded { limit: "nesting depth", .. }));
+}
+
+#[test]
+fn test_bigtest_round_trip() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/bigtest.nbt");
+    let tag = FileParser::new(path, ReadMode::EntireFile, FileType::Nbt).parse().unwrap();
+
+    // Only the root name is lost, SNBT has no syntax for it
+    let mut copy = NbtTag::from_snbt(&tag.to_snbt()).unwrap();
+    if let NbtTag::Compound(compound) = &mut copy {
+        compound.name = tag.compound_as_ref().unwrap().name.clone();
+    }
+    assert!(same(&copy, &tag));
+
+    let mut copy = NbtTag::from_snbt(&tag.to_snbt_pretty()).unwrap();
+    if let NbtTag::Compound(compound) = &mut copy {
+        compound.name = tag.compound_as_ref().unwrap().name.clone();
+    }
+    assert!(same(&copy, &tag));
+}
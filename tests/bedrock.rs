@@ -0,0 +1,75 @@
+//! Tests reading a Bedrock Edition `level.dat`: an 8-byte header followed by
+//! uncompressed little-endian NBT.
+use fastnbt::generic_bin::{detect_bedrock_header, FileType, GenericBinFile};
+use fastnbt::NbtFlavor;
+use std::path::PathBuf;
+
+fn bedrock_level_dat() -> Vec<u8> {
+    let nbt: Vec<u8> = vec![
+        10, 0, 0,
+            8, 9, 0, b'L', b'e', b'v', b'e', b'l', b'N', b'a', b'm', b'e', 5, 0, b'W', b'o', b'r', b'l', b'd',
+            3, 14, 0, b'S', b't', b'o', b'r', b'a', b'g', b'e', b'V', b'e', b'r', b's', b'i', b'o', b'n', 10, 0, 0, 0,
+        0,
+    ];
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&10i32.to_le_bytes());
+    data.extend_from_slice(&(nbt.len() as u32).to_le_bytes());
+    data.extend_from_slice(&nbt);
+    data
+}
+
+#[test]
+fn bedrock_level_dat_header() {
+    let data = bedrock_level_dat();
+    assert_eq!(detect_bedrock_header(&data), Some(10));
+
+    // The length in the header must match the data
+    assert_eq!(detect_bedrock_header(&data[..data.len() - 1]), None);
+}
+
+#[test]
+fn bedrock_level_dat_read() {
+    let path = std::env::temp_dir().join(format!("fastnbt_bedrock_{}_level.dat", std::process::id()));
+    std::fs::write(&path, bedrock_level_dat()).unwrap();
+
+    let bin_file = GenericBinFile::new(path.clone(), FileType::Nbt).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(bin_file.get_flavor(), NbtFlavor::Bedrock);
+    assert_eq!(bin_file.get_bedrock_version(), Some(10));
+
+    let root = bin_file.to_tag_compound().unwrap();
+    assert_eq!(root.values.get("LevelName").unwrap().string().unwrap().value, "World");
+    assert_eq!(root.values.get("StorageVersion").unwrap().int().unwrap().value, 10);
+}
+
+#[test]
+fn java_file_is_not_bedrock() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/bigtest.nbt");
+
+    let bin_file = GenericBinFile::new(path, FileType::Nbt).unwrap();
+
+    assert_eq!(bin_file.get_flavor(), NbtFlavor::Java);
+    assert_eq!(bin_file.get_bedrock_version(), None);
+    assert!(bin_file.to_tag_compound().is_ok());
+}
+
+#[test]
+fn bedrock_level_dat_write_keeps_header() {
+    let dir = std::env::temp_dir().join(format!("fastnbt_bedrock_write_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("level.dat");
+    std::fs::write(&path, bedrock_level_dat()).unwrap();
+
+    let bin_file = GenericBinFile::new(path.clone(), FileType::Nbt).unwrap();
+    let root = bin_file.to_tag_compound().unwrap();
+    bin_file.write(&path, &root).unwrap();
+
+    let written = std::fs::read(&path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(detect_bedrock_header(&written), Some(10));
+    assert_eq!(GenericBinFile::from_raw_data(written).to_tag_compound().unwrap(), root);
+}
@@ -11,9 +11,8 @@ fn bigtest_json() {
     let path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     
     let mut path_res = path.clone();
-    let mut path_out = path.clone();
     path_res.push("tests/resources/bigtest.nbt");
-    path_out.push("tests/outputs/output_bt.json");
+    let path_out = std::env::temp_dir().join(format!("fastnbt_bigtest_json_{}.json", std::process::id()));
 
     let mc_world = McWorldDescriptor::new(path_res);
 
@@ -30,6 +29,7 @@ fn bigtest_json() {
     // let deserialized_nbt: String = serde_json::from_reader(reader).unwrap();
     let json_mc_world = McWorldDescriptor::new(path_out.clone()).unwrap();
     let c_json = json_mc_world.tag_compounds_list.get(0).unwrap().values.clone();
+    fs::remove_file(&path_out).unwrap();
 
     //read the content from a json file and populate the NbtTagCompound
     //let c_json = nbt_tag::NbtTagCompound::from_json("tests/outputs/output_bt.json").unwrap();
@@ -0,0 +1,50 @@
+//! Exports `bigtest.nbt` and the chunks of a test region as typed JSON and checks that
+//! importing the files gives back the same trees.
+use fastnbt::generic_bin::{FileType, GenericBinFile};
+use fastnbt::nbt_tag::NbtTagCompound;
+use fastnbt::region::RegionFile;
+use fastnbt::JsonFormat;
+use std::path::PathBuf;
+
+/// `test` names the temporary file, so tests running in parallel use their own files.
+fn json_round_trip(test: &str, compound: &NbtTagCompound, format: JsonFormat) -> NbtTagCompound {
+    let path = std::env::temp_dir().join(format!("fastnbt_{}_{:?}_{}.json", test, format, std::process::id()));
+    compound.to_json_with_format(&path, format).unwrap();
+    let imported = NbtTagCompound::from_json(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    imported
+}
+
+#[test]
+fn bigtest_typed_json_round_trip() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/bigtest.nbt");
+    let root = GenericBinFile::new(path, FileType::Nbt).unwrap().to_tag_compound().unwrap();
+
+    assert_eq!(json_round_trip("bigtest_typed", &root, JsonFormat::Typed), root);
+}
+
+#[test]
+fn bigtest_plain_json_keeps_values() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/bigtest.nbt");
+    let root = GenericBinFile::new(path, FileType::Nbt).unwrap().to_tag_compound().unwrap();
+
+    let imported = json_round_trip("bigtest_plain", &root, JsonFormat::Plain);
+
+    assert_eq!(imported.values.keys().collect::<Vec<_>>(), root.values.keys().collect::<Vec<_>>());
+    assert_eq!(imported.values["intTest"].int().unwrap().value, 2147483647);
+    assert_eq!(imported.values["longTest"].long().unwrap().value, 9223372036854775807);
+    assert_eq!(imported.values["stringTest"].string().unwrap().value, root.values["stringTest"].string().unwrap().value);
+}
+
+#[test]
+fn region_chunks_typed_json_round_trip() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/test_world/r.-1.0.mca");
+
+    let chunks = RegionFile::new(path).unwrap().to_compounds_list().unwrap();
+    for chunk in chunks.iter().take(8) {
+        assert_eq!(&json_round_trip("region_chunks_typed", chunk, JsonFormat::Typed), chunk);
+    }
+}
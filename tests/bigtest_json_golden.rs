@@ -0,0 +1,19 @@
+//! Checks that the JSON export of `bigtest.nbt` is stable: keys keep the order of the
+//! file, so the output matches the golden file `tests/outputs/output_bt.json`.
+use fastnbt::McWorldDescriptor;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn bigtest_json_matches_golden_file() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let path_res = path.join("tests/resources/bigtest.nbt");
+    let path_golden = path.join("tests/outputs/output_bt.json");
+    let path_out = std::env::temp_dir().join(format!("fastnbt_bigtest_{}.json", std::process::id()));
+
+    McWorldDescriptor::new(path_res).unwrap().to_json(&path_out).unwrap();
+    let json = fs::read_to_string(&path_out).unwrap();
+    fs::remove_file(&path_out).unwrap();
+
+    assert_eq!(json, fs::read_to_string(path_golden).unwrap());
+}
@@ -1,6 +1,7 @@
 //! Tests the library using the `bigtest.nbt` file provided
 //! by Mojang.
-use fastnbt::McWorldDescriptor;
+use fastnbt::nbt_tag::NbtTagType;
+use fastnbt::{McWorldDescriptor, TagPredicate};
 use std::path::PathBuf;
 
 #[test]
@@ -36,3 +37,25 @@ fn bigtest() {
     );
     assert_eq!(c.get("shortTest").unwrap().short().unwrap().value, 32767); */
 }
+
+#[test]
+fn bigtest_predicate_search() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/bigtest.nbt");
+    let mc_world = McWorldDescriptor::new(path).unwrap();
+
+    let found = mc_world.search(&TagPredicate::new().with_type(NbtTagType::String).with_regex("^Compound").unwrap());
+    let mut paths: Vec<_> = found.iter().map(|(_, found)| found.path.clone()).collect();
+    paths.sort();
+    assert_eq!(paths, vec!["\"listTest (compound)\"[0].name", "\"listTest (compound)\"[1].name"]);
+
+    let found = mc_world.search(&TagPredicate::new().with_key("name").with_regex("^(Eggbert|Hampus)$").unwrap());
+    let mut paths: Vec<_> = found.iter().map(|(_, found)| found.path.clone()).collect();
+    paths.sort();
+    assert_eq!(paths, vec!["\"nested compound test\".egg.name", "\"nested compound test\".ham.name"]);
+
+    // Compounds in lists are found too, nested compounds below a match are not searched
+    let (found, compounds) = mc_world.search_compound("nested compound test", false);
+    assert!(found);
+    assert_eq!(compounds.len(), 1);
+}
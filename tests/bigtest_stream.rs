@@ -0,0 +1,26 @@
+//! Tests the streaming parser using the `bigtest.nbt` file provided
+//! by Mojang.
+use fastnbt::file_parser::{FileParser, ReadMode};
+use fastnbt::generic_bin::FileType;
+use std::path::PathBuf;
+
+#[test]
+fn bigtest_stream() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/bigtest.nbt");
+
+    let streamed = FileParser::new(path.clone(), ReadMode::Stream, FileType::Nbt).parse().unwrap();
+    let entire = FileParser::new(path, ReadMode::EntireFile, FileType::Nbt).parse().unwrap();
+
+    // Confirm that values are correct
+    let c = streamed.compound().unwrap().values;
+    assert_eq!(c.get("intTest").unwrap().int().unwrap().value, 2147483647);
+    assert_eq!(c.get("longTest").unwrap().long().unwrap().value, 9223372036854775807);
+    assert_eq!(
+        c.get("byteArrayTest (the first 1000 values of (n*n*255+n*7)%100, starting with n=0 (0, 62, 34, 16, 8, ...))")
+            .unwrap().byte_array().unwrap().values.len(),
+        1000
+    );
+
+    assert_eq!(entire.compound().unwrap().values.len(), c.len());
+}
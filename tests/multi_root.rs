@@ -0,0 +1,68 @@
+//! Files holding several roots back to back, e.g. appended NBT logs.
+use fastnbt::generic_bin::{compress_into, CompressionType, FileType, GenericBinFile};
+use fastnbt::nbt;
+use fastnbt::nbt_tag::NbtTag;
+use fastnbt::nbt_writer::NbtWriter;
+use std::fs;
+
+#[test]
+fn to_compounds_list_returns_every_root() {
+    let mut first = nbt!{ "event": "join", "tick": 10 };
+    first.name = "log".to_string();
+    let second = nbt!{ "event": "leave", "tick": 42 };
+
+    let mut writer = NbtWriter::new(Vec::new());
+    writer.write_compound(&first).unwrap();
+    writer.write_compound(&second).unwrap();
+    let mut data = writer.into_inner();
+    // A string root "motd"
+    data.extend_from_slice(&[8, 0, 4, b'm', b'o', b't', b'd', 0, 2, b'h', b'i']);
+
+    let path = std::env::temp_dir().join(format!("fastnbt_multi_root_{}.nbt", std::process::id()));
+    compress_into(fs::File::create(&path).unwrap(), &data, CompressionType::Gzip, 6).unwrap();
+
+    let bin_file = GenericBinFile::new(path.clone(), FileType::Nbt).unwrap();
+    let compounds = bin_file.to_compounds_list().unwrap();
+
+    assert_eq!(compounds.len(), 3);
+    assert_eq!(compounds[0], first);
+    assert_eq!(compounds[1].get_i32("tick"), Some(42));
+    assert_eq!(compounds[2].get("motd"), Some(&NbtTag::from("hi")));
+    // The first root alone, as before
+    assert_eq!(bin_file.to_tag_compound().unwrap(), first);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn to_compounds_list_reads_every_gzip_member() {
+    let first = nbt!{ "event": "join" };
+    let second = nbt!{ "event": "leave" };
+
+    let path = std::env::temp_dir().join(format!("fastnbt_multi_member_{}.nbt", std::process::id()));
+    let mut file = fs::File::create(&path).unwrap();
+    for compound in [&first, &second] {
+        let mut writer = NbtWriter::new(Vec::new());
+        writer.write_compound(compound).unwrap();
+        compress_into(&mut file, &writer.into_inner(), CompressionType::Gzip, 6).unwrap();
+    }
+
+    let compounds = GenericBinFile::new(path.clone(), FileType::Nbt).unwrap().to_compounds_list().unwrap();
+
+    assert_eq!(compounds, vec![first, second]);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn to_compounds_list_ignores_trailing_zero_padding() {
+    let compound = nbt!{ "event": "join" };
+    let mut writer = NbtWriter::new(Vec::new());
+    writer.write_compound(&compound).unwrap();
+    let mut data = writer.into_inner();
+    data.resize(data.len() + 64, 0);
+
+    let bin_file = GenericBinFile::from_raw_data(data);
+
+    assert_eq!(bin_file.to_compounds_list().unwrap(), vec![compound.clone()]);
+    assert_eq!(bin_file.to_tag_compound().unwrap(), compound);
+}
@@ -0,0 +1,45 @@
+//! Runs NBT path queries on the chunks of a test region and checks them against a manual
+//! walk of the same trees.
+use fastnbt::region::RegionFile;
+use fastnbt::NbtPath;
+use std::path::PathBuf;
+
+#[test]
+fn region_palette_query() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/test_world/r.-1.0.mca");
+    let chunks = RegionFile::new(path).unwrap().to_compounds_list().unwrap();
+
+    let query = NbtPath::parse("sections[].block_states.palette[{Name:\"minecraft:air\"}].Name").unwrap();
+    for chunk in chunks.iter().take(8) {
+        let mut expected = Vec::new();
+        for (i, section) in chunk.values["sections"].list_as_ref().unwrap().values.iter().enumerate() {
+            let block_states = section.compound_as_ref().unwrap().values["block_states"].compound_as_ref().unwrap();
+            for (j, entry) in block_states.values["palette"].list_as_ref().unwrap().values.iter().enumerate() {
+                if entry.compound_as_ref().unwrap().values["Name"].string().unwrap().value == "minecraft:air" {
+                    expected.push(format!("sections[{}].block_states.palette[{}].Name", i, j));
+                }
+            }
+        }
+
+        let found: Vec<_> = query.query_compound(chunk).into_iter().map(|m| m.path).collect();
+        assert!(!found.is_empty());
+        assert_eq!(found, expected);
+    }
+}
+
+#[test]
+fn region_negative_index_query() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/test_world/r.-1.0.mca");
+    let chunks = RegionFile::new(path).unwrap().to_compounds_list().unwrap();
+
+    let query = NbtPath::parse("sections[-1].Y").unwrap();
+    let chunk = &chunks[0];
+    let sections = &chunk.values["sections"].list_as_ref().unwrap().values;
+
+    let found = query.query_compound(chunk);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].path, format!("sections[{}].Y", sections.len() - 1));
+    assert_eq!(*found[0].tag, sections[sections.len() - 1].compound_as_ref().unwrap().values["Y"]);
+}
@@ -0,0 +1,30 @@
+//! Checks the built-in schemas against the vanilla and Litematica files of the test
+//! resources, which are valid.
+use fastnbt::nbt_schema;
+use fastnbt::McWorldDescriptor;
+use std::path::PathBuf;
+
+fn violations(file: &str, schema: &fastnbt::NbtSchema) -> Vec<String> {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push(file);
+    let descriptor = McWorldDescriptor::new(path).unwrap();
+
+    descriptor.validate(schema).iter().map(|(index, violation)| format!("[{}] {}", index, violation)).collect()
+}
+
+#[test]
+fn region_chunks_are_valid() {
+    assert_eq!(violations("tests/resources/test_world/r.-1.0.mca", &nbt_schema::chunk()), Vec::<String>::new());
+}
+
+#[test]
+fn litematic_is_valid() {
+    assert_eq!(violations("tests/resources/test.litematic", &nbt_schema::litematic()), Vec::<String>::new());
+    assert_eq!(violations("tests/resources/20069.litematic", &nbt_schema::litematic()), Vec::<String>::new());
+}
+
+#[test]
+fn litematic_is_not_a_chunk() {
+    let found = violations("tests/resources/test.litematic", &nbt_schema::chunk());
+    assert!(found.contains(&"[0] sections: missing".to_string()));
+}
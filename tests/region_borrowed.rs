@@ -0,0 +1,32 @@
+//! Compares the borrowed representation of every chunk with the owned one.
+use fastnbt::file_parser;
+use fastnbt::nbt_tag::NbtTag;
+use fastnbt::nbt_tag_ref;
+use fastnbt::region::RegionFile;
+use std::path::PathBuf;
+
+#[test]
+fn region_borrowed_chunks() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/test_world/r.-1.0.mca");
+
+    let region = RegionFile::new(path).unwrap();
+    let mut chunks = 0;
+
+    for index in (0..region.get_chunks_num()).filter(|index| region.has_chunk(*index)) {
+        let bytes = region.read_and_decompress_chunk(index).unwrap();
+        let borrowed = nbt_tag_ref::parse(&bytes).unwrap();
+        let owned = file_parser::parse_bytes(&bytes).unwrap();
+
+        let sections = borrowed.get("sections").unwrap().as_list().unwrap();
+        let owned_sections = owned.compound_as_ref().unwrap().values.get("sections").unwrap().list_as_ref().unwrap();
+        assert_eq!(sections.len(), owned_sections.values.len());
+
+        let name = &owned.compound_as_ref().unwrap().name;
+        let copy = NbtTag::Compound(borrowed.to_owned_compound(name));
+        assert_eq!(serde_json::to_value(copy).unwrap(), serde_json::to_value(owned).unwrap());
+        chunks += 1;
+    }
+
+    assert!(chunks > 0);
+}
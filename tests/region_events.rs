@@ -0,0 +1,39 @@
+//! Reads chunk coordinates with the event reader and compares them with the parsed tree.
+use fastnbt::event_reader::{NbtEvent, NbtScalar};
+use fastnbt::region::RegionFile;
+use std::path::PathBuf;
+
+#[test]
+fn region_chunk_events() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/test_world/r.-1.0.mca");
+
+    let region = RegionFile::new(path).unwrap();
+    let mut positions = Vec::new();
+
+    for index in (0..region.get_chunks_num()).filter(|index| region.has_chunk(*index)) {
+        let mut events = region.chunk_events(index).unwrap();
+        let (mut x, mut z) = (None, None);
+
+        while let Some(event) = events.next_event().unwrap() {
+            match event {
+                NbtEvent::Value(name, NbtScalar::Int(value)) if events.depth() == 1 && name == "xPos" => x = Some(value),
+                NbtEvent::Value(name, NbtScalar::Int(value)) if events.depth() == 1 && name == "zPos" => z = Some(value),
+                // Nothing else is needed from nested tags
+                NbtEvent::StartCompound(_) | NbtEvent::StartList(..) | NbtEvent::StartArray(..) if events.depth() > 1 => events.skip_container().unwrap(),
+                _ => {}
+            }
+        }
+
+        positions.push((x.unwrap(), z.unwrap()));
+    }
+
+    let chunks = region.to_compounds_list().unwrap();
+    let expected: Vec<(i32, i32)> = chunks.iter().map(|chunk| (
+        chunk.values.get("xPos").unwrap().int().unwrap().value,
+        chunk.values.get("zPos").unwrap().int().unwrap().value,
+    )).collect();
+
+    assert!(!positions.is_empty());
+    assert_eq!(positions, expected);
+}
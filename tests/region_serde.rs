@@ -0,0 +1,73 @@
+//! Deserializes region chunks into plain Rust structs.
+use fastnbt::region::RegionFile;
+use fastnbt::LongArray;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Deserialize)]
+struct Chunk {
+    #[serde(rename = "xPos")]
+    x_pos: i32,
+    #[serde(rename = "zPos")]
+    z_pos: i32,
+    sections: Vec<Section>,
+}
+
+#[derive(Deserialize)]
+struct Section {
+    #[serde(rename = "Y")]
+    y: i8,
+    block_states: Option<BlockStates>,
+}
+
+#[derive(Deserialize)]
+struct BlockStates {
+    palette: Vec<BlockState>,
+    data: Option<LongArray>,
+}
+
+#[derive(Deserialize)]
+struct BlockState {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Properties")]
+    properties: Option<HashMap<String, String>>,
+}
+
+#[test]
+fn region_chunks_as_structs() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/test_world/r.-1.0.mca");
+
+    let region = RegionFile::new(path).unwrap();
+    let owned = region.to_compounds_list().unwrap();
+    let mut found_properties = false;
+
+    let indices = (0..region.get_chunks_num()).filter(|index| region.has_chunk(*index));
+    for (index, expected) in indices.zip(owned.iter()) {
+        let bytes = region.read_and_decompress_chunk(index).unwrap();
+        let chunk: Chunk = fastnbt::from_bytes(&bytes).unwrap();
+
+        assert_eq!(chunk.x_pos, expected.values.get("xPos").unwrap().int().unwrap().value);
+        assert_eq!(chunk.z_pos, expected.values.get("zPos").unwrap().int().unwrap().value);
+
+        let expected_sections = expected.values.get("sections").unwrap().list_as_ref().unwrap();
+        assert_eq!(chunk.sections.len(), expected_sections.values.len());
+
+        for (section, expected) in chunk.sections.iter().zip(expected_sections.values.iter()) {
+            let expected = expected.compound_as_ref().unwrap();
+            assert_eq!(section.y, expected.values.get("Y").unwrap().byte().unwrap().value);
+
+            let block_states = section.block_states.as_ref().unwrap();
+            assert!(!block_states.palette.is_empty());
+            assert!(block_states.palette.iter().all(|state| state.name.starts_with("minecraft:")));
+            found_properties |= block_states.palette.iter().any(|state| state.properties.is_some());
+
+            let expected_data = expected.values.get("block_states").unwrap().compound_as_ref().unwrap().values.get("data");
+            assert_eq!(block_states.data.as_ref().map(|data| data.len()), expected_data.map(|data| data.long_array_as_ref().unwrap().values.len()));
+        }
+    }
+
+    assert!(found_properties);
+}
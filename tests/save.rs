@@ -0,0 +1,94 @@
+//! Saves parsed files back to disk and reads them again.
+use fastnbt::generic_bin::{CompressionType, FileType, GenericBinFile};
+use fastnbt::nbt_tag::{NbtTag, NbtTagInt};
+use std::fs;
+use std::path::PathBuf;
+
+/// A fresh, empty directory for the files written by one test.
+fn output_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("fastnbt_save_{}_{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn bigtest_path() -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/bigtest.nbt");
+    path
+}
+
+#[test]
+fn save_compound_with_each_compression() {
+    let dir = output_dir("compression");
+    let root = GenericBinFile::new(bigtest_path(), FileType::Nbt).unwrap().to_tag_compound().unwrap();
+
+    for compression in [CompressionType::Gzip, CompressionType::Zlib, CompressionType::Uncompressed] {
+        let path = dir.join("bigtest.nbt");
+        root.save(&path, compression, 9).unwrap();
+
+        let saved = GenericBinFile::new(path, FileType::Nbt).unwrap();
+        assert_eq!(saved.get_compression(), compression);
+        assert_eq!(saved.to_tag_compound().unwrap(), root);
+    }
+
+    // Only the saved file is left, no temporary file
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn write_keeps_original_compression() {
+    let dir = output_dir("keep");
+    let bin_file = GenericBinFile::new(bigtest_path(), FileType::Nbt).unwrap();
+    assert_eq!(bin_file.get_compression(), CompressionType::Gzip);
+
+    let mut root = bin_file.to_tag_compound().unwrap();
+    root.values.insert("intTest".to_string(), NbtTag::Int(NbtTagInt::new(42)));
+
+    let path = dir.join("edited.nbt");
+    bin_file.write(&path, &root).unwrap();
+
+    let saved = GenericBinFile::new(path, FileType::Nbt).unwrap();
+    assert_eq!(saved.get_compression(), CompressionType::Gzip);
+    assert_eq!(saved.to_tag_compound().unwrap().values.get("intTest").unwrap().int().unwrap().value, 42);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn write_replaces_existing_file() {
+    let dir = output_dir("replace");
+    let path = dir.join("level.dat");
+    fs::copy(bigtest_path(), &path).unwrap();
+
+    let bin_file = GenericBinFile::new(path.clone(), FileType::Nbt).unwrap();
+    let mut root = bin_file.to_tag_compound().unwrap();
+    root.values.shift_remove("intTest");
+    bin_file.write(&path, &root).unwrap();
+
+    let saved = GenericBinFile::new(path, FileType::Nbt).unwrap().to_tag_compound().unwrap();
+    assert!(!saved.values.contains_key("intTest"));
+    assert_eq!(saved, root);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn concurrent_saves_of_the_same_path() {
+    let dir = output_dir("concurrent");
+    let path = dir.join("bigtest.nbt");
+    let root = GenericBinFile::new(bigtest_path(), FileType::Nbt).unwrap().to_tag_compound().unwrap();
+
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| root.save(&path, CompressionType::Gzip, 6).unwrap());
+        }
+    });
+
+    assert_eq!(GenericBinFile::new(path, FileType::Nbt).unwrap().to_tag_compound().unwrap(), root);
+    // No temporary file is left behind
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+    fs::remove_dir_all(dir).unwrap();
+}
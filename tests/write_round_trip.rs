@@ -0,0 +1,67 @@
+//! Writes parsed files back with `NbtWriter` and checks that parsing the output gives the
+//! same tree, and the same bytes, for `bigtest.nbt` and the chunks of a test region.
+use fastnbt::file_parser::{parse_bytes, FileParser, ReadMode};
+use fastnbt::generic_bin::{FileType, GenericBinFile};
+use fastnbt::nbt_tag::{self, NbtTag};
+use fastnbt::region::RegionFile;
+use std::path::PathBuf;
+
+#[test]
+fn bigtest_write_round_trip() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/bigtest.nbt");
+
+    let root = FileParser::new(path, ReadMode::EntireFile, FileType::Nbt).parse().unwrap().compound().unwrap();
+
+    let mut bytes = Vec::new();
+    nbt_tag::write(&mut bytes, &root).unwrap();
+
+    assert_eq!(parse_bytes(&bytes).unwrap(), NbtTag::Compound(root));
+}
+
+#[test]
+fn region_chunks_write_round_trip() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/test_world/r.-1.0.mca");
+
+    let chunks = RegionFile::new(path).unwrap().to_compounds_list().unwrap();
+    assert!(!chunks.is_empty());
+
+    for chunk in chunks {
+        let mut bytes = Vec::new();
+        nbt_tag::write(&mut bytes, &chunk).unwrap();
+
+        assert_eq!(parse_bytes(&bytes).unwrap(), NbtTag::Compound(chunk));
+    }
+}
+
+#[test]
+fn bigtest_write_is_byte_identical() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/bigtest.nbt");
+
+    let original = GenericBinFile::new(path, FileType::Nbt).unwrap().try_decode_data().unwrap();
+    let root = parse_bytes(&original).unwrap().compound().unwrap();
+
+    let mut bytes = Vec::new();
+    nbt_tag::write(&mut bytes, &root).unwrap();
+
+    assert_eq!(bytes, original);
+}
+
+#[test]
+fn region_chunks_write_is_byte_identical() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resources/test_world/r.-1.0.mca");
+
+    let region = RegionFile::new(path).unwrap();
+    for index in (0..region.get_chunks_num()).filter(|index| region.has_chunk(*index)) {
+        let original = region.read_and_decompress_chunk(index).unwrap();
+        let chunk = parse_bytes(&original).unwrap().compound().unwrap();
+
+        let mut bytes = Vec::new();
+        nbt_tag::write(&mut bytes, &chunk).unwrap();
+
+        assert!(bytes == original, "chunk {} differs", index);
+    }
+}